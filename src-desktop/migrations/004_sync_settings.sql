@@ -0,0 +1,13 @@
+-- ====================================================================
+-- SYNC SETTINGS MIGRATION (SQLite)
+-- Persists sync engine settings (background sync cadence, conflict
+-- strategy, target workspace) so they survive app restarts
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL, -- JSON value
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+PRAGMA user_version = 4;
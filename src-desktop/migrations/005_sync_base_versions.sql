@@ -0,0 +1,20 @@
+-- ====================================================================
+-- SYNC BASE VERSIONS MIGRATION (SQLite)
+-- Stores the last-synced copy of every record (the common ancestor)
+-- so conflicts can be resolved with a three-way, field-level merge
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_base_versions (
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    data TEXT NOT NULL, -- JSON object as last seen on the server
+    sync_version INTEGER NOT NULL DEFAULT 0,
+    synced_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (table_name, record_id)
+);
+
+-- Conflicts keep the ancestor and the fields that changed on both sides
+ALTER TABLE sync_conflicts ADD COLUMN base_data TEXT; -- JSON object
+ALTER TABLE sync_conflicts ADD COLUMN field_conflicts TEXT; -- JSON array of FieldConflict
+
+PRAGMA user_version = 5;
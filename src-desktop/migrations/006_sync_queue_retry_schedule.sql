@@ -0,0 +1,13 @@
+-- ====================================================================
+-- SYNC QUEUE RETRY SCHEDULE MIGRATION (SQLite)
+-- Failed queue items are retried with exponential backoff; items that
+-- exhaust their attempts move to the DEAD_LETTER status
+-- ====================================================================
+
+ALTER TABLE sync_queue ADD COLUMN next_retry_at TEXT; -- when a FAILED item may be retried
+
+CREATE INDEX IF NOT EXISTS idx_sync_queue_status_next_retry ON sync_queue(status, next_retry_at);
+
+-- Status values: PENDING, IN_PROGRESS, COMPLETED, FAILED, DEAD_LETTER
+
+PRAGMA user_version = 6;
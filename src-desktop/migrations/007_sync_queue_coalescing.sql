@@ -0,0 +1,18 @@
+-- ====================================================================
+-- SYNC QUEUE COALESCING MIGRATION (SQLite)
+-- Queued changes are folded per (table_name, record_id) on enqueue, so
+-- the lookup of a record's waiting change needs its own index. A change
+-- that has been sent may have been applied by the server even when the
+-- push failed, so it is never folded; new edits queue behind it.
+-- ====================================================================
+
+CREATE INDEX IF NOT EXISTS idx_sync_queue_record ON sync_queue(table_name, record_id, status);
+
+ALTER TABLE sync_queue ADD COLUMN last_attempted_at TEXT; -- NULL until the change is first sent
+
+-- Anything that may already have been sent counts as attempted
+UPDATE sync_queue
+SET last_attempted_at = created_at
+WHERE status IN ('IN_PROGRESS', 'FAILED', 'DEAD_LETTER') OR retry_count > 0;
+
+PRAGMA user_version = 7;
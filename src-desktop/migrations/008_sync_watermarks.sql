@@ -0,0 +1,17 @@
+-- ====================================================================
+-- SYNC WATERMARKS MIGRATION (SQLite)
+-- Pulls are paginated with an opaque server cursor; the cursor of the
+-- last committed page is stored per table so an interrupted pull
+-- resumes where it stopped
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_watermarks (
+    table_name TEXT PRIMARY KEY,
+    last_sync_timestamp TEXT NOT NULL,
+    last_sync_version INTEGER NOT NULL DEFAULT 0,
+    record_count INTEGER NOT NULL DEFAULT 0,
+    cursor TEXT, -- opaque, only meaningful to the server
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+PRAGMA user_version = 8;
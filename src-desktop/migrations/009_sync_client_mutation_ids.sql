@@ -0,0 +1,17 @@
+-- ====================================================================
+-- SYNC CLIENT MUTATION IDS MIGRATION (SQLite)
+-- Every queued change carries a stable client mutation ID that is sent
+-- with the push, so the server can ignore a change it already applied
+-- when a retry follows a timed-out request
+-- ====================================================================
+
+ALTER TABLE sync_queue ADD COLUMN client_mutation_id TEXT;
+
+-- Changes queued before this migration get an ID of their own
+UPDATE sync_queue
+SET client_mutation_id = lower(hex(randomblob(16)))
+WHERE client_mutation_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sync_queue_client_mutation_id ON sync_queue(client_mutation_id);
+
+PRAGMA user_version = 9;
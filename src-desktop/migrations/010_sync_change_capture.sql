@@ -0,0 +1,68 @@
+-- ====================================================================
+-- SYNC CHANGE CAPTURE MIGRATION (SQLite)
+-- Triggers on every syncable table log local writes to sync_change_log,
+-- which the sync queue folds into its pending changes. Writes applied
+-- by the sync engine itself set sync_capture_state.suppressed for the
+-- length of their transaction so they don't echo back to the server.
+-- The triggers are generated from the sync table registry at startup.
+-- The *_update_sync triggers from 003 get the same suppression check, so
+-- rows the engine writes from the server aren't marked dirty.
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_capture_state (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    suppressed INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO sync_capture_state (id, suppressed) VALUES (1, 0);
+
+CREATE TABLE IF NOT EXISTS sync_change_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    operation TEXT NOT NULL, -- Insert, Update or Delete
+    captured_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ', 'now'))
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_change_log_record ON sync_change_log(table_name, record_id);
+
+DROP TRIGGER IF EXISTS companies_update_sync;
+CREATE TRIGGER IF NOT EXISTS companies_update_sync
+AFTER UPDATE ON companies
+WHEN COALESCE((SELECT suppressed FROM sync_capture_state WHERE id = 1), 0) = 0
+BEGIN
+    UPDATE companies 
+    SET 
+        updated_at = datetime('now'),
+        is_dirty = 1,
+        sync_version = sync_version + 1
+    WHERE id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS people_update_sync;
+CREATE TRIGGER IF NOT EXISTS people_update_sync
+AFTER UPDATE ON people
+WHEN COALESCE((SELECT suppressed FROM sync_capture_state WHERE id = 1), 0) = 0
+BEGIN
+    UPDATE people 
+    SET 
+        updated_at = datetime('now'),
+        is_dirty = 1,
+        sync_version = sync_version + 1
+    WHERE id = NEW.id;
+END;
+
+DROP TRIGGER IF EXISTS actions_update_sync;
+CREATE TRIGGER IF NOT EXISTS actions_update_sync
+AFTER UPDATE ON actions
+WHEN COALESCE((SELECT suppressed FROM sync_capture_state WHERE id = 1), 0) = 0
+BEGIN
+    UPDATE actions 
+    SET 
+        updated_at = datetime('now'),
+        is_dirty = 1,
+        sync_version = sync_version + 1
+    WHERE id = NEW.id;
+END;
+
+PRAGMA user_version = 10;
@@ -0,0 +1,31 @@
+-- ====================================================================
+-- SYNC HYBRID LOGICAL CLOCKS MIGRATION (SQLite)
+-- Every local write is stamped with a hybrid logical clock (wall time,
+-- counter and device ID) so last-write-wins compares when records were
+-- actually written instead of independently incremented sync_version
+-- counters. The capture triggers advance sync_clock and stamp the row
+-- in sync_record_clocks; pulled records keep the clock they were sent.
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_clock (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    device_id TEXT NOT NULL,
+    wall_ms INTEGER NOT NULL DEFAULT 0, -- Milliseconds since the Unix epoch
+    counter INTEGER NOT NULL DEFAULT 0
+);
+
+INSERT OR IGNORE INTO sync_clock (id, device_id, wall_ms, counter)
+VALUES (1, lower(hex(randomblob(8))), 0, 0);
+
+CREATE TABLE IF NOT EXISTS sync_record_clocks (
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    hlc TEXT NOT NULL, -- "<wall_ms>:<counter>:<device_id>", zero-padded so it sorts as text
+    PRIMARY KEY (table_name, record_id)
+);
+
+-- Conflicts keep both clocks so last-write-wins can order them
+ALTER TABLE sync_conflicts ADD COLUMN local_clock TEXT;
+ALTER TABLE sync_conflicts ADD COLUMN remote_clock TEXT;
+
+PRAGMA user_version = 11;
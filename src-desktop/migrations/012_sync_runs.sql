@@ -0,0 +1,34 @@
+-- ====================================================================
+-- SYNC RUN HISTORY MIGRATION (SQLite)
+-- One row per sync_workspace / sync_table run with its duration,
+-- record counts, bytes transferred, errors and what triggered it, so
+-- slow or failing syncs can be diagnosed after the fact. Rows older
+-- than the retention window are pruned as new runs are recorded.
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    triggered_by TEXT NOT NULL, -- manual, background or reconnect
+    workspace_id TEXT NOT NULL,
+    table_name TEXT, -- NULL for a full workspace sync
+    status TEXT NOT NULL, -- success, partial, failed or cancelled
+    started_at TEXT NOT NULL,
+    finished_at TEXT NOT NULL,
+    duration_ms INTEGER NOT NULL DEFAULT 0,
+    records_processed INTEGER NOT NULL DEFAULT 0,
+    records_created INTEGER NOT NULL DEFAULT 0,
+    records_updated INTEGER NOT NULL DEFAULT 0,
+    records_deleted INTEGER NOT NULL DEFAULT 0,
+    conflicts_found INTEGER NOT NULL DEFAULT 0,
+    request_bytes INTEGER NOT NULL DEFAULT 0,
+    request_bytes_uncompressed INTEGER NOT NULL DEFAULT 0,
+    response_bytes INTEGER NOT NULL DEFAULT 0,
+    response_bytes_uncompressed INTEGER NOT NULL DEFAULT 0,
+    error_count INTEGER NOT NULL DEFAULT 0,
+    errors TEXT -- JSON array of the first errors reported
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_runs_started_at ON sync_runs(started_at);
+CREATE INDEX IF NOT EXISTS idx_sync_runs_workspace ON sync_runs(workspace_id, started_at);
+
+PRAGMA user_version = 12;
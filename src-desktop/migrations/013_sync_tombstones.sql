@@ -0,0 +1,42 @@
+-- ====================================================================
+-- SYNC TOMBSTONES MIGRATION (SQLite)
+-- Every delete, local or pulled, leaves a tombstone with its version
+-- and hybrid timestamp, so an older remote write that arrives after
+-- the delete can't resurrect the record. Tables with a deleted_at
+-- column keep the soft-deleted row alongside its tombstone. Once every
+-- device has pulled past a tombstone and the retention period is over,
+-- the tombstone and its soft-deleted row are purged.
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_tombstones (
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    deleted_at TEXT NOT NULL,
+    sync_version INTEGER NOT NULL DEFAULT 0, -- Version of the delete; remote writes up to it are stale
+    hlc TEXT, -- Hybrid timestamp of the delete, when known
+    acknowledged_at TEXT, -- When the server had the delete; NULL while a local delete is unpushed
+    PRIMARY KEY (table_name, record_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_sync_tombstones_acknowledged_at ON sync_tombstones(acknowledged_at);
+
+-- Rows soft-deleted before tombstones existed are treated as already on the server
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'workspaces', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM workspaces WHERE deleted_at IS NOT NULL;
+
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'companies', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM companies WHERE deleted_at IS NOT NULL;
+
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'people', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM people WHERE deleted_at IS NOT NULL;
+
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'actions', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM actions WHERE deleted_at IS NOT NULL;
+
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'ai_conversations', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM ai_conversations WHERE deleted_at IS NOT NULL;
+
+INSERT OR IGNORE INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, acknowledged_at)
+SELECT 'chronicle_reports', id, deleted_at, COALESCE(sync_version, 0), deleted_at FROM chronicle_reports WHERE deleted_at IS NOT NULL;
+
+PRAGMA user_version = 13;
@@ -0,0 +1,24 @@
+-- ====================================================================
+-- SYNC PULL FAILURES MIGRATION (SQLite)
+-- A pulled change that fails to apply (e.g. its parent row hasn't
+-- arrived yet) is set aside here instead of rolling back its page, so
+-- the rest of the page commits and the cursor moves on. Set-aside
+-- changes are retried at the start of every pull of their table and
+-- removed once they apply or a newer version of the record does.
+-- ====================================================================
+
+CREATE TABLE IF NOT EXISTS sync_pull_failures (
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    operation TEXT NOT NULL,
+    data TEXT NOT NULL, -- The pulled record as JSON
+    sync_version INTEGER NOT NULL DEFAULT 0,
+    last_modified TEXT NOT NULL,
+    error_message TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    first_failed_at TEXT NOT NULL,
+    last_failed_at TEXT NOT NULL,
+    PRIMARY KEY (table_name, record_id)
+);
+
+PRAGMA user_version = 14;
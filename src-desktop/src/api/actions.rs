@@ -105,9 +105,6 @@ pub async fn get_actions(
     // Get database connections
     let sqlite_pool = db_manager.get_sqlite_pool().await
         .map_err(|e| format!("Failed to get SQLite connection: {}", e))?;
-    
-    let pg_pool = db_manager.get_pg_pool().await
-        .map_err(|e| format!("Failed to get PostgreSQL connection: {}", e))?;
 
     // Extract filter parameters
     let page = filters.page.unwrap_or(1);
@@ -174,7 +171,7 @@ pub async fn get_actions(
         );
         
         let counts_rows = sqlx::query(&counts_query)
-            .fetch_all(&*sqlite_pool)
+            .fetch_all(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to fetch action counts: {}", e))?;
 
@@ -225,7 +222,7 @@ pub async fn get_actions(
     // Get total count
     let count_query = format!("SELECT COUNT(*) as count FROM actions {}", where_clause);
     let total_count: i32 = sqlx::query_scalar(&count_query)
-        .fetch_one(&*sqlite_pool)
+        .fetch_one(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to count actions: {}", e))?;
 
@@ -238,7 +235,7 @@ pub async fn get_actions(
     let actions_rows = sqlx::query(&actions_query)
         .bind(limit)
         .bind(offset)
-        .fetch_all(&*sqlite_pool)
+        .fetch_all(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch actions: {}", e))?;
 
@@ -261,8 +258,8 @@ pub async fn get_actions(
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
             deleted_at: row.get("deleted_at"),
-            needs_sync: row.get("needs_sync"),
-            last_sync_at: row.get("last_sync_at"),
+            needs_sync: row.get("is_dirty"),
+            last_sync_at: row.get("last_synced_at"),
         };
         actions.push(action);
     }
@@ -314,7 +311,7 @@ pub async fn create_action(
                 "SELECT EXISTS(SELECT 1 FROM companies WHERE id = ? AND deleted_at IS NULL)"
             )
             .bind(company_id)
-            .fetch_one(&*sqlite_pool)
+            .fetch_one(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to validate company: {}", e))?;
             
@@ -335,7 +332,7 @@ pub async fn create_action(
                 "SELECT EXISTS(SELECT 1 FROM people WHERE id = ? AND deleted_at IS NULL)"
             )
             .bind(person_id)
-            .fetch_one(&*sqlite_pool)
+            .fetch_one(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to validate person: {}", e))?;
             
@@ -363,7 +360,7 @@ pub async fn create_action(
         INSERT INTO actions (
             id, type, subject, description, outcome, scheduled_at, completed_at,
             status, priority, company_id, person_id, user_id, workspace_id,
-            created_at, updated_at, needs_sync
+            created_at, updated_at, is_dirty
         ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
     "#;
 
@@ -383,15 +380,15 @@ pub async fn create_action(
         .bind("default") // TODO: Get from auth context
         .bind(&now)
         .bind(&now)
-        .bind(true) // needs_sync
-        .execute(&*sqlite_pool)
+        .bind(true) // is_dirty
+        .execute(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to create action: {}", e))?;
 
     // Fetch the created action
     let action_row = sqlx::query("SELECT * FROM actions WHERE id = ?")
         .bind(&action_id)
-        .fetch_one(&*sqlite_pool)
+        .fetch_one(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch created action: {}", e))?;
 
@@ -412,8 +409,8 @@ pub async fn create_action(
         created_at: action_row.get("created_at"),
         updated_at: action_row.get("updated_at"),
         deleted_at: action_row.get("deleted_at"),
-        needs_sync: action_row.get("needs_sync"),
-        last_sync_at: action_row.get("last_sync_at"),
+        needs_sync: action_row.get("is_dirty"),
+        last_sync_at: action_row.get("last_synced_at"),
     };
 
     Ok(ActionResponse {
@@ -442,7 +439,7 @@ pub async fn update_action(
     // Get existing action to check current values
     let existing_action_row = sqlx::query("SELECT * FROM actions WHERE id = ? AND deleted_at IS NULL")
         .bind(&action_id)
-        .fetch_optional(&*sqlite_pool)
+        .fetch_optional(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch existing action: {}", e))?;
 
@@ -468,7 +465,7 @@ pub async fn update_action(
                 "SELECT EXISTS(SELECT 1 FROM companies WHERE id = ? AND deleted_at IS NULL)"
             )
             .bind(company_id)
-            .fetch_one(&*sqlite_pool)
+            .fetch_one(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to validate company: {}", e))?;
             
@@ -492,7 +489,7 @@ pub async fn update_action(
                 "SELECT EXISTS(SELECT 1 FROM people WHERE id = ? AND deleted_at IS NULL)"
             )
             .bind(person_id)
-            .fetch_one(&*sqlite_pool)
+            .fetch_one(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to validate person: {}", e))?;
             
@@ -561,10 +558,10 @@ pub async fn update_action(
         });
     }
 
-    // Always update updated_at and needs_sync
+    // Always update updated_at and is_dirty
     update_fields.push("updated_at = ?");
     params.push(Box::new(chrono::Utc::now().to_rfc3339()));
-    update_fields.push("needs_sync = ?");
+    update_fields.push("is_dirty = ?");
     params.push(Box::new(true));
 
     // Add action_id as the last parameter
@@ -576,14 +573,14 @@ pub async fn update_action(
     );
 
     sqlx::query(&update_query)
-        .execute(&*sqlite_pool)
+        .execute(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to update action: {}", e))?;
 
     // Fetch the updated action
     let action_row = sqlx::query("SELECT * FROM actions WHERE id = ?")
         .bind(&action_id)
-        .fetch_one(&*sqlite_pool)
+        .fetch_one(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch updated action: {}", e))?;
 
@@ -604,8 +601,8 @@ pub async fn update_action(
         created_at: action_row.get("created_at"),
         updated_at: action_row.get("updated_at"),
         deleted_at: action_row.get("deleted_at"),
-        needs_sync: action_row.get("needs_sync"),
-        last_sync_at: action_row.get("last_sync_at"),
+        needs_sync: action_row.get("is_dirty"),
+        last_sync_at: action_row.get("last_synced_at"),
     };
 
     Ok(ActionResponse {
@@ -636,7 +633,7 @@ pub async fn delete_action(
         "SELECT EXISTS(SELECT 1 FROM actions WHERE id = ? AND deleted_at IS NULL)"
     )
     .bind(&action_id)
-    .fetch_one(&*sqlite_pool)
+    .fetch_one(&sqlite_pool)
     .await
     .map_err(|e| format!("Failed to check action existence: {}", e))?;
 
@@ -655,18 +652,18 @@ pub async fn delete_action(
         // Hard delete - permanently remove from database
         sqlx::query("DELETE FROM actions WHERE id = ?")
             .bind(&action_id)
-            .execute(&*sqlite_pool)
+            .execute(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to delete action: {}", e))?;
     } else {
         // Soft delete - set deleted_at timestamp
         let now = chrono::Utc::now().to_rfc3339();
-        sqlx::query("UPDATE actions SET deleted_at = ?, updated_at = ?, needs_sync = ? WHERE id = ?")
+        sqlx::query("UPDATE actions SET deleted_at = ?, updated_at = ?, is_dirty = ? WHERE id = ?")
             .bind(&now)
             .bind(&now)
             .bind(true)
             .bind(&action_id)
-            .execute(&*sqlite_pool)
+            .execute(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to delete action: {}", e))?;
     }
@@ -696,7 +693,7 @@ pub async fn get_action_by_id(
 
     let action_row = sqlx::query("SELECT * FROM actions WHERE id = ? AND deleted_at IS NULL")
         .bind(&action_id)
-        .fetch_optional(&*sqlite_pool)
+        .fetch_optional(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch action: {}", e))?;
 
@@ -719,8 +716,8 @@ pub async fn get_action_by_id(
                 created_at: row.get("created_at"),
                 updated_at: row.get("updated_at"),
                 deleted_at: row.get("deleted_at"),
-                needs_sync: row.get("needs_sync"),
-                last_sync_at: row.get("last_sync_at"),
+                needs_sync: row.get("is_dirty"),
+                last_sync_at: row.get("last_synced_at"),
             };
 
             Ok(ActionResponse {
@@ -125,7 +125,7 @@ pub async fn get_chronicle_reports(
     let report_rows = sqlx::query(reports_query)
         .bind(&workspace_id)
         .bind(limit)
-        .fetch_all(&*sqlite_pool)
+        .fetch_all(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch chronicle reports: {}", e))?;
 
@@ -138,8 +138,8 @@ pub async fn get_chronicle_reports(
             SELECT 
                 id,
                 report_id,
-                shared_with,
-                shared_at,
+                COALESCE(allowed_emails, '') AS shared_with,
+                created_at AS shared_at,
                 expires_at
             FROM chronicle_shares
             WHERE report_id = ?
@@ -147,7 +147,7 @@ pub async fn get_chronicle_reports(
         
         let share_rows = sqlx::query(shares_query)
             .bind(&report_id)
-            .fetch_all(&*sqlite_pool)
+            .fetch_all(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to fetch chronicle shares: {}", e))?;
 
@@ -251,14 +251,14 @@ pub async fn create_chronicle_report(
         .bind(&user_id)
         .bind(&now)
         .bind(&now)
-        .execute(&*sqlite_pool)
+        .execute(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to create chronicle report: {}", e))?;
 
     // Fetch the created report
     let report_row = sqlx::query("SELECT * FROM chronicle_reports WHERE id = ?")
         .bind(&report_id)
-        .fetch_one(&*sqlite_pool)
+        .fetch_one(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch created chronicle report: {}", e))?;
 
@@ -267,8 +267,8 @@ pub async fn create_chronicle_report(
         SELECT 
             id,
             report_id,
-            shared_with,
-            shared_at,
+            COALESCE(allowed_emails, '') AS shared_with,
+            created_at AS shared_at,
             expires_at
         FROM chronicle_shares
         WHERE report_id = ?
@@ -276,7 +276,7 @@ pub async fn create_chronicle_report(
     
     let share_rows = sqlx::query(shares_query)
         .bind(&report_id)
-        .fetch_all(&*sqlite_pool)
+        .fetch_all(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch chronicle shares: {}", e))?;
 
@@ -329,7 +329,7 @@ pub async fn get_chronicle_report_by_id(
 
     let report_row = sqlx::query("SELECT * FROM chronicle_reports WHERE id = ? AND deleted_at IS NULL")
         .bind(&report_id)
-        .fetch_optional(&*sqlite_pool)
+        .fetch_optional(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch chronicle report: {}", e))?;
 
@@ -340,8 +340,8 @@ pub async fn get_chronicle_report_by_id(
                 SELECT 
                     id,
                     report_id,
-                    shared_with,
-                    shared_at,
+                    COALESCE(allowed_emails, '') AS shared_with,
+                    created_at AS shared_at,
                     expires_at
                 FROM chronicle_shares
                 WHERE report_id = ?
@@ -349,7 +349,7 @@ pub async fn get_chronicle_report_by_id(
             
             let share_rows = sqlx::query(shares_query)
                 .bind(&report_id)
-                .fetch_all(&*sqlite_pool)
+                .fetch_all(&sqlite_pool)
                 .await
                 .map_err(|e| format!("Failed to fetch chronicle shares: {}", e))?;
 
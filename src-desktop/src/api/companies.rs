@@ -8,7 +8,6 @@
 
 use crate::database::models::*;
 use crate::database_init::get_database_manager;
-use crate::sync::SyncQueue;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 
@@ -485,15 +484,6 @@ pub async fn create_company(
         .await
         .map_err(|e| format!("Failed to create company: {}", e))?;
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool.clone());
-    sync_queue.enqueue_change(
-        "companies",
-        &company_id,
-        crate::sync::models::SyncOperation::Insert,
-        Some(serde_json::to_string(&request).unwrap_or_default()),
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     // Fetch the created company
     let company = get_company_by_id(&sqlite_pool, &company_id).await?;
     
@@ -644,15 +634,6 @@ pub async fn update_company(
         return Err("Company not found".to_string());
     }
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool.clone());
-    sync_queue.enqueue_change(
-        "companies",
-        &company_id,
-        crate::sync::models::SyncOperation::Update,
-        Some(serde_json::to_string(&request).unwrap_or_default()),
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     // Fetch the updated company
     let company = get_company_by_id(&sqlite_pool, &company_id).await?;
     
@@ -700,15 +681,6 @@ pub async fn delete_company(company_id: String) -> Result<CompaniesApiResponse,
         return Err("Company not found".to_string());
     }
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool);
-    sync_queue.enqueue_change(
-        "companies",
-        &company_id,
-        crate::sync::models::SyncOperation::Delete,
-        None,
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     println!("✅ [COMPANIES API] Deleted company: {}", company_id);
     
     Ok(CompaniesApiResponse {
@@ -8,7 +8,6 @@
 
 use crate::database::models::*;
 use crate::database_init::get_database_manager;
-use crate::sync::SyncQueue;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 
@@ -458,15 +457,6 @@ pub async fn create_person(
         .await
         .map_err(|e| format!("Failed to create person: {}", e))?;
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool.clone());
-    sync_queue.enqueue_change(
-        "people",
-        &person_id,
-        crate::sync::models::SyncOperation::Insert,
-        Some(serde_json::to_string(&request).unwrap_or_default()),
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     // Fetch the created person
     let person = get_person_by_id(&sqlite_pool, &person_id).await?;
     
@@ -621,15 +611,6 @@ pub async fn update_person(
         return Err("Person not found".to_string());
     }
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool.clone());
-    sync_queue.enqueue_change(
-        "people",
-        &person_id,
-        crate::sync::models::SyncOperation::Update,
-        Some(serde_json::to_string(&request).unwrap_or_default()),
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     // Fetch the updated person
     let person = get_person_by_id(&sqlite_pool, &person_id).await?;
     
@@ -677,15 +658,6 @@ pub async fn delete_person(person_id: String) -> Result<PeopleApiResponse, Strin
         return Err("Person not found".to_string());
     }
     
-    // Add to sync queue
-    let sync_queue = SyncQueue::new(sqlite_pool);
-    sync_queue.enqueue_change(
-        "people",
-        &person_id,
-        crate::sync::models::SyncOperation::Delete,
-        None,
-    ).await.map_err(|e| format!("Failed to queue sync: {}", e))?;
-    
     println!("✅ [PEOPLE API] Deleted person: {}", person_id);
     
     Ok(PeopleApiResponse {
@@ -157,7 +157,7 @@ pub async fn get_speedrun_data(
     let speedrun_rows = sqlx::query(speedrun_query)
         .bind(&workspace_id)
         .bind(limit)
-        .fetch_all(&*sqlite_pool)
+        .fetch_all(&sqlite_pool)
         .await
         .map_err(|e| format!("Failed to fetch speedrun data: {}", e))?;
 
@@ -174,7 +174,7 @@ pub async fn get_speedrun_data(
                 u.last_name,
                 u.name,
                 u.email
-            FROM co_sellers cs
+            FROM person_co_sellers cs
             JOIN users u ON cs.user_id = u.id
             WHERE cs.person_id = ? AND u.id != ?
         "#;
@@ -182,7 +182,7 @@ pub async fn get_speedrun_data(
         let co_sellers_rows = sqlx::query(co_sellers_query)
             .bind(&person_id)
             .bind(&user_id)
-            .fetch_all(&*sqlite_pool)
+            .fetch_all(&sqlite_pool)
             .await
             .map_err(|e| format!("Failed to fetch co-sellers: {}", e))?;
 
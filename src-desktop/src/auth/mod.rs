@@ -154,7 +154,8 @@ async fn authenticate_user(email: &str, password: &str, db_manager: &HybridDatab
     let sqlite_pool = db_manager.get_sqlite_pool().await
         .map_err(|e| format!("Failed to get SQLite connection: {}", e))?;
     
-    let pg_pool = db_manager.get_pg_pool().await
+    // `None` while the app is running from the SQLite cache only
+    let pg_pool = db_manager.get_postgres_pool().await
         .map_err(|e| format!("Failed to get PostgreSQL connection: {}", e))?;
 
     // Try to authenticate against PostgreSQL first (online)
@@ -176,56 +177,58 @@ async fn authenticate_user(email: &str, password: &str, db_manager: &HybridDatab
     "#;
 
     // Try PostgreSQL first
-    if let Ok(user_row) = sqlx::query(user_query)
-        .bind(email)
-        .fetch_optional(&*pg_pool)
-        .await
-    {
-        if let Some(row) = user_row {
-            // TODO: Validate password hash
-            // For now, we'll assume authentication is successful
+    if let Some(pg_pool) = &pg_pool {
+        if let Ok(user_row) = sqlx::query(user_query)
+            .bind(email)
+            .fetch_optional(pg_pool)
+            .await
+        {
+            if let Some(row) = user_row {
+                // TODO: Validate password hash
+                // For now, we'll assume authentication is successful
             
-            // Get all workspaces for this user
-            let workspaces_query = r#"
-                SELECT 
-                    w.id,
-                    w.name,
-                    wm.role
-                FROM workspaces w
-                JOIN workspace_members wm ON w.id = wm.workspace_id
-                WHERE wm.user_id = ? AND w.deleted_at IS NULL
-            "#;
+                // Get all workspaces for this user
+                let workspaces_query = r#"
+                    SELECT 
+                        w.id,
+                        w.name,
+                        wm.role
+                    FROM workspaces w
+                    JOIN workspace_members wm ON w.id = wm.workspace_id
+                    WHERE wm.user_id = ? AND w.deleted_at IS NULL
+                "#;
             
-            let workspace_rows = sqlx::query(workspaces_query)
-                .bind(&row.get::<String, _>("id"))
-                .fetch_all(&*pg_pool)
-                .await
-                .map_err(|e| format!("Failed to fetch workspaces: {}", e))?;
+                let workspace_rows = sqlx::query(workspaces_query)
+                    .bind(&row.get::<String, _>("id"))
+                    .fetch_all(pg_pool)
+                    .await
+                    .map_err(|e| format!("Failed to fetch workspaces: {}", e))?;
 
-            let mut workspaces = Vec::new();
-            for ws_row in workspace_rows {
-                workspaces.push(Workspace {
-                    id: ws_row.get("id"),
-                    name: ws_row.get("name"),
-                    role: ws_row.get("role"),
+                let mut workspaces = Vec::new();
+                for ws_row in workspace_rows {
+                    workspaces.push(Workspace {
+                        id: ws_row.get("id"),
+                        name: ws_row.get("name"),
+                        role: ws_row.get("role"),
+                    });
+                }
+
+                return Ok(AuthUser {
+                    id: row.get("id"),
+                    email: row.get("email"),
+                    name: row.get("name"),
+                    display_name: row.get("display_name"),
+                    active_workspace_id: row.get("active_workspace_id"),
+                    workspaces,
                 });
             }
-
-            return Ok(AuthUser {
-                id: row.get("id"),
-                email: row.get("email"),
-                name: row.get("name"),
-                display_name: row.get("display_name"),
-                active_workspace_id: row.get("active_workspace_id"),
-                workspaces,
-            });
         }
     }
 
     // If PostgreSQL fails, try SQLite (offline mode)
     if let Ok(user_row) = sqlx::query(user_query)
         .bind(email)
-        .fetch_optional(&*sqlite_pool)
+        .fetch_optional(&sqlite_pool)
         .await
     {
         if let Some(row) = user_row {
@@ -244,7 +247,7 @@ async fn authenticate_user(email: &str, password: &str, db_manager: &HybridDatab
             
             let workspace_rows = sqlx::query(workspaces_query)
                 .bind(&row.get::<String, _>("id"))
-                .fetch_all(&*sqlite_pool)
+                .fetch_all(&sqlite_pool)
                 .await
                 .map_err(|e| format!("Failed to fetch workspaces: {}", e))?;
 
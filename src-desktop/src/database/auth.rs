@@ -250,7 +250,7 @@ impl HybridDatabaseManager {
                     }
                 }
             }
-            DatabaseConnection::_Hybrid { sqlite } => {
+            DatabaseConnection::Hybrid { sqlite } => {
                 println!("🔐 [AUTH] Using hybrid database authentication");
                 self.authenticate_from_sqlite(sqlite, email, password).await
             }
@@ -1,4 +1,4 @@
-use super::models::{DesktopLead, DesktopContact, DatabaseConnection, HybridDatabaseManager};
+use super::models::{DesktopLead, DesktopContact, DatabaseConnection, HybridDatabaseManager, Person};
 use sqlx::Row;
 
 #[derive(Debug, Clone)]
@@ -28,6 +28,75 @@ pub struct CompanyData {
     pub priority: String,
 }
 
+/// The person fields the CRM lists show, read the same way from
+/// PostgreSQL and from the SQLite cache
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct PersonRow {
+    id: String,
+    full_name: String,
+    job_title: Option<String>,
+    department: Option<String>,
+    seniority: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+    linkedin_url: Option<String>,
+    company: Option<String>,
+    status: Option<String>,
+    source: Option<String>,
+    notes: Option<String>,
+    main_seller_id: Option<String>,
+    buyer_group_role: Option<String>,
+    created_at: String,
+    updated_at: String,
+    last_synced_at: Option<String>,
+    is_dirty: bool,
+}
+
+/// `PersonRow` columns in the cache, selected from `people p`
+const CACHED_PERSON_COLUMNS: &str = r#"
+    p.id, p.full_name, p.job_title, p.department, p.seniority, p.email, p.phone,
+    p.linkedin_url, COALESCE(c.name, p.current_company) AS company, p.status, p.source,
+    p.notes, p.main_seller_id, p.buyer_group_role, p.created_at, p.updated_at,
+    p.last_synced_at, COALESCE(p.is_dirty, 0) AS is_dirty
+"#;
+
+impl PersonRow {
+    fn into_person(self, workspace_id: &str) -> Person {
+        let (first_name, last_name) = split_name(&self.full_name);
+
+        Person {
+            id: self.id,
+            workspace_id: workspace_id.to_string(),
+            first_name,
+            last_name,
+            full_name: self.full_name,
+            job_title: self.job_title,
+            department: self.department,
+            seniority: self.seniority,
+            email: self.email,
+            phone: self.phone,
+            linkedin_url: self.linkedin_url,
+            current_company: self.company,
+            status: self.status,
+            source: self.source,
+            notes: self.notes,
+            main_seller_id: self.main_seller_id,
+            buyer_group_role: self.buyer_group_role,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            last_synced_at: self.last_synced_at,
+            is_dirty: self.is_dirty,
+            ..Default::default()
+        }
+    }
+}
+
+/// Split a full name into the first and last names `people` requires
+pub(super) fn split_name(name: &str) -> (String, String) {
+    let mut names = name.splitn(2, ' ');
+    (names.next().unwrap_or_default().to_string(), names.next().unwrap_or_default().to_string())
+}
+
 impl HybridDatabaseManager {
     /// Get leads for a specific workspace and user
     pub async fn get_leads(&self, workspace_id: &str, user_id_or_name: &str) -> Result<Vec<DesktopLead>, Box<dyn std::error::Error + Send + Sync>> {
@@ -40,7 +109,7 @@ impl HybridDatabaseManager {
                 let query_sql = r#"
                     SELECT l.id, l."fullName" as name, l."jobTitle" as title, l.email, l.phone, 
                            l.company, l.status, l.source, l.notes, l."createdAt", l."updatedAt",
-                           l."assignedUserId", l."estimatedValue", l."customFields"
+                           l."assignedUserId", l."customFields"
                     FROM leads l
                     WHERE l."workspaceId" = $1 
                     AND l."assignedUserId" = $2
@@ -64,42 +133,61 @@ impl HybridDatabaseManager {
                         .and_then(|r| r.as_str())
                         .map(|s| s.to_string());
                     
-                    DesktopLead {
+                    PersonRow {
                         id: row.try_get::<String, _>("id").unwrap_or_else(|_| format!("unknown-{}", index)),
-                        name: row.try_get::<String, _>("name").unwrap_or_else(|_| "Unknown Name".to_string()),
-                        title: row.try_get::<Option<String>, _>("title").unwrap_or_default(),
+                        full_name: row.try_get::<String, _>("name").unwrap_or_else(|_| "Unknown Name".to_string()),
+                        job_title: row.try_get::<Option<String>, _>("title").unwrap_or_default(),
+                        department: None,
+                        seniority: None,
                         email: row.try_get::<Option<String>, _>("email").unwrap_or_default(),
                         phone: row.try_get::<Option<String>, _>("phone").unwrap_or_default(),
+                        linkedin_url: None,
                         company: row.try_get::<Option<String>, _>("company").unwrap_or_default(),
-                        status: row.try_get::<String, _>("status").unwrap_or_else(|_| "new".to_string()),
+                        status: Some(row.try_get::<String, _>("status").unwrap_or_else(|_| "new".to_string())),
                         source: row.try_get::<Option<String>, _>("source").unwrap_or_default(),
                         notes: row.try_get::<Option<String>, _>("notes").unwrap_or_default(),
-                        last_action_date: None,
-                        next_action_date: None,
-                        value: row.try_get::<Option<f64>, _>("estimatedValue")
-                            .ok()
-                            .flatten()
-                            .map(|v| format!("${:.2}", v)),
-                        probability: None,
-                        assigned_to: row.try_get::<Option<String>, _>("assignedUserId").unwrap_or_default(),
+                        main_seller_id: row.try_get::<Option<String>, _>("assignedUserId").unwrap_or_default(),
+                        buyer_group_role,
                         created_at: row.try_get::<chrono::NaiveDateTime, _>("createdAt")
                             .map(|dt| dt.and_utc().to_rfc3339())
                             .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339()),
                         updated_at: row.try_get::<chrono::NaiveDateTime, _>("updatedAt")
                             .map(|dt| dt.and_utc().to_rfc3339())
                             .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339()),
-                        needs_sync: false,
-                        last_sync_at: Some(chrono::Utc::now().to_rfc3339()),
-                        cloud_updated_at: None,
-                        buyer_group_role,
+                        last_synced_at: Some(chrono::Utc::now().to_rfc3339()),
+                        is_dirty: false,
                     }
+                    .into_person(workspace_id)
                 }).collect();
                 
                 println!("✅ [CRM] Found {} leads", leads.len());
                 Ok(leads)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(vec![])
+            DatabaseConnection::Hybrid { sqlite } => {
+                // Offline: leads are the cached people still in the LEAD stage
+                let query_sql = format!(r#"
+                    SELECT {}
+                    FROM people p
+                    LEFT JOIN companies c ON p.company_id = c.id
+                    WHERE p.workspace_id = ?
+                    AND p.main_seller_id = ?
+                    AND p.status = 'LEAD'
+                    AND p.deleted_at IS NULL
+                    ORDER BY p.created_at DESC
+                    LIMIT 410
+                "#, CACHED_PERSON_COLUMNS);
+
+                let leads: Vec<DesktopLead> = sqlx::query_as::<_, PersonRow>(&query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id_or_name)
+                    .fetch_all(sqlite)
+                    .await?
+                    .into_iter()
+                    .map(|row| row.into_person(workspace_id))
+                    .collect();
+
+                println!("✅ [CRM] Found {} cached leads", leads.len());
+                Ok(leads)
             }
         }
     }
@@ -167,8 +255,56 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Found {} companies", companies.len());
                 Ok(companies)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(vec![])
+            DatabaseConnection::Hybrid { sqlite } => {
+                let query_sql = r#"
+                    SELECT id, name, COALESCE(website, domain) as website, industry, size, revenue,
+                           city, state, description, email, phone, main_seller_id,
+                           created_at, updated_at, is_dirty
+                    FROM companies
+                    WHERE workspace_id = ?
+                    AND main_seller_id = ?
+                    AND deleted_at IS NULL
+                    ORDER BY created_at DESC
+                    LIMIT 100
+                "#;
+
+                let rows = sqlx::query(query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id_or_name)
+                    .fetch_all(sqlite)
+                    .await?;
+
+                let companies: Vec<serde_json::Value> = rows.into_iter().map(|row| {
+                    let location = [
+                        row.try_get::<Option<String>, _>("city").unwrap_or_default(),
+                        row.try_get::<Option<String>, _>("state").unwrap_or_default(),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                    serde_json::json!({
+                        "id": row.try_get::<String, _>("id").unwrap_or_default(),
+                        "name": row.try_get::<String, _>("name").unwrap_or_default(),
+                        "domain": row.try_get::<Option<String>, _>("website").unwrap_or_default(),
+                        "industry": row.try_get::<Option<String>, _>("industry").unwrap_or_default(),
+                        "size": row.try_get::<Option<String>, _>("size").unwrap_or_default(),
+                        "revenue": row.try_get::<Option<f64>, _>("revenue").unwrap_or_default(),
+                        "location": location,
+                        "description": row.try_get::<Option<String>, _>("description").unwrap_or_default(),
+                        "email": row.try_get::<Option<String>, _>("email").unwrap_or_default(),
+                        "phone": row.try_get::<Option<String>, _>("phone").unwrap_or_default(),
+                        "assignedUserId": row.try_get::<Option<String>, _>("main_seller_id").unwrap_or_default(),
+                        "createdAt": row.try_get::<String, _>("created_at").unwrap_or_default(),
+                        "updatedAt": row.try_get::<String, _>("updated_at").unwrap_or_default(),
+                        "needsSync": row.try_get::<bool, _>("is_dirty").unwrap_or(false),
+                    })
+                }).collect();
+
+                println!("✅ [CRM] Found {} cached companies", companies.len());
+                Ok(companies)
             }
         }
     }
@@ -201,37 +337,59 @@ impl HybridDatabaseManager {
                     .await?;
                 
                 let contacts: Vec<DesktopContact> = rows.into_iter().map(|row| {
-                    DesktopContact {
+                    PersonRow {
                         id: row.try_get::<String, _>("id").unwrap_or_default(),
-                        name: row.try_get::<String, _>("name").unwrap_or_default(),
-                        title: row.try_get::<Option<String>, _>("title").unwrap_or_default(),
+                        full_name: row.try_get::<String, _>("name").unwrap_or_default(),
+                        job_title: row.try_get::<Option<String>, _>("title").unwrap_or_default(),
+                        department: row.try_get::<Option<String>, _>("department").unwrap_or_default(),
+                        seniority: row.try_get::<Option<String>, _>("seniority").unwrap_or_default(),
                         email: row.try_get::<Option<String>, _>("email").unwrap_or_default(),
                         phone: row.try_get::<Option<String>, _>("phone").unwrap_or_default(),
+                        linkedin_url: row.try_get::<Option<String>, _>("linkedinUrl").unwrap_or_default(),
                         company: row.try_get::<Option<String>, _>("company_name").unwrap_or_default(),
-                        department: row.try_get::<Option<String>, _>("department").unwrap_or_default(),
-                        location: None,
+                        status: row.try_get::<Option<String>, _>("status").unwrap_or_default(),
+                        source: None,
                         notes: row.try_get::<Option<String>, _>("notes").unwrap_or_default(),
-                        relationship: row.try_get::<Option<String>, _>("seniority").unwrap_or_default(),
-                        linkedin_url: row.try_get::<Option<String>, _>("linkedinUrl").unwrap_or_default(),
-                        account_id: None,
-                        assigned_to: row.try_get::<Option<String>, _>("assignedUserId").unwrap_or_default(),
+                        main_seller_id: row.try_get::<Option<String>, _>("assignedUserId").unwrap_or_default(),
+                        buyer_group_role: None,
                         created_at: row.try_get::<chrono::NaiveDateTime, _>("createdAt")
                             .map(|dt| dt.and_utc().to_rfc3339())
                             .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339()),
                         updated_at: row.try_get::<chrono::NaiveDateTime, _>("updatedAt")
                             .map(|dt| dt.and_utc().to_rfc3339())
                             .unwrap_or_else(|_| chrono::Utc::now().to_rfc3339()),
-                        needs_sync: false,
-                        last_sync_at: Some(chrono::Utc::now().to_rfc3339()),
-                        cloud_updated_at: None,
+                        last_synced_at: Some(chrono::Utc::now().to_rfc3339()),
+                        is_dirty: false,
                     }
+                    .into_person(workspace_id)
                 }).collect();
                 
                 println!("✅ [CRM] Found {} contacts", contacts.len());
                 Ok(contacts)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(vec![])
+            DatabaseConnection::Hybrid { sqlite } => {
+                let query_sql = format!(r#"
+                    SELECT {}
+                    FROM people p
+                    LEFT JOIN companies c ON p.company_id = c.id
+                    WHERE p.workspace_id = ?
+                    AND p.main_seller_id = ?
+                    AND p.deleted_at IS NULL
+                    ORDER BY p.created_at DESC
+                    LIMIT 100
+                "#, CACHED_PERSON_COLUMNS);
+
+                let contacts: Vec<DesktopContact> = sqlx::query_as::<_, PersonRow>(&query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id_or_name)
+                    .fetch_all(sqlite)
+                    .await?
+                    .into_iter()
+                    .map(|row| row.into_person(workspace_id))
+                    .collect();
+
+                println!("✅ [CRM] Found {} cached contacts", contacts.len());
+                Ok(contacts)
             }
         }
     }
@@ -297,8 +455,54 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Found {} opportunities", opportunities.len());
                 Ok(opportunities)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(vec![])
+            DatabaseConnection::Hybrid { sqlite } => {
+                // Opportunities are embedded in the cached companies
+                let query_sql = r#"
+                    SELECT id, name, description, opportunity_amount, expected_close_date,
+                           opportunity_probability, opportunity_stage, main_seller_id,
+                           created_at, updated_at, is_dirty
+                    FROM companies
+                    WHERE workspace_id = ?
+                    AND main_seller_id = ?
+                    AND status = 'OPPORTUNITY'
+                    AND deleted_at IS NULL
+                    ORDER BY created_at DESC
+                    LIMIT 100
+                "#;
+
+                let rows = sqlx::query(query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id_or_name)
+                    .fetch_all(sqlite)
+                    .await?;
+
+                let opportunities: Vec<serde_json::Value> = rows.into_iter().map(|row| {
+                    let id = row.try_get::<String, _>("id").unwrap_or_default();
+                    let name = row.try_get::<String, _>("name").unwrap_or_default();
+
+                    serde_json::json!({
+                        "id": id,
+                        "name": name,
+                        "description": row.try_get::<Option<String>, _>("description").unwrap_or_default(),
+                        "amount": row.try_get::<Option<f64>, _>("opportunity_amount").unwrap_or_default(),
+                        "expectedCloseDate": row.try_get::<Option<String>, _>("expected_close_date").unwrap_or_default().unwrap_or_default(),
+                        "probability": row.try_get::<Option<f64>, _>("opportunity_probability").unwrap_or_default().unwrap_or(0.0),
+                        "stage": row.try_get::<Option<String>, _>("opportunity_stage").unwrap_or_default().unwrap_or_default(),
+                        "contactId": serde_json::Value::Null,
+                        "accountId": id,
+                        "assignedUserId": row.try_get::<Option<String>, _>("main_seller_id").unwrap_or_default(),
+                        "accountName": name,
+                        "contactName": serde_json::Value::Null,
+                        "engagementScore": serde_json::Value::Null,
+                        "riskScore": serde_json::Value::Null,
+                        "createdAt": row.try_get::<String, _>("created_at").unwrap_or_default(),
+                        "updatedAt": row.try_get::<String, _>("updated_at").unwrap_or_default(),
+                        "needsSync": row.try_get::<bool, _>("is_dirty").unwrap_or(false),
+                    })
+                }).collect();
+
+                println!("✅ [CRM] Found {} cached opportunities", opportunities.len());
+                Ok(opportunities)
             }
         }
     }
@@ -336,10 +540,7 @@ impl HybridDatabaseManager {
                     .execute(postgres)
                     .await?;
                 
-                // Split name into first and last name
-                let name_parts: Vec<&str> = lead_data.name.splitn(2, ' ').collect();
-                let first_name = name_parts.get(0).unwrap_or(&"").to_string();
-                let last_name = name_parts.get(1).unwrap_or(&"").to_string();
+                let (first_name, last_name) = split_name(&lead_data.name);
                 
                 let insert_sql = r#"
                     INSERT INTO people (id, "fullName", "firstName", "lastName", "jobTitle", company, email, phone, status, source, notes, priority, "workspaceId", "assignedUserId", "createdAt", "updatedAt")
@@ -362,34 +563,83 @@ impl HybridDatabaseManager {
                     .execute(postgres)
                     .await?;
                 
-                let lead = DesktopLead {
+                let lead = PersonRow {
                     id: lead_id,
-                    name: lead_data.name.clone(),
-                    title: Some(lead_data.title.clone()),
+                    full_name: lead_data.name.clone(),
+                    job_title: Some(lead_data.title.clone()),
+                    department: None,
+                    seniority: None,
                     email: Some(lead_data.email.clone()),
                     phone: Some(lead_data.phone.clone()),
+                    linkedin_url: None,
                     company: Some(lead_data.company.clone()),
-                    status: "new".to_string(),
+                    status: Some("LEAD".to_string()),
                     source: Some("manual".to_string()),
                     notes: Some("".to_string()),
-                    last_action_date: None,
-                    next_action_date: None,
-                    value: None,
-                    probability: None,
-                    assigned_to: Some(lead_data.user_id.clone()),
+                    main_seller_id: Some(lead_data.user_id.clone()),
+                    buyer_group_role: None,
                     created_at: now.to_rfc3339(),
                     updated_at: now.to_rfc3339(),
-                    needs_sync: false,
-                    last_sync_at: Some(now.to_rfc3339()),
-                    cloud_updated_at: None,
-                    buyer_group_role: None,
-                };
+                    last_synced_at: Some(now.to_rfc3339()),
+                    is_dirty: false,
+                }
+                .into_person(&lead_data.workspace_id);
                 
                 println!("✅ [CRM] Lead added successfully");
                 Ok(lead)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Lead creation not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                // Offline: the lead is cached as a person; the capture triggers queue it for sync
+                let lead_id = uuid::Uuid::new_v4().to_string();
+                let now = chrono::Utc::now().to_rfc3339();
+                let (first_name, last_name) = split_name(&lead_data.name);
+
+                let insert_sql = r#"
+                    INSERT INTO people (id, workspace_id, first_name, last_name, full_name, job_title, current_company,
+                                        email, phone, status, source, notes, priority, main_seller_id, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, 'LEAD', 'manual', '', 'MEDIUM', ?, ?, ?)
+                "#;
+
+                sqlx::query(insert_sql)
+                    .bind(&lead_id)
+                    .bind(&lead_data.workspace_id)
+                    .bind(&first_name)
+                    .bind(&last_name)
+                    .bind(&lead_data.name)
+                    .bind(&lead_data.title)
+                    .bind(&lead_data.company)
+                    .bind(&lead_data.email)
+                    .bind(&lead_data.phone)
+                    .bind(&lead_data.user_id)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(sqlite)
+                    .await?;
+
+                let lead = PersonRow {
+                    id: lead_id,
+                    full_name: lead_data.name.clone(),
+                    job_title: Some(lead_data.title.clone()),
+                    department: None,
+                    seniority: None,
+                    email: Some(lead_data.email.clone()),
+                    phone: Some(lead_data.phone.clone()),
+                    linkedin_url: None,
+                    company: Some(lead_data.company.clone()),
+                    status: Some("LEAD".to_string()),
+                    source: Some("manual".to_string()),
+                    notes: Some("".to_string()),
+                    main_seller_id: Some(lead_data.user_id.clone()),
+                    buyer_group_role: None,
+                    created_at: now.clone(),
+                    updated_at: now,
+                    last_synced_at: None,
+                    is_dirty: true,
+                }
+                .into_person(&lead_data.workspace_id);
+
+                println!("✅ [CRM] Lead added to the local cache");
+                Ok(lead)
             }
         }
     }
@@ -419,8 +669,22 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Lead status updated successfully");
                 Ok(())
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Lead update not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                sqlx::query(r#"
+                    UPDATE people
+                    SET status = ?, updated_at = ?
+                    WHERE id = ? AND workspace_id = ? AND main_seller_id = ?
+                "#)
+                    .bind(new_status)
+                    .bind(chrono::Utc::now().to_rfc3339())
+                    .bind(contact_id)
+                    .bind(workspace_id)
+                    .bind(user_id)
+                    .execute(sqlite)
+                    .await?;
+
+                println!("✅ [CRM] Lead status updated in the local cache");
+                Ok(())
             }
         }
     }
@@ -454,8 +718,15 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Lead activity saved successfully");
                 Ok(())
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Activity logging not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                // The cache has no activity tables; activities are kept as completed actions
+                let activity_type = activity_record.get("type").and_then(|v| v.as_str()).unwrap_or("unknown");
+                let subject = activity_record.get("subject").and_then(|v| v.as_str()).unwrap_or(activity_type);
+
+                save_cached_action(sqlite, workspace_id, user_id, contact_id, &activity_type.to_uppercase(), subject, activity_record).await?;
+
+                println!("✅ [CRM] Lead activity saved to the local cache");
+                Ok(())
             }
         }
     }
@@ -487,8 +758,11 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Call activity saved successfully");
                 Ok(())
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Call activity logging not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                save_cached_action(sqlite, workspace_id, user_id, contact_id, "CALL", "Call", call_record).await?;
+
+                println!("✅ [CRM] Call activity saved to the local cache");
+                Ok(())
             }
         }
     }
@@ -555,8 +829,36 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Company added successfully");
                 Ok(company_id)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Company creation not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                let company_id = uuid::Uuid::new_v4().to_string();
+                let now = chrono::Utc::now().to_rfc3339();
+
+                let insert_sql = r#"
+                    INSERT INTO companies (id, workspace_id, name, website, industry, size, employee_count, revenue,
+                                           address, status, notes, priority, main_seller_id, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, 'unknown', ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#;
+
+                sqlx::query(insert_sql)
+                    .bind(&company_id)
+                    .bind(&company_data.workspace_id)
+                    .bind(&company_data.name)
+                    .bind(&company_data.domain)
+                    .bind(&company_data.industry)
+                    .bind(company_data.employees)
+                    .bind(company_data.revenue.parse::<f64>().ok())
+                    .bind(&company_data.location)
+                    .bind(&company_data.status)
+                    .bind(&company_data.notes)
+                    .bind(&company_data.priority)
+                    .bind(&company_data.user_id)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(sqlite)
+                    .await?;
+
+                println!("✅ [CRM] Company added to the local cache");
+                Ok(company_id)
             }
         }
     }
@@ -601,9 +903,167 @@ impl HybridDatabaseManager {
                 println!("✅ [CRM] Lead updated comprehensively");
                 Ok(())
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Lead update not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                let update_sql = r#"
+                    UPDATE people
+                    SET
+                        full_name = COALESCE(?, full_name),
+                        job_title = COALESCE(?, job_title),
+                        current_company = COALESCE(?, current_company),
+                        email = COALESCE(?, email),
+                        phone = COALESCE(?, phone),
+                        status = COALESCE(?, status),
+                        notes = COALESCE(?, notes),
+                        updated_at = ?
+                    WHERE id = ? AND workspace_id = ? AND main_seller_id = ?
+                "#;
+
+                sqlx::query(update_sql)
+                    .bind(updates.get("name").and_then(|v| v.as_str()))
+                    .bind(updates.get("title").and_then(|v| v.as_str()))
+                    .bind(updates.get("company").and_then(|v| v.as_str()))
+                    .bind(updates.get("email").and_then(|v| v.as_str()))
+                    .bind(updates.get("phone").and_then(|v| v.as_str()))
+                    .bind(updates.get("status").and_then(|v| v.as_str()))
+                    .bind(updates.get("notes").and_then(|v| v.as_str()))
+                    .bind(chrono::Utc::now().to_rfc3339())
+                    .bind(lead_id)
+                    .bind(workspace_id)
+                    .bind(user_id)
+                    .execute(sqlite)
+                    .await?;
+
+                println!("✅ [CRM] Lead updated in the local cache");
+                Ok(())
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Record a lead activity in the cache as a completed action
+async fn save_cached_action(
+    sqlite: &sqlx::SqlitePool,
+    workspace_id: &str,
+    user_id: &str,
+    person_id: &str,
+    action_type: &str,
+    subject: &str,
+    record: &serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    sqlx::query(r#"
+        INSERT INTO actions (id, workspace_id, user_id, person_id, type, subject, description, status, completed_at, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, 'COMPLETED', ?, ?, ?)
+    "#)
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(workspace_id)
+        .bind(user_id)
+        .bind(person_id)
+        .bind(action_type)
+        .bind(subject)
+        .bind(record.to_string())
+        .bind(&now)
+        .bind(&now)
+        .bind(&now)
+        .execute(sqlite)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::{ConnectionMode, ConnectionModeStatus};
+    use crate::sync::{install_capture_triggers, test_cache_pool, SyncTableRegistry};
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
+
+    async fn offline_manager() -> (HybridDatabaseManager, sqlx::SqlitePool) {
+        let sqlite = test_cache_pool().await;
+        install_capture_triggers(&sqlite, &SyncTableRegistry::new()).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO users (id, email, name) VALUES ('u1', 'u1@example.com', 'One');",
+        )
+        .execute(&sqlite)
+        .await
+        .unwrap();
+
+        let manager = HybridDatabaseManager {
+            connection: Arc::new(RwLock::new(DatabaseConnection::Hybrid { sqlite: sqlite.clone() })),
+            status: Arc::new(RwLock::new(ConnectionModeStatus {
+                mode: ConnectionMode::Offline,
+                changed_at: chrono::Utc::now().to_rfc3339(),
+                reason: None,
+            })),
+        };
+        (manager, sqlite)
+    }
+
+    /// Records of `table` the capture triggers logged for the sync queue
+    async fn captured_records(sqlite: &sqlx::SqlitePool, table: &str) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(DISTINCT record_id) FROM sync_change_log WHERE table_name = ?")
+            .bind(table)
+            .fetch_one(sqlite)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn offline_leads_are_cached_and_queued_for_sync() {
+        let (manager, sqlite) = offline_manager().await;
+        let lead_data = LeadData {
+            workspace_id: "ws-1".to_string(),
+            user_id: "u1".to_string(),
+            name: "Dana Scully".to_string(),
+            email: "dana@example.com".to_string(),
+            company: "Acme".to_string(),
+            title: "CTO".to_string(),
+            phone: "555-0100".to_string(),
+        };
+
+        let added = manager.add_lead(&lead_data).await.unwrap();
+        manager
+            .update_lead_comprehensive("ws-1", "u1", &added.id, &serde_json::json!({ "title": "CEO" }))
+            .await
+            .unwrap();
+
+        let leads = manager.get_leads("ws-1", "u1").await.unwrap();
+        assert_eq!(leads.len(), 1);
+        assert_eq!(leads[0].id, added.id);
+        assert_eq!((leads[0].first_name.as_str(), leads[0].last_name.as_str()), ("Dana", "Scully"));
+        assert_eq!(leads[0].job_title.as_deref(), Some("CEO"));
+        assert_eq!(leads[0].current_company.as_deref(), Some("Acme"));
+        assert!(leads[0].is_dirty);
+        assert_eq!(captured_records(&sqlite, "people").await, 1);
+    }
+
+    #[tokio::test]
+    async fn offline_activities_are_cached_as_actions() {
+        let (manager, sqlite) = offline_manager().await;
+        let lead_data = LeadData {
+            workspace_id: "ws-1".to_string(),
+            user_id: "u1".to_string(),
+            name: "Fox".to_string(),
+            email: String::new(),
+            company: String::new(),
+            title: String::new(),
+            phone: String::new(),
+        };
+        let lead = manager.add_lead(&lead_data).await.unwrap();
+
+        manager
+            .save_call_activity("ws-1", "u1", &lead.id, &serde_json::json!({ "duration": 90 }))
+            .await
+            .unwrap();
+
+        let (action_type, person_id): (String, String) = sqlx::query_as("SELECT type, person_id FROM actions")
+            .fetch_one(&sqlite)
+            .await
+            .unwrap();
+        assert_eq!((action_type.as_str(), person_id.as_str()), ("CALL", lead.id.as_str()));
+        assert_eq!(captured_records(&sqlite, "actions").await, 1);
+    }
+}
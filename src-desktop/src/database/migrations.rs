@@ -0,0 +1,587 @@
+// ====================================================================
+// SQLITE CACHE MIGRATIONS
+// ====================================================================
+//
+// The cache.db schema is built from the SQL files in `migrations/`,
+// embedded in the binary and applied in order at startup, each in its
+// own transaction. Every applied migration is recorded in
+// `schema_migrations` with a checksum of its SQL:
+// - A recorded migration whose SQL has since changed stops startup
+// - Migrations only move forward: a database past the newest migration
+//   this build knows is refused, never downgraded
+// - Shipped migration files are never edited; changes go in a new one
+// 001 and 002 are kept for history only. 003 is the baseline every new
+// database starts from, and an older cache is carried over to it:
+// - Tables a migration declares it replaces are set aside as
+//   `legacy_<version>_<table>` instead of being dropped
+// - Their rows are copied into the successor table under the current
+//   column names, and anything the new schema can't hold stays behind
+//   in the legacy table
+// ====================================================================
+
+use sha2::{Digest, Sha256};
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+/// Oldest migration that is ever run; the ones before it are only recorded
+pub const BASELINE_VERSION: i64 = 3;
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+    /// What the migration leaves behind, for recognising a database it was
+    /// applied to before migrations were tracked
+    pub marker: SchemaMarker,
+    /// Existing tables the migration drops or recreates, each with the
+    /// table its rows carry into, if any
+    pub replaces: &'static [(&'static str, Option<&'static str>)],
+}
+
+pub enum SchemaMarker {
+    /// A table or index with this name exists
+    Object(&'static str),
+    /// The table has this column
+    Column(&'static str, &'static str),
+}
+
+macro_rules! migration {
+    ($version:expr, $name:literal, $marker:expr) => {
+        migration!($version, $name, $marker, &[])
+    };
+    ($version:expr, $name:literal, $marker:expr, $replaces:expr) => {
+        Migration {
+            version: $version,
+            name: $name,
+            sql: include_str!(concat!("../../migrations/", $name, ".sql")),
+            marker: $marker,
+            replaces: $replaces,
+        }
+    };
+}
+
+/// Every migration, oldest first; new migrations go at the end
+pub const MIGRATIONS: &[Migration] = &[
+    migration!(1, "001_initial_desktop_schema", SchemaMarker::Object("leads")),
+    migration!(2, "002_complete_desktop_schema", SchemaMarker::Object("enrichment_cache")),
+    migration!(
+        3,
+        "003_streamlined_schema_parity",
+        SchemaMarker::Object("person_co_sellers"),
+        &[
+            ("users", Some("users")),
+            ("people", Some("people")),
+            ("co_sellers", Some("person_co_sellers")),
+            ("leads", None),
+            ("contacts", None),
+            ("accounts", None),
+            ("opportunities", None),
+            ("outbox_settings", None),
+            ("sync_metadata", None),
+        ]
+    ),
+    migration!(4, "004_sync_settings", SchemaMarker::Object("sync_settings")),
+    migration!(5, "005_sync_base_versions", SchemaMarker::Object("sync_base_versions")),
+    migration!(6, "006_sync_queue_retry_schedule", SchemaMarker::Column("sync_queue", "next_retry_at")),
+    migration!(7, "007_sync_queue_coalescing", SchemaMarker::Column("sync_queue", "last_attempted_at")),
+    migration!(8, "008_sync_watermarks", SchemaMarker::Object("sync_watermarks")),
+    migration!(9, "009_sync_client_mutation_ids", SchemaMarker::Column("sync_queue", "client_mutation_id")),
+    migration!(10, "010_sync_change_capture", SchemaMarker::Object("sync_change_log")),
+    migration!(11, "011_sync_hybrid_clocks", SchemaMarker::Object("sync_record_clocks")),
+    migration!(12, "012_sync_runs", SchemaMarker::Object("sync_runs")),
+    migration!(13, "013_sync_tombstones", SchemaMarker::Object("sync_tombstones")),
+    migration!(14, "014_sync_pull_failures", SchemaMarker::Object("sync_pull_failures")),
+];
+
+/// Columns renamed since the legacy schemas, as (table, old, new); a
+/// table of `None` applies the rename to every table
+const LEGACY_COLUMN_RENAMES: &[(Option<&str>, &str, &str)] = &[
+    (None, "needs_sync", "is_dirty"),
+    (None, "last_sync_at", "last_synced_at"),
+    (Some("chronicle_shares"), "shared_with", "allowed_emails"),
+    (Some("chronicle_shares"), "shared_at", "created_at"),
+];
+
+impl Migration {
+    /// SHA-256 of the SQL, ignoring how the checkout ended its lines
+    pub fn checksum(&self) -> String {
+        hex::encode(Sha256::digest(self.sql.replace("\r\n", "\n").as_bytes()))
+    }
+
+    fn is_legacy(&self) -> bool {
+        self.version < BASELINE_VERSION
+    }
+
+    async fn is_present(&self, conn: &mut SqliteConnection) -> Result<bool, sqlx::Error> {
+        match self.marker {
+            SchemaMarker::Object(name) => object_exists(conn, name).await,
+            SchemaMarker::Column(table, column) => Ok(table_columns(conn, table).await?.iter().any(|c| c == column)),
+        }
+    }
+}
+
+/// Schema version this build expects cache.db to be at
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+/// Newest migration recorded in cache.db, or 0 if it was never migrated
+pub async fn applied_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    if !object_exists(&mut conn, "schema_migrations").await? {
+        return Ok(0);
+    }
+
+    let version: Option<i64> = sqlx::query_scalar("SELECT MAX(version) FROM schema_migrations")
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(version.unwrap_or(0))
+}
+
+/// Bring cache.db up to the latest schema, returning its schema version
+pub async fn run_migrations(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            checksum TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    adopt_untracked_schema(pool).await?;
+
+    let applied = sqlx::query("SELECT version, name, checksum FROM schema_migrations ORDER BY version")
+        .fetch_all(pool)
+        .await?;
+    let current = applied.last().map_or(0, |row| row.get::<i64, _>("version"));
+
+    for row in &applied {
+        let version: i64 = row.get("version");
+        let name: String = row.get("name");
+        let checksum: String = row.get("checksum");
+
+        let migration = MIGRATIONS.iter().find(|migration| migration.version == version).ok_or_else(|| {
+            if version > latest_version() {
+                sqlx::Error::Protocol(format!(
+                    "cache.db is at schema version {} but this build only knows up to {}; downgrades are not supported",
+                    current,
+                    latest_version()
+                ))
+            } else {
+                sqlx::Error::Protocol(format!("cache.db records migration {}, which this build doesn't have", name))
+            }
+        })?;
+
+        if migration.checksum() != checksum {
+            return Err(sqlx::Error::Protocol(format!(
+                "Migration {} changed after it was applied to cache.db (recorded checksum {}, embedded {})",
+                migration.name,
+                checksum,
+                migration.checksum()
+            )));
+        }
+    }
+
+    // Forward only: a migration added below the applied version is never run
+    let skipped = MIGRATIONS.iter().find(|migration| {
+        !migration.is_legacy()
+            && migration.version < current
+            && !applied.iter().any(|row| row.get::<i64, _>("version") == migration.version)
+    });
+    if let Some(skipped) = skipped {
+        return Err(sqlx::Error::Protocol(format!(
+            "Migration {} is older than the applied schema version {}; new migrations must come after the newest one",
+            skipped.name, current
+        )));
+    }
+
+    for migration in MIGRATIONS.iter().filter(|migration| !migration.is_legacy() && migration.version > current) {
+        apply_migration(pool, migration).await?;
+    }
+
+    Ok(latest_version())
+}
+
+/// Apply one migration and record it, all or nothing
+///
+/// Foreign keys are switched off on the migration's connection so tables
+/// can be set aside and rebuilt, and carried-over rows that point at
+/// missing parents are left in their legacy table instead.
+async fn apply_migration(pool: &SqlitePool, migration: &Migration) -> Result<(), sqlx::Error> {
+    let mut conn = pool.acquire().await?;
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await?;
+
+    let result = apply_migration_in(&mut conn, migration).await;
+
+    sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await?;
+    let carried = result?;
+
+    println!("✅ [DATABASE MIGRATIONS] Applied {}", migration.name);
+    for (legacy, target, rows) in carried {
+        println!(
+            "📦 [DATABASE MIGRATIONS] Carried {} rows from {} into {} (the rest stay in {})",
+            rows, legacy, target, legacy
+        );
+    }
+    Ok(())
+}
+
+async fn apply_migration_in(
+    conn: &mut SqliteConnection,
+    migration: &Migration,
+) -> Result<Vec<(String, String, u64)>, sqlx::Error> {
+    let mut tx = sqlx::Connection::begin(&mut *conn).await?;
+
+    let mut set_aside = Vec::new();
+    for (table, successor) in migration.replaces {
+        if let Some(legacy) = set_aside_table(&mut tx, migration, table).await? {
+            set_aside.push((legacy, *successor));
+        }
+    }
+
+    sqlx::raw_sql(migration.sql)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| sqlx::Error::Protocol(format!("Migration {} failed: {}", migration.name, e)))?;
+
+    let mut carried = Vec::new();
+    for (legacy, successor) in set_aside {
+        if let Some(successor) = successor {
+            let rows = carry_forward(&mut tx, &legacy, successor).await?;
+            carried.push((legacy, successor.to_string(), rows));
+        }
+    }
+    rename_legacy_columns(&mut tx).await?;
+
+    record_migration(&mut tx, migration).await?;
+    tx.commit().await?;
+
+    Ok(carried)
+}
+
+/// Record migrations applied before they were tracked
+///
+/// A database with no `schema_migrations` rows is matched against each
+/// migration's marker, oldest first. Matching migrations are recorded as
+/// applied; the first one from the baseline on that doesn't match, and
+/// everything after it, is left to run.
+async fn adopt_untracked_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    let tracked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations")
+        .fetch_one(pool)
+        .await?;
+    if tracked > 0 {
+        return Ok(());
+    }
+
+    let mut tx = pool.begin().await?;
+    let mut adopted = None;
+    for migration in MIGRATIONS {
+        if migration.is_present(&mut tx).await? {
+            record_migration(&mut tx, migration).await?;
+            adopted = Some(migration.version);
+        } else if !migration.is_legacy() {
+            break;
+        }
+    }
+    tx.commit().await?;
+
+    if let Some(version) = adopted {
+        println!("🔗 [DATABASE MIGRATIONS] Adopted existing schema at version {}", version);
+    }
+    Ok(())
+}
+
+async fn record_migration(conn: &mut SqliteConnection, migration: &Migration) -> Result<(), sqlx::Error> {
+    sqlx::query("INSERT INTO schema_migrations (version, name, checksum, applied_at) VALUES (?, ?, ?, ?)")
+        .bind(migration.version)
+        .bind(migration.name)
+        .bind(migration.checksum())
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Rename a table the migration replaces to `legacy_<version>_<table>`,
+/// returning the new name, or `None` if the table isn't there
+///
+/// The table's own indexes and triggers are dropped so the migration can
+/// create ones with the same names for its successor. Foreign keys in other
+/// tables keep pointing at the original name, so they reference the successor.
+async fn set_aside_table(
+    conn: &mut SqliteConnection,
+    migration: &Migration,
+    table: &str,
+) -> Result<Option<String>, sqlx::Error> {
+    let is_table: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+        .bind(table)
+        .fetch_one(&mut *conn)
+        .await?;
+    if is_table == 0 {
+        return Ok(None);
+    }
+
+    let dependents = sqlx::query(
+        "SELECT type, name FROM sqlite_master WHERE tbl_name = ? AND type IN ('index', 'trigger') AND sql IS NOT NULL",
+    )
+    .bind(table)
+    .fetch_all(&mut *conn)
+    .await?;
+    for dependent in dependents {
+        let kind: String = dependent.get("type");
+        let name: String = dependent.get("name");
+        sqlx::query(&format!("DROP {} \"{}\"", kind.to_uppercase(), name))
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    // Since SQLite 3.26 a rename also rewrites references to the table in
+    // other tables' foreign keys, unless legacy_alter_table is on
+    let legacy = format!("legacy_{:03}_{}", migration.version, table);
+    sqlx::query("PRAGMA legacy_alter_table = ON").execute(&mut *conn).await?;
+    let renamed = sqlx::query(&format!("ALTER TABLE \"{}\" RENAME TO \"{}\"", table, legacy))
+        .execute(&mut *conn)
+        .await;
+    sqlx::query("PRAGMA legacy_alter_table = OFF").execute(&mut *conn).await?;
+    renamed?;
+
+    Ok(Some(legacy))
+}
+
+/// Copy a set-aside table's rows into its successor, returning how many
+/// made it
+///
+/// Columns are matched by name, with legacy column names mapped to their
+/// current ones. Rows the new table rejects (missing required columns,
+/// duplicate keys, references to rows that no longer exist) stay only in
+/// the legacy table.
+async fn carry_forward(conn: &mut SqliteConnection, legacy: &str, target: &str) -> Result<u64, sqlx::Error> {
+    let source_columns = table_columns(conn, legacy).await?;
+    let target_columns = table_columns(conn, target).await?;
+
+    let mut inserts = Vec::new();
+    let mut selects = Vec::new();
+    for column in &target_columns {
+        let source = if source_columns.contains(column) {
+            Some(column.as_str())
+        } else {
+            LEGACY_COLUMN_RENAMES
+                .iter()
+                .filter(|(table, _, new)| table.is_none_or(|table| table == target) && new == column)
+                .map(|(_, old, _)| *old)
+                .find(|old| source_columns.iter().any(|c| c == old))
+        };
+        if let Some(source) = source {
+            inserts.push(format!("\"{}\"", column));
+            selects.push(format!("\"{}\"", source));
+        }
+    }
+    if inserts.is_empty() {
+        return Ok(0);
+    }
+
+    let before: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{}\"", target))
+        .fetch_one(&mut *conn)
+        .await?;
+    sqlx::query(&format!(
+        "INSERT OR IGNORE INTO \"{}\" ({}) SELECT {} FROM \"{}\"",
+        target,
+        inserts.join(", "),
+        selects.join(", "),
+        legacy
+    ))
+    .execute(&mut *conn)
+    .await?;
+
+    // OR IGNORE doesn't cover foreign keys, so dangling rows are removed here
+    let dangling = sqlx::query(&format!("PRAGMA foreign_key_check(\"{}\")", target))
+        .fetch_all(&mut *conn)
+        .await?;
+    for row in dangling {
+        sqlx::query(&format!("DELETE FROM \"{}\" WHERE rowid = ?", target))
+            .bind(row.get::<i64, _>("rowid"))
+            .execute(&mut *conn)
+            .await?;
+    }
+
+    let after: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{}\"", target))
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok((after - before).max(0) as u64)
+}
+
+/// Rename legacy columns that are still in place on current tables
+async fn rename_legacy_columns(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' AND name NOT LIKE 'legacy_%'",
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for table in tables {
+        let columns = table_columns(conn, &table).await?;
+        for (only, old, new) in LEGACY_COLUMN_RENAMES {
+            if only.is_some_and(|only| only != table) {
+                continue;
+            }
+            if columns.iter().any(|c| c == old) && !columns.iter().any(|c| c == new) {
+                sqlx::query(&format!("ALTER TABLE \"{}\" RENAME COLUMN \"{}\" TO \"{}\"", table, old, new))
+                    .execute(&mut *conn)
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn object_exists(conn: &mut SqliteConnection, name: &str) -> Result<bool, sqlx::Error> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE name = ?")
+        .bind(name)
+        .fetch_one(&mut *conn)
+        .await?;
+    Ok(count > 0)
+}
+
+async fn table_columns(conn: &mut SqliteConnection, table: &str) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+        .bind(table)
+        .fetch_all(&mut *conn)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn memory_pool() -> SqlitePool {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    async fn recorded_versions(pool: &SqlitePool) -> Vec<i64> {
+        sqlx::query_scalar("SELECT version FROM schema_migrations ORDER BY version")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fresh_database_runs_from_the_baseline() {
+        let pool = memory_pool().await;
+
+        assert_eq!(applied_version(&pool).await.unwrap(), 0);
+
+        assert_eq!(run_migrations(&pool).await.unwrap(), latest_version());
+        assert_eq!(recorded_versions(&pool).await, (BASELINE_VERSION..=latest_version()).collect::<Vec<_>>());
+        assert_eq!(applied_version(&pool).await.unwrap(), latest_version());
+
+        // A second start has nothing to do
+        run_migrations(&pool).await.unwrap();
+        assert_eq!(recorded_versions(&pool).await.len() as i64, latest_version() - BASELINE_VERSION + 1);
+    }
+
+    #[tokio::test]
+    async fn legacy_cache_is_carried_forward() {
+        let pool = memory_pool().await;
+        sqlx::raw_sql(MIGRATIONS[0].sql).execute(&pool).await.unwrap();
+        sqlx::raw_sql(
+            r#"
+            INSERT INTO users (id, name, email, workspace_id, workspace_name, last_sync_at)
+            VALUES ('user-1', 'Dana', 'dana@example.com', 'ws-1', 'Acme', '2024-01-01T00:00:00Z');
+            INSERT INTO leads (id, name, needs_sync) VALUES ('lead-1', 'Unsynced lead', 1);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+
+        let mut expected = vec![1];
+        expected.extend(BASELINE_VERSION..=latest_version());
+        assert_eq!(recorded_versions(&pool).await, expected);
+
+        let last_synced_at: Option<String> = sqlx::query_scalar("SELECT last_synced_at FROM users WHERE id = 'user-1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(last_synced_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+
+        // leads has no successor, so the unsynced lead is set aside, not dropped
+        let needs_sync: bool = sqlx::query_scalar("SELECT needs_sync FROM legacy_003_leads WHERE id = 'lead-1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert!(needs_sync);
+    }
+
+    #[tokio::test]
+    async fn foreign_keys_into_a_replaced_table_point_at_its_successor() {
+        let pool = memory_pool().await;
+        sqlx::raw_sql(MIGRATIONS[1].sql).execute(&pool).await.unwrap();
+
+        run_migrations(&pool).await.unwrap();
+
+        let parents: Vec<String> =
+            sqlx::query_scalar(r#"SELECT "table" FROM pragma_foreign_key_list('enrichment_executions')"#)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(parents, vec!["users"]);
+
+        // A user created after the upgrade can own new rows
+        sqlx::raw_sql(
+            "PRAGMA foreign_keys = ON;
+             INSERT INTO users (id, email, name) VALUES ('user-2', 'new@example.com', 'New');
+             INSERT INTO enrichment_executions (id, execution_id, trigger_type, start_time, workspace_id, user_id)
+             VALUES ('exec-1', 'exec-1', 'manual', '2026-01-01T00:00:00Z', 'ws-1', 'user-2');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn untracked_schema_is_adopted_from_its_tables() {
+        let pool = memory_pool().await;
+        for migration in MIGRATIONS.iter().filter(|m| (BASELINE_VERSION..=10).contains(&m.version)) {
+            sqlx::raw_sql(migration.sql).execute(&pool).await.unwrap();
+        }
+        sqlx::query("INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Acme', 'acme')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        run_migrations(&pool).await.unwrap();
+
+        assert_eq!(recorded_versions(&pool).await, (BASELINE_VERSION..=latest_version()).collect::<Vec<_>>());
+        let workspaces: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM workspaces")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(workspaces, 1);
+    }
+
+    #[tokio::test]
+    async fn a_changed_migration_stops_startup() {
+        let pool = memory_pool().await;
+        run_migrations(&pool).await.unwrap();
+        sqlx::query("UPDATE schema_migrations SET checksum = 'edited' WHERE version = 4")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let error = run_migrations(&pool).await.unwrap_err().to_string();
+        assert!(error.contains("004_sync_settings changed"), "{}", error);
+    }
+}
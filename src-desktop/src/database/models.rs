@@ -279,7 +279,7 @@ pub struct Company {
     pub is_dirty: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, FromRow)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, FromRow)]
 pub struct Person {
     pub id: String,
     pub workspace_id: String,
@@ -929,13 +929,35 @@ pub struct AuthUserRow {
 // ====================================================================
 
 pub enum DatabaseConnection {
+    /// Neon PostgreSQL, with the SQLite cache when it could be opened
     Production { postgres: PgPool, sqlite: Option<SqlitePool> },
-    _Hybrid { sqlite: SqlitePool },
+    /// SQLite cache only, while PostgreSQL can't be reached
+    Hybrid { sqlite: SqlitePool },
+}
+
+/// Which database the app is working against, for the UI's connection indicator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionMode {
+    /// Connected to PostgreSQL
+    Online,
+    /// Working from the SQLite cache until PostgreSQL can be reached
+    Offline,
+}
+
+/// Payload of `CONNECTION_MODE_EVENT` and the `get_connection_mode` command
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionModeStatus {
+    pub mode: ConnectionMode,
+    pub changed_at: String,
+    /// Why PostgreSQL isn't connected, while offline
+    pub reason: Option<String>,
 }
 
 #[derive(Clone)]
 pub struct HybridDatabaseManager {
     pub connection: Arc<RwLock<DatabaseConnection>>,
+    pub status: Arc<RwLock<ConnectionModeStatus>>,
 }
 
 pub type DatabaseState = Arc<std::sync::Mutex<Option<HybridDatabaseManager>>>; 
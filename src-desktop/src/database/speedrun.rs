@@ -1,3 +1,4 @@
+use super::crm::split_name;
 use super::models::{HybridDatabaseManager, DatabaseConnection};
 use sqlx::Row;
 use std::time::{Instant, Duration};
@@ -90,8 +91,47 @@ impl HybridDatabaseManager {
                 println!("✅ [MARK I] Fetched {} leads in {}ms", processed_leads.len(), start_time.elapsed().as_millis());
                 Ok(processed_leads)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(vec![])
+            DatabaseConnection::Hybrid { sqlite } => {
+                let query_sql = r#"
+                    SELECT p.id, p.full_name as name, p.job_title as title, c.name as company,
+                           p.email, p.phone, p.mobile_phone, p.linkedin_url, p.status, p.priority,
+                           p.next_action, p.buyer_group_role, p.source, c.industry, p.department,
+                           p.tags, p.custom_fields, p.workspace_id, p.main_seller_id,
+                           p.created_at, p.updated_at
+                    FROM people p
+                    LEFT JOIN companies c ON p.company_id = c.id AND c.deleted_at IS NULL
+                    WHERE p.workspace_id = ?
+                    AND p.main_seller_id = ?
+                    AND p.deleted_at IS NULL
+                    AND p.status NOT IN ('CLIENT', 'SUPERFAN')
+                    AND (p.email IS NOT NULL OR p.phone IS NOT NULL OR p.mobile_phone IS NOT NULL)
+                    ORDER BY
+                        CASE p.priority
+                            WHEN 'HIGH' THEN 1
+                            WHEN 'MEDIUM' THEN 2
+                            WHEN 'LOW' THEN 3
+                            ELSE 4
+                        END,
+                        p.updated_at DESC,
+                        p.created_at DESC
+                    LIMIT ?
+                "#;
+
+                let rows = sqlx::query(query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id)
+                    .bind(limit as i64)
+                    .fetch_all(sqlite)
+                    .await?;
+
+                // Not cached: the next sync or local edit would otherwise be hidden for a minute
+                let processed_leads: Vec<serde_json::Value> = rows
+                    .iter()
+                    .map(|row| self.convert_cached_lead_row_to_json(row))
+                    .collect();
+
+                println!("✅ [MARK I] Fetched {} cached leads in {}ms", processed_leads.len(), start_time.elapsed().as_millis());
+                Ok(processed_leads)
             }
         }
     }
@@ -152,8 +192,24 @@ impl HybridDatabaseManager {
                 let count: i64 = row.try_get::<i64, _>("count").unwrap_or(0);
                 Ok(count as i32)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Ok(0)
+            DatabaseConnection::Hybrid { sqlite } => {
+                let query_sql = r#"
+                    SELECT COUNT(*) as count
+                    FROM people p
+                    WHERE p.workspace_id = ?
+                    AND p.main_seller_id = ?
+                    AND p.status IN ('LEAD', 'PROSPECT')
+                    AND p.deleted_at IS NULL
+                "#;
+
+                let row = sqlx::query(query_sql)
+                    .bind(workspace_id)
+                    .bind(user_id)
+                    .fetch_one(sqlite)
+                    .await?;
+
+                let count: i64 = row.try_get::<i64, _>("count").unwrap_or(0);
+                Ok(count as i32)
             }
         }
     }
@@ -175,10 +231,7 @@ impl HybridDatabaseManager {
                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
                 "#;
                 
-                // Split name into first and last name
-                let name_parts: Vec<&str> = contact_data.name.splitn(2, ' ').collect();
-                let first_name = name_parts.get(0).unwrap_or(&"").to_string();
-                let last_name = name_parts.get(1).unwrap_or(&"").to_string();
+                let (first_name, last_name) = split_name(&contact_data.name);
                 
                 sqlx::query(insert_sql)
                     .bind(&contact_id)
@@ -205,8 +258,38 @@ impl HybridDatabaseManager {
                 
                 Ok(contact_id)
             },
-            DatabaseConnection::_Hybrid { .. } => {
-                Err("Speedrun contact creation not supported in hybrid mode".into())
+            DatabaseConnection::Hybrid { sqlite } => {
+                // Offline: cached as a person; the capture triggers queue it for sync
+                let contact_id = uuid::Uuid::new_v4().to_string();
+                let now = chrono::Utc::now().to_rfc3339();
+                let (first_name, last_name) = split_name(&contact_data.name);
+
+                let insert_sql = r#"
+                    INSERT INTO people (id, workspace_id, first_name, last_name, full_name, job_title, current_company,
+                                        email, phone, status, source, priority, main_seller_id, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#;
+
+                sqlx::query(insert_sql)
+                    .bind(&contact_id)
+                    .bind(&contact_data.workspace_id)
+                    .bind(&first_name)
+                    .bind(&last_name)
+                    .bind(&contact_data.name)
+                    .bind(&contact_data.title)
+                    .bind(&contact_data.company)
+                    .bind(&contact_data.email)
+                    .bind(&contact_data.phone)
+                    .bind(&contact_data.status)
+                    .bind(&contact_data.source)
+                    .bind(&contact_data.priority)
+                    .bind(&contact_data.user_id)
+                    .bind(&now)
+                    .bind(&now)
+                    .execute(sqlite)
+                    .await?;
+
+                Ok(contact_id)
             }
         }
     }
@@ -280,4 +363,57 @@ impl HybridDatabaseManager {
             "monacoEnrichment": monaco_data.cloned()
         })
     }
+
+    /// Same shape as `convert_mark_i_row_to_json`, from a cached `people` row
+    fn convert_cached_lead_row_to_json(&self, row: &sqlx::sqlite::SqliteRow) -> serde_json::Value {
+        let name = row.try_get::<String, _>("name").unwrap_or_default();
+        let status = row.try_get::<Option<String>, _>("status").unwrap_or_default().unwrap_or_default();
+        let priority = row.try_get::<Option<String>, _>("priority").unwrap_or_default().unwrap_or_default();
+
+        // JSON is stored as text in the cache
+        let custom_fields: Option<serde_json::Value> = row.try_get::<Option<String>, _>("custom_fields")
+            .unwrap_or_default()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        let tags: Vec<String> = row.try_get::<Option<String>, _>("tags")
+            .unwrap_or_default()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let monaco_data = custom_fields.as_ref().and_then(|cf| cf.get("monacoEnrichment"));
+
+        let relationship = row.try_get::<Option<String>, _>("buyer_group_role")
+            .unwrap_or_default()
+            .unwrap_or_else(|| match priority.as_str() {
+                "HIGH" => "Champion".to_string(),
+                _ => "Influencer".to_string(),
+            });
+
+        serde_json::json!({
+            "id": row.try_get::<String, _>("id").unwrap_or_default(),
+            "name": name,
+            "title": row.try_get::<Option<String>, _>("title").unwrap_or_default().unwrap_or_default(),
+            "company": row.try_get::<Option<String>, _>("company").unwrap_or_default().unwrap_or_default(),
+            "email": row.try_get::<Option<String>, _>("email").unwrap_or_default().unwrap_or_default(),
+            "phone": row.try_get::<Option<String>, _>("phone").unwrap_or_default().unwrap_or_default(),
+            "mobilePhone": row.try_get::<Option<String>, _>("mobile_phone").unwrap_or_default().unwrap_or_default(),
+            "linkedin": row.try_get::<Option<String>, _>("linkedin_url").unwrap_or_default()
+                .unwrap_or_else(|| format!("linkedin.com/in/{}", name.to_lowercase().replace(' ', "-"))),
+            "priority": priority,
+            "status": status,
+            "nextAction": row.try_get::<Option<String>, _>("next_action").unwrap_or_default()
+                .unwrap_or_else(|| "Follow up".to_string()),
+            "buyerGroupRole": relationship,
+            "relationship": relationship,
+            "source": row.try_get::<Option<String>, _>("source").unwrap_or_default()
+                .unwrap_or_else(|| "Local Cache".to_string()),
+            "industry": row.try_get::<Option<String>, _>("industry").unwrap_or_default(),
+            "department": row.try_get::<Option<String>, _>("department").unwrap_or_default(),
+            "tags": tags,
+            "workspaceId": row.try_get::<String, _>("workspace_id").unwrap_or_default(),
+            "assignedUserId": row.try_get::<Option<String>, _>("main_seller_id").unwrap_or_default().unwrap_or_default(),
+            "createdAt": row.try_get::<String, _>("created_at").unwrap_or_default(),
+            "updatedAt": row.try_get::<String, _>("updated_at").unwrap_or_default(),
+            "customFields": custom_fields.clone().unwrap_or_default(),
+            "monacoEnrichment": monaco_data.cloned()
+        })
+    }
 } 
\ No newline at end of file
@@ -1,10 +1,22 @@
 use std::sync::{Arc, OnceLock};
-use crate::database::models::{HybridDatabaseManager, AuthUser, DatabaseState};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+use crate::database::models::{HybridDatabaseManager, AuthUser, DatabaseState, ConnectionMode, ConnectionModeStatus};
 use crate::database::create_database_state;
 
 // Global database state
 static DATABASE_STATE: OnceLock<DatabaseState> = OnceLock::new();
 
+/// Event name the frontend listens on for connection mode changes
+pub const CONNECTION_MODE_EVENT: &str = "database://connection-mode";
+
+/// Time between attempts to reach PostgreSQL while running from the SQLite cache
+const UPGRADE_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+// Whether the connection upgrade task is running
+static UPGRADE_TASK_RUNNING: AtomicBool = AtomicBool::new(false);
+
 // Initialize database manager with bulletproof environment loading
 pub async fn init_database_manager(app_handle: &tauri::AppHandle<tauri::Wry>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     println!("🔗 [TAURI] ===== COMPREHENSIVE DATABASE INITIALIZATION =====");
@@ -58,18 +70,15 @@ pub async fn init_database_manager(app_handle: &tauri::AppHandle<tauri::Wry>) ->
     println!("🔍 [TAURI] Step 2: Setting up database credentials...");
     
     // SECURITY: Never hardcode credentials - always use environment variables
-    // Get DATABASE_URL from environment - fail if not set
-    let _database_url = match std::env::var("DATABASE_URL") {
+    // Without DATABASE_URL the app starts from the SQLite cache only
+    match std::env::var("DATABASE_URL") {
         Ok(url) => {
             println!("✅ [TAURI] DATABASE_URL found in environment: {}...", &url[..50.min(url.len())]);
-            url
         },
         Err(_) => {
-            let error_msg = "❌ [TAURI] DATABASE_URL environment variable is required but not set. Please configure it in your .env file or environment.";
-            println!("{}", error_msg);
-            return Err(Box::new(std::io::Error::other(error_msg)));
+            println!("⚠️ [TAURI] DATABASE_URL not set, starting from the SQLite cache only. Configure it in your .env file or environment to connect to PostgreSQL.");
         }
-    };
+    }
     
     // Set other critical environment variables (optional - can be set in .env)
     if std::env::var("DEFAULT_WORKSPACE_ID").is_err() {
@@ -88,10 +97,19 @@ pub async fn init_database_manager(app_handle: &tauri::AppHandle<tauri::Wry>) ->
         Ok(manager) => {
             println!("✅ [TAURI] Database manager created successfully");
             
+            let status = manager.connection_mode().await;
+            
             // Store the manager globally
             let db_state = DATABASE_STATE.get_or_init(create_database_state);
             *db_state.lock().unwrap() = Some(manager);
             
+            emit_connection_mode(app_handle, &status);
+            
+            // Keep trying PostgreSQL in the background; without a URL there is nothing to retry
+            if status.mode == ConnectionMode::Offline && std::env::var("DATABASE_URL").is_ok() {
+                spawn_connection_upgrade(app_handle.clone());
+            }
+            
             println!("✅ [TAURI] Database initialization completed successfully");
             Ok(())
         },
@@ -114,6 +132,56 @@ pub fn get_database_manager() -> Result<Arc<HybridDatabaseManager>, String> {
     }
 }
 
+// Upgrade from the SQLite cache to the full connection once PostgreSQL can be reached
+fn spawn_connection_upgrade(app_handle: tauri::AppHandle<tauri::Wry>) {
+    if UPGRADE_TASK_RUNNING.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    
+    println!("🔄 [TAURI] Offline: retrying PostgreSQL every {:?}", UPGRADE_RETRY_INTERVAL);
+    
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(UPGRADE_RETRY_INTERVAL).await;
+            
+            let db_manager = match get_database_manager() {
+                Ok(manager) => manager,
+                Err(_) => continue,
+            };
+            
+            match db_manager.upgrade_to_production().await {
+                Ok(upgraded) => {
+                    if upgraded {
+                        // Rebuild the sync engine so it picks up the PostgreSQL pool
+                        app_handle.state::<crate::sync::SharedSyncEngine>().reload().await;
+                        emit_connection_mode(&app_handle, &db_manager.connection_mode().await);
+                    }
+                    break;
+                },
+                Err(e) => {
+                    println!("📴 [TAURI] Still offline: {}", e);
+                }
+            }
+        }
+        
+        UPGRADE_TASK_RUNNING.store(false, Ordering::SeqCst);
+    });
+}
+
+fn emit_connection_mode(app_handle: &tauri::AppHandle<tauri::Wry>, status: &ConnectionModeStatus) {
+    if let Err(e) = app_handle.emit(CONNECTION_MODE_EVENT, status) {
+        println!("⚠️ [TAURI] Failed to emit connection mode event: {}", e);
+    }
+}
+
+// Connection mode command, for the UI's online/offline indicator
+#[tauri::command]
+pub async fn get_connection_mode() -> Result<ConnectionModeStatus, String> {
+    let db_manager = get_database_manager()?;
+    
+    Ok(db_manager.connection_mode().await)
+}
+
 // Authentication command
 #[tauri::command]
 pub async fn authenticate_user_direct(email: String, password: String) -> Result<Option<AuthUser>, String> {
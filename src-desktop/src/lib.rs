@@ -1,4 +1,5 @@
 // No global imports needed - using module references in invoke_handler
+use tauri::Manager;
 
 // App state for Tauri
 #[derive(Default)]
@@ -35,13 +36,22 @@ mod browser;
 // (Commands are referenced as module::command in the invoke_handler)
 
 pub fn run() {
+    // One sync engine for the whole app, shared by commands and the scheduler
+    let sync_engine = sync::SharedSyncEngine::new();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_websocket::init())
+        .manage(sync_engine.clone())
+        .manage(sync::BackgroundSyncScheduler::new(sync_engine.clone()))
+        .manage(sync::ConnectivityMonitor::new(sync_engine))
         .setup(|app| {
             println!("🚀 [TAURI] Starting Adrata Desktop Application");
             
+            // Let the sync engine stream progress events to the frontend
+            app.state::<sync::SharedSyncEngine>().attach_app_handle(app.handle().clone());
+
             // Initialize database on app startup
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -49,6 +59,16 @@ pub fn run() {
                     println!("❌ [TAURI] Database initialization failed: {}", e);
                 } else {
                     println!("✅ [TAURI] Database initialization completed successfully");
+                    
+                    // Resume background sync with the persisted schedule
+                    let scheduler = app_handle.state::<sync::BackgroundSyncScheduler>();
+                    if let Err(e) = scheduler.restore_from_settings().await {
+                        println!("⚠️ [TAURI] Failed to restore background sync: {}", e);
+                    }
+
+                    // Watch for reconnects so offline edits are pushed as soon as possible
+                    let monitor = app_handle.state::<sync::ConnectivityMonitor>();
+                    monitor.start(app_handle.clone()).await;
                 }
             });
             
@@ -74,6 +94,7 @@ pub fn run() {
             database_init::test_database_connection,
             database_init::initialize_user_auth,
             database_init::authenticate_user_direct,
+            database_init::get_connection_mode,
 
             // Data Access
             data::get_leads,
@@ -245,14 +266,30 @@ pub fn run() {
                 sync::sync_table,
                 sync::push_changes,
                 sync::pull_changes,
+                sync::cancel_sync,
                 sync::resolve_conflict,
+                sync::get_unresolved_conflicts,
+                sync::get_conflict_diff,
+                sync::resolve_conflict_fields,
                 sync::get_sync_status,
+                sync::get_sync_performance,
+                sync::get_sync_history,
+                sync::get_connectivity_status,
+                sync::get_replication_scope,
+                sync::set_replication_scope,
                 sync::enable_background_sync,
                 sync::disable_background_sync,
+                sync::get_background_sync_status,
                 sync::get_sync_queue_stats,
                 sync::get_conflict_statistics,
                 sync::retry_failed_syncs,
                 sync::clear_failed_syncs,
+                sync::list_sync_queue,
+                sync::edit_queued_change,
+                sync::requeue_queued_change,
+                sync::discard_queued_change,
+                sync::export_sync_bundle,
+                sync::import_sync_bundle,
                 sync::get_sync_health,
 
                 // API Commands - Matching V1 APIs
@@ -0,0 +1,304 @@
+// ====================================================================
+// SYNC BUNDLES
+// ====================================================================
+//
+// This module packs a device's unpushed queue into a portable bundle
+// file, so changes made where the server can't be reached can be carried
+// to a device that can reach it. A bundle is a zip archive holding:
+// - manifest.json: format version, workspace, source device and a
+//   checksum of the changes
+// - changes.json: every pending and failed change, with the row it
+//   would push and the record's clock
+// - signature: HMAC-SHA256 of the manifest, keyed by the shared secret
+//   in `SYNC_BUNDLE_SIGNING_KEY`
+// Changes keep their client mutation IDs, so a change pushed from both
+// devices is only applied once by the server.
+// ====================================================================
+
+use super::models::*;
+use super::SyncError;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Read, Write};
+
+/// Bundle format written by this build; newer formats are refused
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Environment variable holding the secret bundles are signed with
+pub const BUNDLE_SIGNING_KEY_ENV: &str = "SYNC_BUNDLE_SIGNING_KEY";
+
+const MANIFEST_FILE: &str = "manifest.json";
+const CHANGES_FILE: &str = "changes.json";
+const SIGNATURE_FILE: &str = "signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+// ====================================================================
+// BUNDLE MODELS
+// ====================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub bundle_id: String,
+    pub workspace_id: String,
+    /// Device whose queue the changes were exported from
+    pub device_id: String,
+    pub created_at: String,
+    pub change_count: usize,
+    /// SHA-256 of `changes.json`, so the signature covers the changes too
+    pub changes_sha256: String,
+}
+
+/// A queued change as carried in a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundledChange {
+    /// The payload is the row as it was when exported
+    #[serde(flatten)]
+    pub change: SyncQueueItem,
+    /// Version of the exported row, `None` once the row is deleted
+    pub sync_version: Option<i32>,
+    /// Hybrid timestamp of the change on the source device
+    pub hlc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncBundle {
+    pub manifest: BundleManifest,
+    pub changes: Vec<BundledChange>,
+}
+
+/// What importing a bundle did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleImportReport {
+    pub bundle_id: String,
+    pub workspace_id: String,
+    pub source_device_id: String,
+    pub changes_staged: i32,
+    /// Changes an earlier import of the same bundle already queued
+    pub changes_already_imported: i32,
+    /// Changes that conflicted with unsynced edits on this device
+    pub conflicts: i32,
+    /// Records left alone because this device deleted them after the change
+    pub skipped_records: Vec<String>,
+    /// Sync that pushed the staged changes, `None` while offline
+    pub sync_report: Option<SyncReport>,
+}
+
+// ====================================================================
+// BUNDLE FILES
+// ====================================================================
+
+impl SyncBundle {
+    pub fn new(workspace_id: &str, device_id: &str, changes: Vec<BundledChange>) -> Result<Self, SyncError> {
+        let changes_sha256 = hex::encode(Sha256::digest(serde_json::to_vec(&changes)?));
+
+        Ok(Self {
+            manifest: BundleManifest {
+                format_version: BUNDLE_FORMAT_VERSION,
+                bundle_id: uuid::Uuid::new_v4().to_string(),
+                workspace_id: workspace_id.to_string(),
+                device_id: device_id.to_string(),
+                created_at: chrono::Utc::now().to_rfc3339(),
+                change_count: changes.len(),
+                changes_sha256,
+            },
+            changes,
+        })
+    }
+
+    /// Write the signed zip archive
+    pub fn to_bytes(&self, key: &[u8]) -> Result<Vec<u8>, SyncError> {
+        let changes = serde_json::to_vec(&self.changes)?;
+        let manifest = serde_json::to_vec_pretty(&self.manifest)?;
+        let signature = hex::encode(manifest_mac(key, &manifest).finalize().into_bytes());
+
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+        for (name, contents) in [
+            (MANIFEST_FILE, manifest.as_slice()),
+            (CHANGES_FILE, changes.as_slice()),
+            (SIGNATURE_FILE, signature.as_bytes()),
+        ] {
+            archive.start_file(name, options).map_err(bundle_error)?;
+            archive.write_all(contents).map_err(bundle_error)?;
+        }
+
+        Ok(archive.finish().map_err(bundle_error)?.into_inner())
+    }
+
+    /// Read a zip archive, refusing it unless the signature and checksum match
+    pub fn from_bytes(bytes: &[u8], key: &[u8]) -> Result<Self, SyncError> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(bundle_error)?;
+        let manifest = read_entry(&mut archive, MANIFEST_FILE)?;
+        let signature = read_entry(&mut archive, SIGNATURE_FILE)?;
+
+        let signature = hex::decode(String::from_utf8_lossy(&signature).trim())
+            .map_err(|_| SyncError::Bundle("Bundle signature is not valid hex".to_string()))?;
+        if manifest_mac(key, &manifest).verify_slice(&signature).is_err() {
+            return Err(SyncError::Bundle(
+                "Bundle signature does not match; it was changed or signed with a different key".to_string(),
+            ));
+        }
+
+        let manifest: BundleManifest = serde_json::from_slice(&manifest)?;
+        if manifest.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(SyncError::Bundle(format!(
+                "Bundle format {} is newer than this app supports ({})",
+                manifest.format_version, BUNDLE_FORMAT_VERSION
+            )));
+        }
+
+        let changes = read_entry(&mut archive, CHANGES_FILE)?;
+        if hex::encode(Sha256::digest(&changes)) != manifest.changes_sha256 {
+            return Err(SyncError::Bundle("Bundle changes do not match the signed manifest".to_string()));
+        }
+
+        let changes: Vec<BundledChange> = serde_json::from_slice(&changes)?;
+        if changes.len() != manifest.change_count {
+            return Err(SyncError::Bundle(format!(
+                "Bundle lists {} changes but holds {}",
+                manifest.change_count,
+                changes.len()
+            )));
+        }
+
+        Ok(Self { manifest, changes })
+    }
+}
+
+/// The shared secret bundles are signed and verified with
+pub fn signing_key() -> Result<Vec<u8>, SyncError> {
+    match std::env::var(BUNDLE_SIGNING_KEY_ENV) {
+        Ok(key) if !key.is_empty() => Ok(key.into_bytes()),
+        _ => Err(SyncError::Configuration(format!(
+            "Set {} to sign and verify sync bundles",
+            BUNDLE_SIGNING_KEY_ENV
+        ))),
+    }
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Vec<u8>, SyncError> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| SyncError::Bundle(format!("Bundle has no {}", name)))?;
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents).map_err(bundle_error)?;
+
+    Ok(contents)
+}
+
+fn bundle_error(e: impl std::fmt::Display) -> SyncError {
+    SyncError::Bundle(e.to_string())
+}
+
+/// HMAC-SHA256 of the manifest; `verify_slice` compares in constant time
+fn manifest_mac(key: &[u8], manifest: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(manifest);
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"test signing key";
+
+    fn bundle() -> SyncBundle {
+        let change = BundledChange {
+            change: SyncQueueItem {
+                id: 7,
+                table_name: "companies".to_string(),
+                record_id: "c1".to_string(),
+                operation: SyncOperation::Update,
+                data: Some(serde_json::json!({ "id": "c1", "name": "Acme" }).to_string()),
+                created_at: "2026-01-01T00:00:00Z".to_string(),
+                synced_at: None,
+                error_message: Some("timed out".to_string()),
+                retry_count: 2,
+                next_retry_at: None,
+                status: SyncQueueStatus::Failed,
+                client_mutation_id: "mutation-1".to_string(),
+            },
+            sync_version: Some(3),
+            hlc: Some("1767225600000:000000:device-a".to_string()),
+        };
+
+        SyncBundle::new("ws-test", "device-a", vec![change]).unwrap()
+    }
+
+    /// Rewrite one entry of a bundle archive, keeping the others as they are
+    fn replace_entry(bytes: &[u8], name: &str, contents: &[u8]) -> Vec<u8> {
+        let mut original = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+
+        for entry_name in [MANIFEST_FILE, CHANGES_FILE, SIGNATURE_FILE] {
+            let entry = if entry_name == name {
+                contents.to_vec()
+            } else {
+                read_entry(&mut original, entry_name).unwrap()
+            };
+            archive.start_file(entry_name, zip::write::FileOptions::default()).unwrap();
+            archive.write_all(&entry).unwrap();
+        }
+
+        archive.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn signed_bundles_round_trip() {
+        let bundle = bundle();
+        let read = SyncBundle::from_bytes(&bundle.to_bytes(KEY).unwrap(), KEY).unwrap();
+
+        assert_eq!(read.manifest.bundle_id, bundle.manifest.bundle_id);
+        assert_eq!(read.manifest.workspace_id, "ws-test");
+        assert_eq!(read.manifest.change_count, 1);
+
+        let change = &read.changes[0];
+        assert_eq!(change.change.id, 7);
+        assert_eq!(change.change.record_id, "c1");
+        assert_eq!(change.change.operation, SyncOperation::Update);
+        assert_eq!(change.change.data, bundle.changes[0].change.data);
+        assert_eq!(change.change.retry_count, 2);
+        assert_eq!(change.change.status, SyncQueueStatus::Failed);
+        assert_eq!(change.change.client_mutation_id, "mutation-1");
+        assert_eq!(change.sync_version, Some(3));
+        assert_eq!(change.hlc, bundle.changes[0].hlc);
+    }
+
+    #[test]
+    fn bundles_signed_with_another_key_are_refused() {
+        let bytes = bundle().to_bytes(b"another key").unwrap();
+
+        assert!(matches!(SyncBundle::from_bytes(&bytes, KEY), Err(SyncError::Bundle(_))));
+    }
+
+    #[test]
+    fn changes_edited_under_a_signed_manifest_are_refused() {
+        let bundle = bundle();
+        let mut changes = bundle.changes.clone();
+        changes[0].change.data = Some(serde_json::json!({ "id": "c1", "name": "Forged" }).to_string());
+        let bytes = replace_entry(&bundle.to_bytes(KEY).unwrap(), CHANGES_FILE, &serde_json::to_vec(&changes).unwrap());
+
+        match SyncBundle::from_bytes(&bytes, KEY) {
+            Err(SyncError::Bundle(message)) => assert_eq!(message, "Bundle changes do not match the signed manifest"),
+            other => panic!("expected a checksum mismatch, got {:?}", other.map(|bundle| bundle.manifest)),
+        }
+    }
+
+    #[test]
+    fn a_manifest_miscounting_its_changes_is_refused() {
+        let mut bundle = bundle();
+        bundle.manifest.change_count = 2;
+
+        let bytes = bundle.to_bytes(KEY).unwrap();
+
+        match SyncBundle::from_bytes(&bytes, KEY) {
+            Err(SyncError::Bundle(message)) => assert_eq!(message, "Bundle lists 2 changes but holds 1"),
+            other => panic!("expected a count mismatch, got {:?}", other.map(|bundle| bundle.manifest)),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// ====================================================================
+// SYNC CHANGE CAPTURE
+// ====================================================================
+//
+// This module captures local writes at the database level. Every table
+// the registry pushes gets INSERT/UPDATE/DELETE triggers that mark the
+// row dirty, stamp it with the hybrid logical clock, keep its tombstone
+// current and log the change to `sync_change_log`, so no command has to
+// remember to enqueue its edits. The queue folds the log into its pending changes before each
+// push.
+//
+// Writes the sync engine applies (pulled records, conflict resolutions,
+// clearing dirty flags) run in "sync write" transactions that suppress
+// the triggers, so they never echo back to the server.
+// ====================================================================
+
+use super::clock::stamp_record_sql;
+use super::registry::SyncTableRegistry;
+use super::tombstones::{tombstone_trigger_sql, SOFT_DELETE_COLUMN};
+use sqlx::{Row, Sqlite, SqliteConnection, SqlitePool, Transaction};
+
+/// Create or refresh the capture triggers for every pushed table
+///
+/// Tables missing from the local schema are skipped. Tables that are no
+/// longer pushed lose their triggers.
+pub async fn install_capture_triggers(pool: &SqlitePool, registry: &SyncTableRegistry) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let mut installed = 0;
+
+    for table in registry.tables().iter().filter(|table| !table.direction.pushes()) {
+        for event in ["insert", "update", "delete"] {
+            sqlx::query(&format!("DROP TRIGGER IF EXISTS sync_capture_{}_{}", table.name, event))
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    for table in registry.tables().iter().filter(|table| table.direction.pushes()) {
+        let columns = sqlx::query(&format!("PRAGMA table_info({})", table.name))
+            .fetch_all(&mut *tx)
+            .await?;
+
+        if columns.is_empty() {
+            println!("⚠️ [SYNC CAPTURE] Table {} does not exist, skipping capture triggers", table.name);
+            continue;
+        }
+
+        let has_column = |name: &str| columns.iter().any(|column| column.get::<String, _>("name") == name);
+        let has_dirty_flag = has_column("is_dirty");
+        let soft_delete = has_column(SOFT_DELETE_COLUMN);
+        let has_version = has_column("sync_version");
+
+        for (event, operation, row) in [("INSERT", "Insert", "NEW"), ("UPDATE", "Update", "NEW"), ("DELETE", "Delete", "OLD")] {
+            let trigger_name = format!("sync_capture_{}_{}", table.name, event.to_lowercase());
+
+            // On inserts this also fires the UPDATE trigger; the queue folds
+            // that extra Update into the Insert
+            let mark_dirty = if has_dirty_flag && row == "NEW" {
+                format!(
+                    "UPDATE {table} SET is_dirty = 1 WHERE {pk} = NEW.{pk} AND is_dirty IS NOT 1;",
+                    table = table.name,
+                    pk = table.primary_key
+                )
+            } else {
+                String::new()
+            };
+
+            sqlx::query(&format!("DROP TRIGGER IF EXISTS {}", trigger_name))
+                .execute(&mut *tx)
+                .await?;
+
+            let query = format!(
+                r#"
+                CREATE TRIGGER {trigger_name} AFTER {event} ON {table}
+                WHEN COALESCE((SELECT suppressed FROM sync_capture_state WHERE id = 1), 0) = 0
+                BEGIN
+                    INSERT INTO sync_change_log (table_name, record_id, operation)
+                    VALUES ('{table}', {row}.{pk}, '{operation}');
+                    {stamp_clock}
+                    {tombstone}
+                    {mark_dirty}
+                END
+                "#,
+                trigger_name = trigger_name,
+                event = event,
+                table = table.name,
+                row = row,
+                pk = table.primary_key,
+                operation = operation,
+                stamp_clock = stamp_record_sql(table.name, &format!("{}.{}", row, table.primary_key)),
+                tombstone = tombstone_trigger_sql(table.name, table.primary_key, event, soft_delete, has_version),
+                mark_dirty = mark_dirty,
+            );
+
+            sqlx::query(&query).execute(&mut *tx).await?;
+        }
+
+        installed += 1;
+    }
+
+    tx.commit().await?;
+    println!("✅ [SYNC CAPTURE] Change capture triggers installed on {} tables", installed);
+
+    Ok(())
+}
+
+/// Begin a transaction whose writes are not captured as local changes
+///
+/// Must be finished with `commit_sync_write`; dropping it rolls back,
+/// which also lifts the suppression.
+pub async fn begin_sync_write(pool: &SqlitePool) -> Result<Transaction<'static, Sqlite>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    set_capture_suppressed(&mut tx, true).await?;
+    Ok(tx)
+}
+
+/// Lift the capture suppression and commit a sync write
+pub async fn commit_sync_write(mut tx: Transaction<'static, Sqlite>) -> Result<(), sqlx::Error> {
+    set_capture_suppressed(&mut tx, false).await?;
+    tx.commit().await
+}
+
+async fn set_capture_suppressed(conn: &mut SqliteConnection, suppressed: bool) -> Result<(), sqlx::Error> {
+    // SQLite has one writer at a time and the flag is reset before commit,
+    // so no other connection ever sees it set
+    sqlx::query("UPDATE sync_capture_state SET suppressed = ? WHERE id = 1")
+        .bind(suppressed)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{test_cache_pool, SyncQueue};
+
+    async fn captured(pool: &SqlitePool) -> Vec<(String, String)> {
+        sqlx::query_as("SELECT record_id, operation FROM sync_change_log ORDER BY id")
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn is_dirty(pool: &SqlitePool) -> bool {
+        sqlx::query_scalar("SELECT is_dirty FROM companies WHERE id = 'c1'")
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn cache_with_triggers() -> SqlitePool {
+        let pool = test_cache_pool().await;
+        install_capture_triggers(&pool, &SyncTableRegistry::new()).await.unwrap();
+        sqlx::query("INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn local_writes_are_captured_and_queued() {
+        let pool = cache_with_triggers().await;
+
+        sqlx::query("INSERT INTO companies (id, workspace_id, name) VALUES ('c1', 'ws-1', 'Acme')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // The workspace is pull-only, so only the company was captured
+        let log = captured(&pool).await;
+        assert_eq!(log[0], ("c1".to_string(), "Insert".to_string()));
+        assert!(log.iter().all(|(record_id, _)| record_id == "c1"));
+        assert!(is_dirty(&pool).await);
+
+        // The updates from marking the new row dirty fold into the insert
+        let queue = SyncQueue::new(pool.clone());
+        assert_eq!(queue.collect_captured_changes().await.unwrap(), log.len() as i32);
+        let pending = queue.get_pending_changes("companies").await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].operation, crate::sync::SyncOperation::Insert);
+        assert!(captured(&pool).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn sync_writes_are_not_captured_or_marked_dirty() {
+        let pool = cache_with_triggers().await;
+
+        let mut tx = begin_sync_write(&pool).await.unwrap();
+        sqlx::query("INSERT INTO companies (id, workspace_id, name, is_dirty) VALUES ('c1', 'ws-1', 'Acme', 0)")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE companies SET name = 'Acme Inc' WHERE id = 'c1'")
+            .execute(&mut *tx)
+            .await
+            .unwrap();
+        commit_sync_write(tx).await.unwrap();
+
+        assert!(captured(&pool).await.is_empty());
+        assert!(!is_dirty(&pool).await);
+    }
+}
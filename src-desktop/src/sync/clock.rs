@@ -0,0 +1,320 @@
+// ====================================================================
+// SYNC HYBRID LOGICAL CLOCKS
+// ====================================================================
+//
+// This module orders writes across devices with hybrid logical clocks.
+// A timestamp is wall time in milliseconds, a counter that breaks ties
+// within the same millisecond, and the device ID that breaks ties
+// between devices, so any two writes compare the same way everywhere.
+//
+// - Local writes are stamped by the capture triggers (see `stamp_record_sql`)
+// - Records carry their clock through sync in the `hlc` field
+// - Pulled clocks advance the local clock, so later local writes
+//   always order after everything this device has seen
+// - Pulled clocks too far ahead of local wall time are not trusted; the
+//   write is ordered as received instead
+// ====================================================================
+
+use serde_json::Value;
+use sqlx::{Row, SqliteConnection};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Record field that carries the clock through sync
+pub const CLOCK_FIELD: &str = "hlc";
+
+/// Remote clocks further ahead of local wall time than this are not adopted
+const MAX_CLOCK_DRIFT_MS: i64 = 5 * 60 * 1000;
+
+/// Current wall time in milliseconds, as an SQLite expression
+const SQL_NOW_MS: &str = "CAST((julianday('now') - 2440587.5) * 86400000 AS INTEGER)";
+
+/// The stored clock as timestamp text, as an SQLite expression
+const SQL_CLOCK_TEXT: &str = "printf('%013d:%06d:%s', wall_ms, counter, device_id)";
+
+/// A hybrid logical clock timestamp
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridTimestamp {
+    pub wall_ms: i64,
+    pub counter: i64,
+    pub device_id: String,
+}
+
+impl Ord for HybridTimestamp {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.wall_ms
+            .cmp(&other.wall_ms)
+            .then(self.counter.cmp(&other.counter))
+            .then_with(|| self.device_id.cmp(&other.device_id))
+    }
+}
+
+impl PartialOrd for HybridTimestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for HybridTimestamp {
+    // Keep in sync with the printf format in `SQL_CLOCK_TEXT`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:013}:{:06}:{}", self.wall_ms, self.counter, self.device_id)
+    }
+}
+
+impl FromStr for HybridTimestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, ':');
+        let (wall_ms, counter, device_id) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(wall_ms), Some(counter), Some(device_id)) if !device_id.is_empty() => (wall_ms, counter, device_id),
+            _ => return Err(format!("Invalid hybrid timestamp: {}", s)),
+        };
+
+        Ok(Self {
+            wall_ms: wall_ms.parse().map_err(|_| format!("Invalid wall time in hybrid timestamp: {}", s))?,
+            counter: counter.parse().map_err(|_| format!("Invalid counter in hybrid timestamp: {}", s))?,
+            device_id: device_id.to_string(),
+        })
+    }
+}
+
+impl HybridTimestamp {
+    /// Read the clock carried in a record's `hlc` field
+    pub fn from_record(data: &Value) -> Option<Self> {
+        data.get(CLOCK_FIELD)?.as_str()?.parse().ok()
+    }
+
+    /// Parse a stored clock, treating unreadable values as missing
+    pub fn parse_opt(value: Option<&str>) -> Option<Self> {
+        value.and_then(|v| v.parse().ok())
+    }
+}
+
+/// Parse a wall-clock write time, for ordering writes that carry no clock
+///
+/// Accepts RFC 3339 and SQLite's `datetime('now')` format, read as UTC.
+pub fn parse_write_time(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(time) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&chrono::Utc));
+    }
+
+    chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|time| time.and_utc())
+}
+
+// ====================================================================
+// CLOCK STATE
+// ====================================================================
+
+/// Advance the clock for a local write and return the new timestamp
+///
+/// Runs the same statement the capture triggers do.
+pub async fn tick(conn: &mut SqliteConnection) -> Result<HybridTimestamp, sqlx::Error> {
+    sqlx::query(&tick_sql()).execute(&mut *conn).await?;
+
+    let stamp: String = sqlx::query_scalar(&format!("SELECT {} FROM sync_clock WHERE id = 1", SQL_CLOCK_TEXT))
+        .fetch_one(&mut *conn)
+        .await?;
+
+    stamp.parse().map_err(sqlx::Error::Protocol)
+}
+
+/// Advance the clock past a timestamp received from another device
+///
+/// Returns the timestamp to order the remote write by: its own, or, when
+/// it is too far ahead of local wall time to trust, a local timestamp for
+/// the moment it was received. Otherwise the write would win over every
+/// later local edit until real time caught up with it.
+pub async fn observe(conn: &mut SqliteConnection, remote: &HybridTimestamp) -> Result<HybridTimestamp, sqlx::Error> {
+    let last = load_clock(&mut *conn).await?;
+    let now = chrono::Utc::now().timestamp_millis();
+
+    if remote.wall_ms > now + MAX_CLOCK_DRIFT_MS {
+        println!(
+            "⚠️ [SYNC CLOCK] Clock from device {} is {}ms ahead, ordering its write as received now",
+            remote.device_id,
+            remote.wall_ms - now
+        );
+        return tick(conn).await;
+    }
+
+    let wall_ms = last.wall_ms.max(remote.wall_ms).max(now);
+    let counter = if wall_ms == last.wall_ms && wall_ms == remote.wall_ms {
+        last.counter.max(remote.counter) + 1
+    } else if wall_ms == last.wall_ms {
+        last.counter + 1
+    } else if wall_ms == remote.wall_ms {
+        remote.counter + 1
+    } else {
+        0
+    };
+
+    save_clock(&mut *conn, &HybridTimestamp { wall_ms, counter, ..last }).await?;
+    Ok(remote.clone())
+}
+
+/// ID of this device, as carried in its timestamps
+pub async fn device_id(conn: &mut SqliteConnection) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar("SELECT device_id FROM sync_clock WHERE id = 1")
+        .fetch_one(&mut *conn)
+        .await
+}
+
+async fn load_clock(conn: &mut SqliteConnection) -> Result<HybridTimestamp, sqlx::Error> {
+    let row = sqlx::query("SELECT device_id, wall_ms, counter FROM sync_clock WHERE id = 1")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(HybridTimestamp {
+        wall_ms: row.get("wall_ms"),
+        counter: row.get("counter"),
+        device_id: row.get("device_id"),
+    })
+}
+
+async fn save_clock(conn: &mut SqliteConnection, clock: &HybridTimestamp) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE sync_clock SET wall_ms = ?, counter = ? WHERE id = 1")
+        .bind(clock.wall_ms)
+        .bind(clock.counter)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+// ====================================================================
+// RECORD CLOCKS
+// ====================================================================
+
+/// Get the clock of a record's latest write
+pub async fn record_clock(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    record_id: &str,
+) -> Result<Option<HybridTimestamp>, sqlx::Error> {
+    let hlc: Option<String> = sqlx::query_scalar("SELECT hlc FROM sync_record_clocks WHERE table_name = ? AND record_id = ?")
+        .bind(table_name)
+        .bind(record_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    Ok(HybridTimestamp::parse_opt(hlc.as_deref()))
+}
+
+/// Set the clock of a record's latest write
+pub async fn set_record_clock(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    record_id: &str,
+    clock: &HybridTimestamp,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO sync_record_clocks (table_name, record_id, hlc)
+        VALUES (?, ?, ?)
+        ON CONFLICT(table_name, record_id) DO UPDATE SET hlc = excluded.hlc
+        "#,
+    )
+    .bind(table_name)
+    .bind(record_id)
+    .bind(clock.to_string())
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Statement that advances the clock for a local write, used by `tick` and the triggers
+fn tick_sql() -> String {
+    format!(
+        r#"
+                    UPDATE sync_clock SET
+                        counter = CASE WHEN {now} > wall_ms THEN 0 ELSE counter + 1 END,
+                        wall_ms = MAX(wall_ms, {now})
+                    WHERE id = 1"#,
+        now = SQL_NOW_MS,
+    )
+}
+
+/// Trigger statements that tick the clock and stamp the written row
+///
+/// `row_id` is the trigger's reference to the row key, e.g. `NEW.id`.
+pub(crate) fn stamp_record_sql(table_name: &str, row_id: &str) -> String {
+    format!(
+        r#"{tick};
+                    INSERT INTO sync_record_clocks (table_name, record_id, hlc)
+                    SELECT '{table}', {row_id}, {clock_text}
+                    FROM sync_clock WHERE id = 1
+                    ON CONFLICT(table_name, record_id) DO UPDATE SET hlc = excluded.hlc;"#,
+        tick = tick_sql(),
+        table = table_name,
+        row_id = row_id,
+        clock_text = SQL_CLOCK_TEXT,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{install_capture_triggers, test_cache_pool, SyncTableRegistry};
+
+    fn now_ms() -> i64 {
+        chrono::Utc::now().timestamp_millis()
+    }
+
+    fn other_device(wall_ms: i64) -> HybridTimestamp {
+        HybridTimestamp { wall_ms, counter: 7, device_id: "other".to_string() }
+    }
+
+    #[tokio::test]
+    async fn ticks_order_after_observed_clocks() {
+        let pool = test_cache_pool().await;
+        let mut conn = pool.acquire().await.unwrap();
+
+        let remote = other_device(now_ms() + 60_000);
+        assert_eq!(observe(&mut conn, &remote).await.unwrap(), remote);
+
+        let local = tick(&mut conn).await.unwrap();
+        assert!(local > remote);
+        assert_eq!(local.wall_ms, remote.wall_ms);
+    }
+
+    #[tokio::test]
+    async fn far_future_clocks_are_ordered_as_received() {
+        let pool = test_cache_pool().await;
+        let mut conn = pool.acquire().await.unwrap();
+        let own_device = load_clock(&mut conn).await.unwrap().device_id;
+
+        let remote = other_device(now_ms() + 3_600_000);
+        let observed = observe(&mut conn, &remote).await.unwrap();
+
+        assert!(observed < remote);
+        assert!(observed.wall_ms <= now_ms());
+        assert_eq!(observed.device_id, own_device);
+        assert!(tick(&mut conn).await.unwrap() > observed);
+    }
+
+    #[tokio::test]
+    async fn triggers_and_tick_advance_the_same_clock() {
+        let pool = test_cache_pool().await;
+        install_capture_triggers(&pool, &SyncTableRegistry::new()).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO companies (id, workspace_id, name) VALUES ('c1', 'ws-1', 'Acme');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let mut conn = pool.acquire().await.unwrap();
+        let stamped = record_clock(&mut conn, "companies", "c1").await.unwrap().unwrap();
+        let next = tick(&mut conn).await.unwrap();
+
+        assert!(next > stamped);
+        assert_eq!(next.device_id, stamped.device_id);
+    }
+}
@@ -16,19 +16,14 @@ use serde::{Deserialize, Serialize};
 // ====================================================================
 
 #[tauri::command]
-pub async fn sync_workspace(workspace_id: String) -> Result<SyncReport, String> {
+pub async fn sync_workspace(
+    workspace_id: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<SyncReport, String> {
     println!("🔄 [SYNC COMMAND] Starting workspace sync for: {}", workspace_id);
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Perform sync
     match sync_engine.sync_workspace(&workspace_id).await {
@@ -48,19 +43,15 @@ pub async fn sync_workspace(workspace_id: String) -> Result<SyncReport, String>
 // ====================================================================
 
 #[tauri::command]
-pub async fn sync_table(table_name: String, workspace_id: String) -> Result<SyncResult, String> {
+pub async fn sync_table(
+    table_name: String,
+    workspace_id: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<SyncResult, String> {
     println!("🔄 [SYNC COMMAND] Syncing table: {} for workspace: {}", table_name, workspace_id);
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Perform table sync
     match sync_engine.sync_table(&table_name, &workspace_id).await {
@@ -80,19 +71,14 @@ pub async fn sync_table(table_name: String, workspace_id: String) -> Result<Sync
 // ====================================================================
 
 #[tauri::command]
-pub async fn push_changes(workspace_id: String) -> Result<SyncResult, String> {
+pub async fn push_changes(
+    workspace_id: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<SyncResult, String> {
     println!("📤 [SYNC COMMAND] Pushing changes for workspace: {}", workspace_id);
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Push changes
     match sync_engine.push_changes(&workspace_id).await {
@@ -112,19 +98,14 @@ pub async fn push_changes(workspace_id: String) -> Result<SyncResult, String> {
 // ====================================================================
 
 #[tauri::command]
-pub async fn pull_changes(workspace_id: String) -> Result<SyncResult, String> {
+pub async fn pull_changes(
+    workspace_id: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<SyncResult, String> {
     println!("📥 [SYNC COMMAND] Pulling changes for workspace: {}", workspace_id);
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Pull changes
     match sync_engine.pull_changes(&workspace_id).await {
@@ -139,22 +120,43 @@ pub async fn pull_changes(workspace_id: String) -> Result<SyncResult, String> {
     }
 }
 
+// ====================================================================
+// CANCEL SYNC COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn cancel_sync(engine: tauri::State<'_, SharedSyncEngine>) -> Result<bool, String> {
+    println!("⏹️ [SYNC COMMAND] Cancelling sync");
+    
+    // The running sync stops at its next batch boundary
+    let cancelled = engine.cancel();
+    
+    if cancelled {
+        println!("✅ [SYNC COMMAND] Cancellation requested");
+    } else {
+        println!("ℹ️ [SYNC COMMAND] No sync is running");
+    }
+    
+    Ok(cancelled)
+}
+
 // ====================================================================
 // RESOLVE CONFLICT COMMAND
 // ====================================================================
 
 #[tauri::command]
-pub async fn resolve_conflict(conflict_id: i64, resolution: ConflictResolution) -> Result<(), String> {
+pub async fn resolve_conflict(
+    conflict_id: i64,
+    resolution: ConflictResolution,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<(), String> {
     println!("🔧 [SYNC COMMAND] Resolving conflict: {} with resolution: {:?}", conflict_id, resolution);
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create conflict resolver
-    let conflict_resolver = ConflictResolver::new(db_manager.get_sqlite_pool().await?);
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Resolve conflict
-    match conflict_resolver.resolve_conflict(conflict_id, resolution).await {
+    match sync_engine.resolve_conflict(conflict_id, resolution).await {
         Ok(()) => {
             println!("✅ [SYNC COMMAND] Conflict resolved successfully");
             Ok(())
@@ -166,24 +168,86 @@ pub async fn resolve_conflict(conflict_id: i64, resolution: ConflictResolution)
     }
 }
 
+// ====================================================================
+// CONFLICT INSPECTION & FIELD-LEVEL RESOLUTION COMMANDS
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_unresolved_conflicts(engine: tauri::State<'_, SharedSyncEngine>) -> Result<Vec<SyncConflict>, String> {
+    println!("📋 [SYNC COMMAND] Getting unresolved conflicts");
+    
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.get_unresolved_conflicts().await {
+        Ok(conflicts) => {
+            println!("✅ [SYNC COMMAND] Found {} unresolved conflicts", conflicts.len());
+            Ok(conflicts)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to get unresolved conflicts: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_conflict_diff(
+    conflict_id: i64,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<conflict_resolver::ConflictDiff, String> {
+    println!("🔍 [SYNC COMMAND] Getting field diff for conflict: {}", conflict_id);
+    
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.get_conflict_diff(conflict_id).await {
+        Ok(Some(diff)) => {
+            println!("✅ [SYNC COMMAND] Conflict {} differs in {} fields", conflict_id, diff.fields.len());
+            Ok(diff)
+        }
+        Ok(None) => Err(format!("Conflict {} not found", conflict_id)),
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to diff conflict: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn resolve_conflict_fields(
+    conflict_id: i64,
+    choices: HashMap<String, diff::FieldChoice>,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<String, String> {
+    println!("🔧 [SYNC COMMAND] Resolving conflict {} with {} field choices", conflict_id, choices.len());
+    
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    // Compose the record, apply it locally and queue it for push
+    match sync_engine.resolve_conflict_fields(conflict_id, choices).await {
+        Ok(resolved_data) => {
+            println!("✅ [SYNC COMMAND] Conflict resolved field by field");
+            Ok(resolved_data)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Field-level resolution failed: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 // ====================================================================
 // GET SYNC STATUS COMMAND
 // ====================================================================
 
 #[tauri::command]
-pub async fn get_sync_status() -> Result<SyncStatusResponse, String> {
+pub async fn get_sync_status(engine: tauri::State<'_, SharedSyncEngine>) -> Result<SyncStatusResponse, String> {
     println!("📊 [SYNC COMMAND] Getting sync status");
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Get sync status
     match sync_engine.get_sync_status().await {
@@ -198,27 +262,143 @@ pub async fn get_sync_status() -> Result<SyncStatusResponse, String> {
     }
 }
 
+// ====================================================================
+// GET SYNC PERFORMANCE COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_sync_performance(engine: tauri::State<'_, SharedSyncEngine>) -> Result<SyncPerformanceMetrics, String> {
+    println!("📊 [SYNC COMMAND] Getting sync performance metrics");
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.get_sync_performance().await {
+        Ok(metrics) => {
+            println!("✅ [SYNC COMMAND] Sync performance metrics retrieved successfully");
+            Ok(metrics)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to get sync performance metrics: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+// ====================================================================
+// GET SYNC HISTORY COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_sync_history(
+    query: Option<SyncHistoryQuery>,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<SyncHistory, String> {
+    println!("📊 [SYNC COMMAND] Getting sync run history");
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.get_sync_history(&query.unwrap_or_default()).await {
+        Ok(history) => {
+            println!("✅ [SYNC COMMAND] Retrieved {} of {} sync runs", history.runs.len(), history.total_runs);
+            Ok(history)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to get sync history: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+// ====================================================================
+// GET CONNECTIVITY STATUS COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_connectivity_status(
+    monitor: tauri::State<'_, ConnectivityMonitor>,
+) -> Result<ConnectivityStatus, String> {
+    println!("📡 [SYNC COMMAND] Getting connectivity status");
+    
+    Ok(monitor.status().await)
+}
+
+// ====================================================================
+// GET REPLICATION SCOPE COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_replication_scope(
+    workspace_id: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<ReplicationScope, String> {
+    println!("🎯 [SYNC COMMAND] Getting replication scope for workspace: {}", workspace_id);
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.get_replication_scope(&workspace_id).await {
+        Ok(scope) => Ok(scope),
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to get replication scope: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+// ====================================================================
+// SET REPLICATION SCOPE COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn set_replication_scope(
+    scope: ReplicationScope,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<(), String> {
+    println!("🎯 [SYNC COMMAND] Setting replication scope for workspace: {}", scope.workspace_id);
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.set_replication_scope(&scope).await {
+        Ok(()) => {
+            println!("✅ [SYNC COMMAND] Replication scope saved for {} tables", scope.tables.len());
+            Ok(())
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to set replication scope: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
 // ====================================================================
 // ENABLE BACKGROUND SYNC COMMAND
 // ====================================================================
 
 #[tauri::command]
-pub async fn enable_background_sync(interval_minutes: u32) -> Result<(), String> {
-    println!("🔄 [SYNC COMMAND] Enabling background sync with {} minute interval", interval_minutes);
+pub async fn enable_background_sync(
+    workspace_id: String,
+    interval_minutes: u32,
+    engine: tauri::State<'_, SharedSyncEngine>,
+    scheduler: tauri::State<'_, BackgroundSyncScheduler>,
+) -> Result<(), String> {
+    println!("🔄 [SYNC COMMAND] Enabling background sync for {} with {} minute interval", workspace_id, interval_minutes);
     
     // Get database manager
     let db_manager = get_database_manager()?;
+    let sqlite_pool = db_manager.get_sqlite_pool().await?;
+    
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Persist the schedule so it survives restarts
+    sync_engine.enable_background_sync(interval_minutes).await.map_err(|e| e.to_string())?;
+    SyncSettingsStore::new(sqlite_pool)
+        .set_background_workspace(Some(&workspace_id))
+        .await
+        .map_err(|e| e.to_string())?;
+    engine.reload().await;
     
-    // Enable background sync
-    match sync_engine.enable_background_sync(interval_minutes).await {
+    // Start the background task
+    match scheduler.start(workspace_id, interval_minutes).await {
         Ok(()) => {
             println!("✅ [SYNC COMMAND] Background sync enabled successfully");
             Ok(())
@@ -235,23 +415,22 @@ pub async fn enable_background_sync(interval_minutes: u32) -> Result<(), String>
 // ====================================================================
 
 #[tauri::command]
-pub async fn disable_background_sync() -> Result<(), String> {
+pub async fn disable_background_sync(
+    engine: tauri::State<'_, SharedSyncEngine>,
+    scheduler: tauri::State<'_, BackgroundSyncScheduler>,
+) -> Result<(), String> {
     println!("⏹️ [SYNC COMMAND] Disabling background sync");
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
+    // Stop the background task first so no new tick starts
+    scheduler.stop().await;
     
-    // Create sync engine
-    let config = SyncConfig::default();
-    let sync_engine = SyncEngine::new(
-        db_manager.get_sqlite_pool().await?,
-        db_manager.get_postgres_pool().await?,
-        config,
-    );
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Disable background sync
     match sync_engine.disable_background_sync().await {
         Ok(()) => {
+            engine.reload().await;
             println!("✅ [SYNC COMMAND] Background sync disabled successfully");
             Ok(())
         }
@@ -262,12 +441,25 @@ pub async fn disable_background_sync() -> Result<(), String> {
     }
 }
 
+// ====================================================================
+// GET BACKGROUND SYNC STATUS COMMAND
+// ====================================================================
+
+#[tauri::command]
+pub async fn get_background_sync_status(
+    scheduler: tauri::State<'_, BackgroundSyncScheduler>,
+) -> Result<scheduler::BackgroundSyncStatus, String> {
+    println!("📊 [SYNC COMMAND] Getting background sync status");
+    
+    Ok(scheduler.status().await)
+}
+
 // ====================================================================
 // ADDITIONAL SYNC COMMANDS
 // ====================================================================
 
 #[tauri::command]
-pub async fn get_sync_queue_stats() -> Result<QueueStats, String> {
+pub async fn get_sync_queue_stats() -> Result<queue::QueueStats, String> {
     println!("📊 [SYNC COMMAND] Getting sync queue statistics");
     
     // Get database manager
@@ -290,7 +482,7 @@ pub async fn get_sync_queue_stats() -> Result<QueueStats, String> {
 }
 
 #[tauri::command]
-pub async fn get_conflict_statistics() -> Result<ConflictStatistics, String> {
+pub async fn get_conflict_statistics() -> Result<conflict_resolver::ConflictStatistics, String> {
     println!("📊 [SYNC COMMAND] Getting conflict statistics");
     
     // Get database manager
@@ -313,17 +505,14 @@ pub async fn get_conflict_statistics() -> Result<ConflictStatistics, String> {
 }
 
 #[tauri::command]
-pub async fn retry_failed_syncs() -> Result<i32, String> {
+pub async fn retry_failed_syncs(engine: tauri::State<'_, SharedSyncEngine>) -> Result<i32, String> {
     println!("🔄 [SYNC COMMAND] Retrying failed syncs");
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync queue
-    let sync_queue = SyncQueue::new(db_manager.get_sqlite_pool().await?);
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Retry failed syncs
-    match sync_queue.retry_failed_changes().await {
+    match sync_engine.retry_failed_changes().await {
         Ok(count) => {
             println!("✅ [SYNC COMMAND] Retried {} failed syncs", count);
             Ok(count)
@@ -336,17 +525,14 @@ pub async fn retry_failed_syncs() -> Result<i32, String> {
 }
 
 #[tauri::command]
-pub async fn clear_failed_syncs() -> Result<(), String> {
+pub async fn clear_failed_syncs(engine: tauri::State<'_, SharedSyncEngine>) -> Result<(), String> {
     println!("🗑️ [SYNC COMMAND] Clearing failed syncs");
     
-    // Get database manager
-    let db_manager = get_database_manager()?;
-    
-    // Create sync queue
-    let sync_queue = SyncQueue::new(db_manager.get_sqlite_pool().await?);
+    // Get the shared sync engine
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
     
     // Clear failed syncs
-    match sync_queue.clear_failed_changes().await {
+    match sync_engine.clear_failed_changes().await {
         Ok(()) => {
             println!("✅ [SYNC COMMAND] Failed syncs cleared successfully");
             Ok(())
@@ -359,7 +545,141 @@ pub async fn clear_failed_syncs() -> Result<(), String> {
 }
 
 #[tauri::command]
-pub async fn get_sync_health() -> Result<SyncHealthStatus, String> {
+pub async fn list_sync_queue(
+    filter: Option<SyncQueueFilter>,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<Vec<SyncQueueEntry>, String> {
+    println!("🔍 [SYNC COMMAND] Listing queued changes");
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.list_queued_changes(&filter.unwrap_or_default()).await {
+        Ok(entries) => {
+            println!("✅ [SYNC COMMAND] Found {} queued changes", entries.len());
+            Ok(entries)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to list queued changes: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn edit_queued_change(
+    change_id: i64,
+    data: serde_json::Value,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<Vec<String>, String> {
+    println!("✏️ [SYNC COMMAND] Editing queued change: {}", change_id);
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.edit_queued_change(change_id, &data).await {
+        Ok(dropped_fields) => {
+            println!("✅ [SYNC COMMAND] Queued change {} edited and requeued", change_id);
+            Ok(dropped_fields)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to edit queued change: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn requeue_queued_change(
+    change_id: i64,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<(), String> {
+    println!("🔁 [SYNC COMMAND] Requeueing queued change: {}", change_id);
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.requeue_queued_change(change_id).await {
+        Ok(()) => {
+            println!("✅ [SYNC COMMAND] Queued change {} requeued", change_id);
+            Ok(())
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to requeue queued change: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn discard_queued_change(
+    change_id: i64,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<(), String> {
+    println!("🗑️ [SYNC COMMAND] Discarding queued change: {}", change_id);
+    
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+    
+    match sync_engine.discard_queued_change(change_id).await {
+        Ok(()) => {
+            println!("✅ [SYNC COMMAND] Queued change {} discarded, server version restored", change_id);
+            Ok(())
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to discard queued change: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn export_sync_bundle(
+    workspace_id: String,
+    path: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<BundleManifest, String> {
+    println!("📦 [SYNC COMMAND] Exporting offline change bundle for workspace {} to {}", workspace_id, path);
+
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+
+    match sync_engine.export_sync_bundle(&workspace_id, std::path::Path::new(&path)).await {
+        Ok(manifest) => {
+            println!("✅ [SYNC COMMAND] Exported {} changes in bundle {}", manifest.change_count, manifest.bundle_id);
+            Ok(manifest)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to export sync bundle: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn import_sync_bundle(
+    workspace_id: String,
+    path: String,
+    engine: tauri::State<'_, SharedSyncEngine>,
+) -> Result<BundleImportReport, String> {
+    println!("📦 [SYNC COMMAND] Importing offline change bundle for workspace {} from {}", workspace_id, path);
+
+    let sync_engine = engine.get().await.map_err(|e| e.to_string())?;
+
+    match sync_engine.import_sync_bundle(&workspace_id, std::path::Path::new(&path)).await {
+        Ok(report) => {
+            println!(
+                "✅ [SYNC COMMAND] Imported bundle {}: {} changes staged, {} records skipped",
+                report.bundle_id,
+                report.changes_staged,
+                report.skipped_records.len()
+            );
+            Ok(report)
+        }
+        Err(e) => {
+            println!("❌ [SYNC COMMAND] Failed to import sync bundle: {}", e);
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_sync_health() -> Result<status::SyncHealthStatus, String> {
     println!("🏥 [SYNC COMMAND] Getting sync health status");
     
     // Get database manager
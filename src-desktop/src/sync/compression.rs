@@ -0,0 +1,129 @@
+// ====================================================================
+// SYNC PAYLOAD COMPRESSION
+// ====================================================================
+//
+// This module compresses push bodies and decodes compressed pull
+// responses when `SyncConfig.enable_compression` is set, and counts
+// the bytes moved so the savings show up in `SyncPerformanceMetrics`.
+// ====================================================================
+
+use super::*;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Encodings offered to the server for pull responses
+pub const ACCEPT_ENCODING: &str = "zstd, gzip";
+
+const ZSTD_LEVEL: i32 = 3;
+
+impl CompressionCodec {
+    /// Value of the `Content-Encoding` header for this codec
+    pub fn content_encoding(&self) -> &'static str {
+        match self {
+            CompressionCodec::Gzip => "gzip",
+            CompressionCodec::Zstd => "zstd",
+        }
+    }
+
+    pub fn compress(&self, body: &[u8]) -> Result<Vec<u8>, SyncError> {
+        let compressed = match self {
+            CompressionCodec::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(body).and_then(|_| encoder.finish())
+            }
+            CompressionCodec::Zstd => zstd::stream::encode_all(body, ZSTD_LEVEL),
+        };
+
+        compressed.map_err(|e| SyncError::Network(format!("Failed to compress request body: {}", e)))
+    }
+}
+
+/// Decode a response body according to its `Content-Encoding` header
+pub fn decompress_body(content_encoding: Option<&str>, body: &[u8]) -> Result<Vec<u8>, SyncError> {
+    let encoding = content_encoding.map(|e| e.trim().to_ascii_lowercase());
+
+    let decoded = match encoding.as_deref() {
+        None | Some("") | Some("identity") => return Ok(body.to_vec()),
+        Some("gzip") | Some("x-gzip") => {
+            let mut decoded = Vec::new();
+            flate2::read::GzDecoder::new(body).read_to_end(&mut decoded).map(|_| decoded)
+        }
+        Some("zstd") => zstd::stream::decode_all(body),
+        Some(other) => {
+            return Err(SyncError::Network(format!("Unsupported response encoding: {}", other)));
+        }
+    };
+
+    decoded.map_err(|e| SyncError::Network(format!("Failed to decompress response body: {}", e)))
+}
+
+/// Byte counts for sync traffic, on the wire and uncompressed
+#[derive(Default)]
+pub struct TransferStats {
+    request_bytes: AtomicU64,
+    request_bytes_uncompressed: AtomicU64,
+    response_bytes: AtomicU64,
+    response_bytes_uncompressed: AtomicU64,
+}
+
+impl TransferStats {
+    pub fn record_request(&self, wire_bytes: usize, uncompressed_bytes: usize) {
+        self.request_bytes.fetch_add(wire_bytes as u64, Ordering::Relaxed);
+        self.request_bytes_uncompressed.fetch_add(uncompressed_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_response(&self, wire_bytes: usize, uncompressed_bytes: usize) {
+        self.response_bytes.fetch_add(wire_bytes as u64, Ordering::Relaxed);
+        self.response_bytes_uncompressed.fetch_add(uncompressed_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn reset(&self) {
+        self.request_bytes.store(0, Ordering::Relaxed);
+        self.request_bytes_uncompressed.store(0, Ordering::Relaxed);
+        self.response_bytes.store(0, Ordering::Relaxed);
+        self.response_bytes_uncompressed.store(0, Ordering::Relaxed);
+    }
+
+    /// Copy the byte counts into the metrics, with the overall compression ratio
+    pub fn apply_to(&self, metrics: &mut SyncPerformanceMetrics) {
+        metrics.request_bytes = self.request_bytes.load(Ordering::Relaxed) as i64;
+        metrics.request_bytes_uncompressed = self.request_bytes_uncompressed.load(Ordering::Relaxed) as i64;
+        metrics.response_bytes = self.response_bytes.load(Ordering::Relaxed) as i64;
+        metrics.response_bytes_uncompressed = self.response_bytes_uncompressed.load(Ordering::Relaxed) as i64;
+
+        let wire = metrics.request_bytes + metrics.response_bytes;
+        let uncompressed = metrics.request_bytes_uncompressed + metrics.response_bytes_uncompressed;
+        metrics.compression_ratio = if wire > 0 {
+            uncompressed as f64 / wire as f64
+        } else {
+            1.0
+        };
+    }
+
+    /// Copy the byte counts into a run's history entry
+    pub fn apply_to_run(&self, run: &mut SyncRun) {
+        run.request_bytes = self.request_bytes.load(Ordering::Relaxed) as i64;
+        run.request_bytes_uncompressed = self.request_bytes_uncompressed.load(Ordering::Relaxed) as i64;
+        run.response_bytes = self.response_bytes.load(Ordering::Relaxed) as i64;
+        run.response_bytes_uncompressed = self.response_bytes_uncompressed.load(Ordering::Relaxed) as i64;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_bodies_decode_by_their_content_encoding() {
+        let body = br#"{"changes":[{"id":"c1","name":"Acme"}]}"#.repeat(20);
+
+        for codec in [CompressionCodec::Gzip, CompressionCodec::Zstd] {
+            let compressed = codec.compress(&body).unwrap();
+            assert!(compressed.len() < body.len());
+            assert_eq!(decompress_body(Some(codec.content_encoding()), &compressed).unwrap(), body);
+        }
+
+        assert_eq!(decompress_body(None, &body).unwrap(), body);
+        assert!(decompress_body(Some("br"), &body).is_err());
+    }
+}
@@ -8,45 +8,93 @@
 // ====================================================================
 
 use super::models::*;
-use sqlx::SqlitePool;
+use super::diff::{diff_records, set_path, FieldChange, FieldChoice, FieldDiff};
+use super::capture::{begin_sync_write, commit_sync_write};
+use super::clock::{self, HybridTimestamp, CLOCK_FIELD};
+use super::mapping::{RecordMapper, VersionUpdate};
+use super::queue::SyncQueue;
+use super::registry::SyncTableRegistry;
+use super::tombstones;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 use serde_json::Value;
 use std::collections::HashMap;
 
+/// Fields rewritten on every save; they never count as a field conflict
+const MERGE_BOOKKEEPING_FIELDS: &[&str] = &["updated_at", "sync_version", "last_synced_at", "is_dirty"];
+
 pub struct ConflictResolver {
     pool: SqlitePool,
+    table_registry: SyncTableRegistry,
+    record_mapper: RecordMapper,
 }
 
 impl ConflictResolver {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            table_registry: SyncTableRegistry::new(),
+            record_mapper: RecordMapper::new(),
+        }
     }
 
     /// Detect conflicts between local and remote data
+    ///
+    /// When the last-synced base version of the record is known, fields
+    /// changed on only one side are merged automatically and only fields
+    /// changed on both sides produce a conflict.
     pub async fn detect_conflicts(
         &self,
+        conn: &mut SqliteConnection,
         table_name: &str,
         local_data: &str,
         remote_data: &str,
         record_id: &str,
-    ) -> Result<Option<SyncConflict>, sqlx::Error> {
+    ) -> Result<ConflictCheck, sqlx::Error> {
         // Parse JSON data
-        let local_json: Value = serde_json::from_str(local_data)
+        let mut local_json: Value = serde_json::from_str(local_data)
             .map_err(|e| sqlx::Error::Protocol(format!("Invalid local JSON: {}", e)))?;
         
-        let remote_json: Value = serde_json::from_str(remote_data)
+        let mut remote_json: Value = serde_json::from_str(remote_data)
             .map_err(|e| sqlx::Error::Protocol(format!("Invalid remote JSON: {}", e)))?;
 
+        // The clocks are compared when resolving, not merged as content
+        let remote_clock = HybridTimestamp::from_record(&remote_json);
+        strip_clock(&mut local_json);
+        strip_clock(&mut remote_json);
+
         // Check if there are actual differences
         if local_json == remote_json {
-            return Ok(None); // No conflict
+            return Ok(ConflictCheck::NoConflict);
+        }
+
+        let base_data = self.get_base_version(&mut *conn, table_name, record_id).await?;
+        let base_json = match &base_data {
+            Some(base) => serde_json::from_str::<Value>(base).ok().map(|mut base| {
+                strip_clock(&mut base);
+                base
+            }),
+            None => None,
+        };
+
+        // Without a base every differing field counts as changed on both sides
+        let merge = Self::merge_three_way(
+            base_json.as_ref().unwrap_or(&Value::Null),
+            &local_json,
+            &remote_json,
+        );
+
+        if merge.conflicts.is_empty() {
+            let merged = serde_json::to_string(&merge.merged)
+                .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize merged data: {}", e)))?;
+            return Ok(ConflictCheck::AutoMerged(merged));
         }
 
         // Get current sync versions
-        let local_version = self.get_local_sync_version(table_name, record_id).await?;
+        let local_version = self.get_local_sync_version(&mut *conn, table_name, record_id).await?;
         let remote_version = self.get_remote_sync_version(&remote_json)?;
 
         // Create conflict record
-        let conflict = SyncConflict::new(
+        let mut conflict = SyncConflict::new(
             table_name.to_string(),
             record_id.to_string(),
             local_version,
@@ -54,11 +102,238 @@ impl ConflictResolver {
             Some(local_data.to_string()),
             Some(remote_data.to_string()),
         );
+        conflict.base_data = base_data;
+        conflict.local_clock = clock::record_clock(&mut *conn, table_name, record_id).await?.map(|c| c.to_string());
+        conflict.remote_clock = remote_clock.map(|c| c.to_string());
+        conflict.field_conflicts = Some(
+            serde_json::to_string(&merge.conflicts)
+                .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize field conflicts: {}", e)))?,
+        );
 
         // Store conflict in database
-        let conflict_id = self.store_conflict(&conflict).await?;
+        conflict.id = self.store_conflict(&mut *conn, &conflict).await?;
+
+        Ok(ConflictCheck::Conflict(Box::new(conflict)))
+    }
+
+    /// Three-way, field-level merge of a record
+    ///
+    /// Fields changed on one side only take that side's value; fields changed
+    /// on both sides to different values are reported as conflicts and take
+    /// the remote value in `merged` until resolved.
+    pub fn merge_three_way(base: &Value, local: &Value, remote: &Value) -> ThreeWayMerge {
+        let empty = serde_json::Map::new();
+        let (local_obj, remote_obj) = match (local, remote) {
+            (Value::Object(local_obj), Value::Object(remote_obj)) => (local_obj, remote_obj),
+            _ => {
+                // Not field-addressable, treat the whole record as one field
+                let conflicts = if local != remote {
+                    vec![FieldConflict {
+                        field: String::new(),
+                        base_value: base.clone(),
+                        local_value: local.clone(),
+                        remote_value: remote.clone(),
+                    }]
+                } else {
+                    Vec::new()
+                };
+                return ThreeWayMerge { merged: remote.clone(), conflicts };
+            }
+        };
+        let base_obj = base.as_object().unwrap_or(&empty);
+
+        let mut fields: Vec<&String> = base_obj.keys()
+            .chain(local_obj.keys())
+            .chain(remote_obj.keys())
+            .collect();
+        fields.sort();
+        fields.dedup();
+
+        let mut merged = serde_json::Map::new();
+        let mut conflicts = Vec::new();
+
+        for field in fields {
+            let base_value = base_obj.get(field).unwrap_or(&Value::Null);
+            let local_value = local_obj.get(field).unwrap_or(&Value::Null);
+            let remote_value = remote_obj.get(field).unwrap_or(&Value::Null);
+
+            // Bookkeeping fields change on every write, the remote copy is authoritative
+            if MERGE_BOOKKEEPING_FIELDS.contains(&field.as_str()) {
+                let value = if remote_obj.contains_key(field) { remote_value } else { local_value };
+                merged.insert(field.clone(), value.clone());
+                continue;
+            }
+
+            let local_changed = local_value != base_value;
+            let remote_changed = remote_value != base_value;
+
+            let value = match (local_changed, remote_changed) {
+                (false, false) => base_value,
+                (true, false) => local_value,
+                (false, true) => remote_value,
+                (true, true) if local_value == remote_value => local_value,
+                (true, true) => {
+                    conflicts.push(FieldConflict {
+                        field: field.clone(),
+                        base_value: base_value.clone(),
+                        local_value: local_value.clone(),
+                        remote_value: remote_value.clone(),
+                    });
+                    remote_value
+                }
+            };
+
+            merged.insert(field.clone(), value.clone());
+        }
+
+        ThreeWayMerge {
+            merged: Value::Object(merged),
+            conflicts,
+        }
+    }
+
+    /// Record the last-synced version of a record (the merge base)
+    pub async fn record_base_version(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+        data: &str,
+        sync_version: i32,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+            INSERT INTO sync_base_versions (table_name, record_id, data, sync_version, synced_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(table_name, record_id) DO UPDATE SET
+                data = excluded.data,
+                sync_version = excluded.sync_version,
+                synced_at = excluded.synced_at
+        "#;
+
+        sqlx::query(query)
+            .bind(table_name)
+            .bind(record_id)
+            .bind(data)
+            .bind(sync_version)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the last-synced version of a record
+    pub async fn get_base_version(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
+        let query = r#"
+            SELECT data
+            FROM sync_base_versions
+            WHERE table_name = ? AND record_id = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(table_name)
+            .bind(record_id)
+            .fetch_optional(&mut *conn)
+            .await?;
 
-        Ok(Some(conflict))
+        Ok(row.map(|r| r.get::<String, _>("data")))
+    }
+
+    /// Forget the base version of a deleted record
+    pub async fn clear_base_version(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_base_versions WHERE table_name = ? AND record_id = ?")
+            .bind(table_name)
+            .bind(record_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a conflict between a local edit and a remote delete
+    pub async fn detect_delete_conflict(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        local_data: &str,
+        record_id: &str,
+        remote_version: i32,
+        remote_clock: Option<HybridTimestamp>,
+    ) -> Result<SyncConflict, sqlx::Error> {
+        let local_version = self.get_local_sync_version(&mut *conn, table_name, record_id).await?;
+
+        let mut conflict = SyncConflict::new(
+            table_name.to_string(),
+            record_id.to_string(),
+            local_version,
+            remote_version,
+            Some(local_data.to_string()),
+            None, // Remote record was deleted
+        );
+        conflict.base_data = self.get_base_version(&mut *conn, table_name, record_id).await?;
+        conflict.local_clock = clock::record_clock(&mut *conn, table_name, record_id).await?.map(|c| c.to_string());
+        conflict.remote_clock = remote_clock.map(|c| c.to_string());
+
+        conflict.id = self.store_conflict(&mut *conn, &conflict).await?;
+
+        Ok(conflict)
+    }
+
+    /// Map a configured strategy to the resolution for a single conflict
+    ///
+    /// Returns `None` when the conflict must be held for manual resolution.
+    pub fn resolution_for_strategy(
+        conflict: &SyncConflict,
+        strategy: &ConflictResolutionStrategy,
+    ) -> Option<ConflictResolution> {
+        match strategy {
+            ConflictResolutionStrategy::LastWriteWins => {
+                if Self::local_written_last(conflict) {
+                    Some(ConflictResolution::LocalWins)
+                } else {
+                    Some(ConflictResolution::RemoteWins)
+                }
+            }
+            ConflictResolutionStrategy::LocalWins => Some(ConflictResolution::LocalWins),
+            ConflictResolutionStrategy::RemoteWins => Some(ConflictResolution::RemoteWins),
+            ConflictResolutionStrategy::Merge => Some(ConflictResolution::Merge),
+            ConflictResolutionStrategy::Manual => None,
+        }
+    }
+
+    /// Whether the local write happened after the remote one
+    ///
+    /// Ordered by hybrid logical clock when both sides have one. A side
+    /// without a clock (written before clocks existed, or by a client that
+    /// doesn't send one) falls back to `updated_at`, then sync versions.
+    fn local_written_last(conflict: &SyncConflict) -> bool {
+        let local = HybridTimestamp::parse_opt(conflict.local_clock.as_deref());
+        let remote = HybridTimestamp::parse_opt(conflict.remote_clock.as_deref());
+
+        if let (Some(local), Some(remote)) = (local, remote) {
+            return local > remote;
+        }
+
+        match (Self::written_at(conflict.local_data.as_deref()), Self::written_at(conflict.remote_data.as_deref())) {
+            (Some(local), Some(remote)) if local != remote => local > remote,
+            _ => conflict.local_version > conflict.remote_version,
+        }
+    }
+
+    /// The `updated_at` of a conflict side, if it has a readable one
+    fn written_at(data: Option<&str>) -> Option<chrono::DateTime<chrono::Utc>> {
+        let record: Value = serde_json::from_str(data?).ok()?;
+        clock::parse_write_time(record.get("updated_at")?.as_str()?)
     }
 
     /// Resolve a conflict using the specified resolution strategy
@@ -67,26 +342,45 @@ impl ConflictResolver {
         conflict_id: i64,
         resolution: ConflictResolution,
     ) -> Result<(), sqlx::Error> {
-        let conflict = self.get_conflict(conflict_id).await?;
-        
-        if conflict.is_none() {
-            return Err(sqlx::Error::RowNotFound);
-        }
+        let conflict = self.get_conflict(conflict_id).await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        let mut tx = begin_sync_write(&self.pool).await?;
+        self.resolve_loaded_conflict(&mut tx, conflict, resolution).await?;
+        commit_sync_write(tx).await?;
+
+        Ok(())
+    }
+
+    /// Resolve an already loaded conflict on an open connection or transaction
+    pub async fn resolve_loaded_conflict(
+        &self,
+        conn: &mut SqliteConnection,
+        mut conflict: SyncConflict,
+        resolution: ConflictResolution,
+    ) -> Result<(), sqlx::Error> {
+        // Local edits made while the conflict was held belong to the local side
+        let local_data = match self.record_mapper.read_record(&mut *conn, &conflict.table_name, &conflict.record_id).await? {
+            Some(local) if local.is_dirty => Some(
+                serde_json::to_string(&local.data)
+                    .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize local data: {}", e)))?,
+            ),
+            _ => conflict.local_data.clone(),
+        };
 
-        let mut conflict = conflict.unwrap();
-        
         // Apply resolution strategy
         let resolved_data = match resolution {
             ConflictResolution::LocalWins => {
-                conflict.local_data.clone()
+                local_data
             }
             ConflictResolution::RemoteWins => {
                 conflict.remote_data.clone()
             }
             ConflictResolution::Merge => {
                 self.merge_data(
-                    &conflict.local_data.unwrap_or_default(),
-                    &conflict.remote_data.unwrap_or_default(),
+                    conflict.base_data.as_deref(),
+                    local_data.as_deref().unwrap_or_default(),
+                    conflict.remote_data.as_deref().unwrap_or_default(),
                 ).await?
             }
             ConflictResolution::Manual => {
@@ -98,14 +392,8 @@ impl ConflictResolver {
 
         // Update conflict record
         conflict.resolve(resolution, resolved_data, "system".to_string());
-        
-        // Update conflict in database
-        self.update_conflict(&conflict).await?;
 
-        // Apply resolved data to the actual record
-        self.apply_resolved_data(&conflict).await?;
-
-        Ok(())
+        self.commit_resolution(conn, &conflict).await
     }
 
     /// Resolve conflict with manual data
@@ -114,31 +402,94 @@ impl ConflictResolver {
         conflict_id: i64,
         resolved_data: String,
     ) -> Result<(), sqlx::Error> {
-        let conflict = self.get_conflict(conflict_id).await?;
-        
-        if conflict.is_none() {
-            return Err(sqlx::Error::RowNotFound);
-        }
+        let mut conflict = self.get_conflict(conflict_id).await?
+            .ok_or(sqlx::Error::RowNotFound)?;
 
-        let mut conflict = conflict.unwrap();
-        
         // Update conflict record
         conflict.resolve(ConflictResolution::Manual, Some(resolved_data), "user".to_string());
-        
-        // Update conflict in database
-        self.update_conflict(&conflict).await?;
 
-        // Apply resolved data to the actual record
-        self.apply_resolved_data(&conflict).await?;
+        let mut tx = begin_sync_write(&self.pool).await?;
+        self.commit_resolution(&mut tx, &conflict).await?;
+        commit_sync_write(tx).await?;
 
         Ok(())
     }
 
+    /// Field-by-field diff of a conflict, including nested JSON fields
+    pub async fn get_conflict_diff(&self, conflict_id: i64) -> Result<Option<ConflictDiff>, sqlx::Error> {
+        let conflict = match self.get_conflict(conflict_id).await? {
+            Some(conflict) => conflict,
+            None => return Ok(None),
+        };
+
+        let (base, local, remote) = Self::parse_conflict_versions(&conflict)?;
+
+        Ok(Some(ConflictDiff {
+            conflict_id: conflict.id,
+            table_name: conflict.table_name.clone(),
+            record_id: conflict.record_id.clone(),
+            remote_deleted: conflict.remote_data.is_none(),
+            fields: diff_records(&base, &local, &remote, MERGE_BOOKKEEPING_FIELDS),
+        }))
+    }
+
+    /// Resolve a conflict by picking local, remote or a custom value per field
+    ///
+    /// Fields changed on one side only default to that side; every field
+    /// changed on both sides needs a choice. Returns the composed record.
+    pub async fn resolve_conflict_fields(
+        &self,
+        conflict_id: i64,
+        choices: HashMap<String, FieldChoice>,
+    ) -> Result<String, sqlx::Error> {
+        let conflict = self.get_conflict(conflict_id).await?
+            .ok_or(sqlx::Error::RowNotFound)?;
+
+        if conflict.is_resolved() {
+            return Err(sqlx::Error::Protocol(format!("Conflict {} is already resolved", conflict_id)));
+        }
+
+        let (base, local, remote) = Self::parse_conflict_versions(&conflict)?;
+        let mut composed = local.as_object().cloned().unwrap_or_default();
+
+        for diff in diff_records(&base, &local, &remote, MERGE_BOOKKEEPING_FIELDS) {
+            let value = match (choices.get(&diff.path), &diff.change) {
+                (Some(FieldChoice::Local), _) => diff.local_value,
+                (Some(FieldChoice::Remote), _) => diff.remote_value,
+                (Some(FieldChoice::Custom(value)), _) => value.clone(),
+                (None, FieldChange::LocalOnly) => diff.local_value,
+                (None, FieldChange::RemoteOnly) => diff.remote_value,
+                (None, FieldChange::Conflict) => {
+                    return Err(sqlx::Error::Protocol(format!("No choice given for conflicting field {}", diff.path)));
+                }
+            };
+
+            set_path(&mut composed, &diff.path, value);
+        }
+
+        // As in a three-way merge, the remote copy is authoritative for bookkeeping
+        if let Value::Object(remote) = &remote {
+            for field in MERGE_BOOKKEEPING_FIELDS {
+                if let Some(value) = remote.get(*field) {
+                    composed.insert(field.to_string(), value.clone());
+                }
+            }
+        }
+
+        let resolved_data = serde_json::to_string(&Value::Object(composed))
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize resolved data: {}", e)))?;
+
+        self.resolve_conflict_manual(conflict_id, resolved_data.clone()).await?;
+
+        Ok(resolved_data)
+    }
+
     /// Get all unresolved conflicts
     pub async fn get_unresolved_conflicts(&self) -> Result<Vec<SyncConflict>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, local_version, remote_version,
-                   local_data, remote_data, resolution, resolved_data,
+                   local_data, remote_data, base_data, field_conflicts,
+                   local_clock, remote_clock, resolution, resolved_data,
                    created_at, resolved_at, resolved_by
             FROM sync_conflicts
             WHERE resolution IS NULL
@@ -156,7 +507,8 @@ impl ConflictResolver {
     pub async fn get_conflicts_for_table(&self, table_name: &str) -> Result<Vec<SyncConflict>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, local_version, remote_version,
-                   local_data, remote_data, resolution, resolved_data,
+                   local_data, remote_data, base_data, field_conflicts,
+                   local_clock, remote_clock, resolution, resolved_data,
                    created_at, resolved_at, resolved_by
             FROM sync_conflicts
             WHERE table_name = ? AND resolution IS NULL
@@ -252,18 +604,9 @@ impl ConflictResolver {
         let mut resolved_count = 0;
 
         for conflict in conflicts {
-            let resolution = match strategy {
-                ConflictResolutionStrategy::LastWriteWins => {
-                    if conflict.local_version > conflict.remote_version {
-                        ConflictResolution::LocalWins
-                    } else {
-                        ConflictResolution::RemoteWins
-                    }
-                }
-                ConflictResolutionStrategy::LocalWins => ConflictResolution::LocalWins,
-                ConflictResolutionStrategy::RemoteWins => ConflictResolution::RemoteWins,
-                ConflictResolutionStrategy::Merge => ConflictResolution::Merge,
-                ConflictResolutionStrategy::Manual => continue, // Skip manual conflicts
+            let resolution = match Self::resolution_for_strategy(&conflict, &strategy) {
+                Some(resolution) => resolution,
+                None => continue, // Skip manual conflicts
             };
 
             if let Ok(()) = self.resolve_conflict(conflict.id, resolution).await {
@@ -278,15 +621,33 @@ impl ConflictResolver {
     // PRIVATE HELPER METHODS
     // ====================================================================
 
-    async fn get_local_sync_version(&self, table_name: &str, record_id: &str) -> Result<i32, sqlx::Error> {
-        let query = format!("SELECT sync_version FROM {} WHERE id = ?", table_name);
+    /// Primary key column of a registered sync table
+    ///
+    /// Unregistered table names are rejected before they reach any SQL.
+    fn primary_key(&self, table_name: &str) -> Result<&'static str, sqlx::Error> {
+        self.table_registry
+            .get(table_name)
+            .map(|table| table.primary_key)
+            .ok_or_else(|| sqlx::Error::Configuration(format!("Unknown sync table: {}", table_name).into()))
+    }
+
+    async fn get_local_sync_version(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<i32, sqlx::Error> {
+        let query = format!("SELECT sync_version FROM {} WHERE {} = ?", table_name, self.primary_key(table_name)?);
         
         let row = sqlx::query(&query)
             .bind(record_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *conn)
             .await?;
 
-        Ok(row.map(|r| r.get::<i32, _>("sync_version")).unwrap_or(0))
+        // sync_version is nullable in the cache schema
+        Ok(row
+            .and_then(|r| r.try_get::<Option<i32>, _>("sync_version").ok().flatten())
+            .unwrap_or(0))
     }
 
     fn get_remote_sync_version(&self, remote_data: &Value) -> Result<i32, sqlx::Error> {
@@ -300,12 +661,13 @@ impl ConflictResolver {
         }
     }
 
-    async fn store_conflict(&self, conflict: &SyncConflict) -> Result<i64, sqlx::Error> {
+    async fn store_conflict(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<i64, sqlx::Error> {
         let query = r#"
             INSERT INTO sync_conflicts (
                 table_name, record_id, local_version, remote_version,
-                local_data, remote_data, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?)
+                local_data, remote_data, base_data, field_conflicts,
+                local_clock, remote_clock, created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         "#;
 
         let result = sqlx::query(query)
@@ -315,17 +677,22 @@ impl ConflictResolver {
             .bind(conflict.remote_version)
             .bind(&conflict.local_data)
             .bind(&conflict.remote_data)
+            .bind(&conflict.base_data)
+            .bind(&conflict.field_conflicts)
+            .bind(&conflict.local_clock)
+            .bind(&conflict.remote_clock)
             .bind(&conflict.created_at)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
 
         Ok(result.last_insert_rowid())
     }
 
-    async fn get_conflict(&self, conflict_id: i64) -> Result<Option<SyncConflict>, sqlx::Error> {
+    pub async fn get_conflict(&self, conflict_id: i64) -> Result<Option<SyncConflict>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, local_version, remote_version,
-                   local_data, remote_data, resolution, resolved_data,
+                   local_data, remote_data, base_data, field_conflicts,
+                   local_clock, remote_clock, resolution, resolved_data,
                    created_at, resolved_at, resolved_by
             FROM sync_conflicts
             WHERE id = ?
@@ -339,7 +706,7 @@ impl ConflictResolver {
         Ok(row)
     }
 
-    async fn update_conflict(&self, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+    async fn update_conflict(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
         let query = r#"
             UPDATE sync_conflicts 
             SET resolution = ?, resolved_data = ?, resolved_at = ?, resolved_by = ?
@@ -352,32 +719,147 @@ impl ConflictResolver {
             .bind(&conflict.resolved_at)
             .bind(&conflict.resolved_by)
             .bind(conflict.id)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
 
         Ok(())
     }
 
-    async fn apply_resolved_data(&self, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+    /// Persist a resolution: the conflict row, the record, its base version
+    /// and any follow-up push all commit together or not at all
+    async fn commit_resolution(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+        self.update_conflict(&mut *conn, conflict).await?;
+        self.apply_resolved_data(&mut *conn, conflict).await?;
+        self.stamp_resolved_clock(&mut *conn, conflict).await?;
+        self.advance_base_to_remote(&mut *conn, conflict).await?;
+        self.requeue_resolved_data(&mut *conn, conflict).await?;
+
+        Ok(())
+    }
+
+    async fn apply_resolved_data(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
         if let Some(resolved_data) = &conflict.resolved_data {
-            let query = format!(
-                "UPDATE {} SET data = ?, sync_version = sync_version + 1, last_synced_at = ?, is_dirty = ? WHERE id = ?",
-                conflict.table_name
-            );
-
-            sqlx::query(&query)
-                .bind(resolved_data)
-                .bind(chrono::Utc::now().to_rfc3339())
-                .bind(false)
-                .bind(&conflict.record_id)
-                .execute(&self.pool)
+            self.record_mapper
+                .write_record(
+                    &mut *conn,
+                    &conflict.table_name,
+                    &conflict.record_id,
+                    resolved_data,
+                    VersionUpdate::Increment,
+                    Self::diverges_from_remote(conflict),
+                )
                 .await?;
+            tombstones::clear_tombstone(&mut *conn, &conflict.table_name, &conflict.record_id).await?;
+        } else if conflict.resolution == Some(ConflictResolution::RemoteWins) && conflict.remote_data.is_none() {
+            // Remote side deleted the record and the delete won
+            let schema = self.record_mapper.schema(&mut *conn, &conflict.table_name).await?;
+
+            tombstones::apply_delete(
+                &mut *conn,
+                &conflict.table_name,
+                schema.primary_key(),
+                &conflict.record_id,
+                schema.has_column(tombstones::SOFT_DELETE_COLUMN),
+                conflict.remote_version,
+                HybridTimestamp::parse_opt(conflict.remote_clock.as_deref()).as_ref(),
+                None,
+            )
+            .await?;
         }
 
         Ok(())
     }
 
-    async fn merge_data(&self, local_data: &str, remote_data: &str) -> Result<Option<String>, sqlx::Error> {
+    /// Stamp the resolved record's clock
+    ///
+    /// Data that differs from the remote copy is a new local write and gets
+    /// a fresh timestamp; otherwise the record keeps the remote write's clock.
+    async fn stamp_resolved_clock(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+        let stamp = if Self::diverges_from_remote(conflict) {
+            Some(clock::tick(&mut *conn).await?)
+        } else if conflict.resolved_data.is_some() {
+            HybridTimestamp::parse_opt(conflict.remote_clock.as_deref())
+        } else {
+            None
+        };
+
+        if let Some(stamp) = stamp {
+            clock::set_record_clock(&mut *conn, &conflict.table_name, &conflict.record_id, &stamp).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Resolved data the server doesn't have yet goes back into the push queue
+    ///
+    /// When the server already holds the resolved version, the local changes
+    /// still queued for the record are dropped instead, so they can't push
+    /// the losing side back over it.
+    async fn requeue_resolved_data(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+        if !Self::diverges_from_remote(conflict) {
+            SyncQueue::cancel_unpushed_changes_in(conn, &conflict.table_name, &conflict.record_id).await?;
+            return Ok(());
+        }
+
+        // A remote delete that lost has to recreate the record
+        let operation = if conflict.remote_data.is_some() {
+            SyncOperation::Update
+        } else {
+            SyncOperation::Insert
+        };
+
+        SyncQueue::enqueue_change_in(conn, &conflict.table_name, &conflict.record_id, operation, conflict.resolved_data.clone())
+            .await?;
+
+        Ok(())
+    }
+
+    fn diverges_from_remote(conflict: &SyncConflict) -> bool {
+        let resolved = match &conflict.resolved_data {
+            Some(resolved) => resolved,
+            None => return false,
+        };
+
+        match &conflict.remote_data {
+            Some(remote) => {
+                serde_json::from_str::<Value>(remote).ok() != serde_json::from_str::<Value>(resolved).ok()
+            }
+            None => true,
+        }
+    }
+
+    fn parse_conflict_versions(conflict: &SyncConflict) -> Result<(Value, Value, Value), sqlx::Error> {
+        let parse = |data: &Option<String>, side: &str| -> Result<Value, sqlx::Error> {
+            match data {
+                Some(json) => serde_json::from_str(json)
+                    .map_err(|e| sqlx::Error::Protocol(format!("Invalid {} JSON: {}", side, e))),
+                None => Ok(Value::Null),
+            }
+        };
+
+        Ok((
+            parse(&conflict.base_data, "base")?,
+            parse(&conflict.local_data, "local")?,
+            parse(&conflict.remote_data, "remote")?,
+        ))
+    }
+
+    /// The remote copy is what the server holds, so it becomes the new base
+    async fn advance_base_to_remote(&self, conn: &mut SqliteConnection, conflict: &SyncConflict) -> Result<(), sqlx::Error> {
+        match &conflict.remote_data {
+            Some(remote_data) => {
+                self.record_base_version(conn, &conflict.table_name, &conflict.record_id, remote_data, conflict.remote_version).await
+            }
+            None => self.clear_base_version(conn, &conflict.table_name, &conflict.record_id).await,
+        }
+    }
+
+    async fn merge_data(
+        &self,
+        base_data: Option<&str>,
+        local_data: &str,
+        remote_data: &str,
+    ) -> Result<Option<String>, sqlx::Error> {
         // Parse JSON data
         let local_json: Value = serde_json::from_str(local_data)
             .map_err(|e| sqlx::Error::Protocol(format!("Invalid local JSON: {}", e)))?;
@@ -385,18 +867,26 @@ impl ConflictResolver {
         let remote_json: Value = serde_json::from_str(remote_data)
             .map_err(|e| sqlx::Error::Protocol(format!("Invalid remote JSON: {}", e)))?;
 
-        // Simple merge strategy: prefer non-null values, remote wins on conflicts
-        let mut merged = local_json.clone();
-        
-        if let Value::Object(ref mut local_obj) = merged {
-            if let Value::Object(remote_obj) = remote_json {
-                for (key, remote_value) in remote_obj {
-                    if !remote_value.is_null() {
-                        local_obj.insert(key, remote_value);
+        let merged = match base_data.and_then(|base| serde_json::from_str::<Value>(base).ok()) {
+            // Three-way merge, remote wins on fields changed on both sides
+            Some(base_json) => Self::merge_three_way(&base_json, &local_json, &remote_json).merged,
+            // No common ancestor: prefer non-null values, remote wins on conflicts
+            None => {
+                let mut merged = local_json.clone();
+
+                if let Value::Object(ref mut local_obj) = merged {
+                    if let Value::Object(remote_obj) = remote_json {
+                        for (key, remote_value) in remote_obj {
+                            if !remote_value.is_null() {
+                                local_obj.insert(key, remote_value);
+                            }
+                        }
                     }
                 }
+
+                merged
             }
-        }
+        };
 
         // Serialize merged data
         let merged_json = serde_json::to_string(&merged)
@@ -406,6 +896,46 @@ impl ConflictResolver {
     }
 }
 
+// ====================================================================
+// THREE-WAY MERGE MODELS
+// ====================================================================
+
+/// Result of checking a pulled record against unsynced local edits
+#[derive(Debug, Clone)]
+pub enum ConflictCheck {
+    /// Local and remote content are identical
+    NoConflict,
+    /// Each field changed on at most one side; holds the merged JSON object
+    AutoMerged(String),
+    /// At least one field changed on both sides
+    Conflict(Box<SyncConflict>),
+}
+
+/// Field-by-field view of a conflict for the resolution UI
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ConflictDiff {
+    pub conflict_id: i64,
+    pub table_name: String,
+    pub record_id: String,
+    pub remote_deleted: bool,
+    pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ThreeWayMerge {
+    pub merged: Value,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+/// A field changed on both sides since the last sync
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FieldConflict {
+    pub field: String,
+    pub base_value: Value,
+    pub local_value: Value,
+    pub remote_value: Value,
+}
+
 // ====================================================================
 // CONFLICT STATISTICS MODEL
 // ====================================================================
@@ -449,8 +979,10 @@ impl ConflictResolver {
 
             // Check for common conflict patterns
             if self.is_timestamp_conflict(&local_json, &remote_json) {
+                // Last write wins: keep whichever side was updated most recently
+                let local_is_newer = local_json.get("updated_at").and_then(Value::as_str) > remote_json.get("updated_at").and_then(Value::as_str);
                 suggestions.push(ConflictResolutionSuggestion {
-                    resolution: ConflictResolution::LastWriteWins,
+                    resolution: if local_is_newer { ConflictResolution::LocalWins } else { ConflictResolution::RemoteWins },
                     confidence: 0.9,
                     reason: "Timestamp-based conflict detected".to_string(),
                 });
@@ -495,3 +1027,137 @@ pub struct ConflictResolutionSuggestion {
     pub confidence: f64,
     pub reason: String,
 }
+
+/// Drop the clock field from a record before comparing its content
+fn strip_clock(record: &mut Value) {
+    if let Value::Object(fields) = record {
+        fields.remove(CLOCK_FIELD);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+    use serde_json::json;
+
+    #[test]
+    fn fields_changed_on_one_side_merge_and_fields_changed_on_both_conflict() {
+        let base = json!({ "name": "Acme", "phone": "1", "city": "Oslo" });
+        let local = json!({ "name": "Acme Local", "phone": "1", "city": "Bergen" });
+        let remote = json!({ "name": "Acme", "phone": "2", "city": "Tromsø" });
+
+        let merge = ConflictResolver::merge_three_way(&base, &local, &remote);
+
+        assert_eq!(merge.merged["name"], "Acme Local");
+        assert_eq!(merge.merged["phone"], "2");
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.conflicts[0].field, "city");
+        assert_eq!(merge.conflicts[0].base_value, "Oslo");
+    }
+
+    async fn detect(resolver: &ConflictResolver, local: &str, remote: &str) -> ConflictCheck {
+        let mut conn = resolver.pool.acquire().await.unwrap();
+        resolver.detect_conflicts(&mut conn, "companies", local, remote, "c1").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_known_base_turns_disjoint_edits_into_an_auto_merge() {
+        let resolver = ConflictResolver::new(test_cache_pool().await);
+        let local = json!({ "id": "c1", "name": "Acme Local", "phone": "1" }).to_string();
+        let remote = json!({ "id": "c1", "name": "Acme", "phone": "2" }).to_string();
+
+        // Without a base both differing fields count as changed on both sides
+        let check = detect(&resolver, &local, &remote).await;
+        assert!(matches!(check, ConflictCheck::Conflict(_)));
+
+        let base = json!({ "id": "c1", "name": "Acme", "phone": "1" }).to_string();
+        let mut conn = resolver.pool.acquire().await.unwrap();
+        resolver.record_base_version(&mut conn, "companies", "c1", &base, 1).await.unwrap();
+        drop(conn);
+
+        match detect(&resolver, &local, &remote).await {
+            ConflictCheck::AutoMerged(merged) => {
+                let merged: Value = serde_json::from_str(&merged).unwrap();
+                assert_eq!(merged, json!({ "id": "c1", "name": "Acme Local", "phone": "2" }));
+            }
+            other => panic!("expected an auto-merge, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn field_resolution_needs_a_choice_for_every_conflicting_field() {
+        let resolver = ConflictResolver::new(test_cache_pool().await);
+        let local = json!({ "id": "c1", "name": "Acme Local", "phone": "1" }).to_string();
+        let remote = json!({ "id": "c1", "name": "Acme Remote", "phone": "2" }).to_string();
+        let conflict = match detect(&resolver, &local, &remote).await {
+            ConflictCheck::Conflict(conflict) => conflict,
+            other => panic!("expected a conflict, got {:?}", other),
+        };
+
+        let diff = resolver.get_conflict_diff(conflict.id).await.unwrap().unwrap();
+        let paths: Vec<&str> = diff.fields.iter().map(|field| field.path.as_str()).collect();
+        assert_eq!(paths, ["name", "phone"]);
+
+        let choices = HashMap::from([("name".to_string(), FieldChoice::Local)]);
+        assert!(resolver.resolve_conflict_fields(conflict.id, choices).await.is_err());
+        assert_eq!(resolver.count_conflicts().await.unwrap(), 1);
+    }
+
+    fn clocked_conflict(local_clock: Option<&str>, local_updated: &str, remote_updated: &str) -> SyncConflict {
+        let mut conflict = SyncConflict::new(
+            "companies".to_string(),
+            "c1".to_string(),
+            3,
+            2,
+            Some(json!({ "id": "c1", "updated_at": local_updated }).to_string()),
+            Some(json!({ "id": "c1", "updated_at": remote_updated }).to_string()),
+        );
+        conflict.local_clock = local_clock.map(str::to_string);
+        conflict
+    }
+
+    #[test]
+    fn a_remote_write_without_a_clock_is_ordered_by_write_time() {
+        let local_clock = Some("1767225600000:000000:device-a");
+
+        // The remote write came later, even though only the local side has a clock
+        let conflict = clocked_conflict(local_clock, "2026-01-01 00:00:00", "2026-01-02T00:00:00Z");
+        assert_eq!(
+            ConflictResolver::resolution_for_strategy(&conflict, &ConflictResolutionStrategy::LastWriteWins),
+            Some(ConflictResolution::RemoteWins)
+        );
+
+        let conflict = clocked_conflict(local_clock, "2026-01-03 00:00:00", "2026-01-02T00:00:00Z");
+        assert_eq!(
+            ConflictResolver::resolution_for_strategy(&conflict, &ConflictResolutionStrategy::LastWriteWins),
+            Some(ConflictResolution::LocalWins)
+        );
+    }
+
+    #[test]
+    fn bookkeeping_fields_are_left_out_of_the_diff() {
+        let base = json!({ "id": "c1", "name": "Acme", "updated_at": "2026-01-01T00:00:00Z" });
+        let local = json!({ "id": "c1", "name": "Acme", "updated_at": "2026-01-02T00:00:00Z" });
+        let remote = json!({ "id": "c1", "name": "Acme", "updated_at": "2026-01-03T00:00:00Z" });
+
+        assert!(diff_records(&base, &local, &remote, MERGE_BOOKKEEPING_FIELDS).is_empty());
+    }
+
+    #[tokio::test]
+    async fn field_choices_need_no_entry_for_bookkeeping_fields() {
+        let resolver = ConflictResolver::new(test_cache_pool().await);
+        let local = json!({ "id": "c1", "name": "Acme Local", "updated_at": "2026-01-02T00:00:00Z" }).to_string();
+        let remote = json!({ "id": "c1", "name": "Acme Remote", "updated_at": "2026-01-03T00:00:00Z" }).to_string();
+        let conflict = match detect(&resolver, &local, &remote).await {
+            ConflictCheck::Conflict(conflict) => conflict,
+            other => panic!("expected a conflict, got {:?}", other),
+        };
+
+        let choices = HashMap::from([("name".to_string(), FieldChoice::Local)]);
+        let resolved: Value = serde_json::from_str(&resolver.resolve_conflict_fields(conflict.id, choices).await.unwrap()).unwrap();
+
+        assert_eq!(resolved["name"], "Acme Local");
+        assert_eq!(resolved["updated_at"], "2026-01-03T00:00:00Z");
+    }
+}
@@ -0,0 +1,326 @@
+// ====================================================================
+// SYNC CONNECTIVITY MONITOR
+// ====================================================================
+//
+// This module owns the long-lived task that probes the sync server and
+// tracks online/offline transitions. A flip is only accepted after
+// `CONFIRMATIONS_REQUIRED` consecutive probes agree, so a single
+// dropped request doesn't bounce the UI between states. Transitions
+// are emitted on `CONNECTIVITY_EVENT`, and when the connection comes
+// back (or the first probe after startup finds it up) the pending
+// `sync_queue` is flushed with a reconnect-triggered sync instead of
+// waiting for the next background tick.
+// ====================================================================
+
+use super::*;
+use crate::database_init::get_database_manager;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::{watch, Mutex};
+
+/// Event name the frontend listens on for online/offline changes
+pub const CONNECTIVITY_EVENT: &str = "sync://connectivity";
+
+/// Time between probes while the state is settled
+const PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Time between probes while a possible flip is being confirmed
+const CONFIRM_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Consecutive probes that must disagree with the current state to flip it
+const CONFIRMATIONS_REQUIRED: u32 = 2;
+
+const STATE_UNKNOWN: u8 = 0;
+const STATE_ONLINE: u8 = 1;
+const STATE_OFFLINE: u8 = 2;
+
+/// Last known connectivity, shared with every engine built by `SharedSyncEngine`
+pub struct ConnectivityState {
+    state: AtomicU8,
+    changed_at: StdMutex<Option<String>>,
+}
+
+impl ConnectivityState {
+    pub fn new() -> Self {
+        Self {
+            state: AtomicU8::new(STATE_UNKNOWN),
+            changed_at: StdMutex::new(None),
+        }
+    }
+
+    /// Whether we're online, or `None` before the monitor's first probe
+    pub fn is_online(&self) -> Option<bool> {
+        match self.state.load(Ordering::SeqCst) {
+            STATE_ONLINE => Some(true),
+            STATE_OFFLINE => Some(false),
+            _ => None,
+        }
+    }
+
+    pub fn changed_at(&self) -> Option<String> {
+        self.changed_at.lock().unwrap_or_else(|p| p.into_inner()).clone()
+    }
+
+    fn set(&self, online: bool) {
+        let state = if online { STATE_ONLINE } else { STATE_OFFLINE };
+        self.state.store(state, Ordering::SeqCst);
+        *self.changed_at.lock().unwrap_or_else(|p| p.into_inner()) = Some(SyncUtils::current_timestamp());
+    }
+}
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Payload of `CONNECTIVITY_EVENT` and the `get_connectivity_status` command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectivityStatus {
+    /// `None` until the first probe has completed
+    pub is_online: Option<bool>,
+    pub changed_at: Option<String>,
+    pub monitoring: bool,
+}
+
+pub struct ConnectivityMonitor {
+    engine: SharedSyncEngine,
+    task: Mutex<Option<watch::Sender<bool>>>,
+}
+
+impl ConnectivityMonitor {
+    pub fn new(engine: SharedSyncEngine) -> Self {
+        Self {
+            engine,
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Start the monitor loop (called once the database is ready)
+    pub async fn start(&self, app_handle: tauri::AppHandle<tauri::Wry>) {
+        let mut task = self.task.lock().await;
+
+        if let Some(previous) = task.take() {
+            let _ = previous.send(true);
+        }
+
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        tauri::async_runtime::spawn(run_monitor(self.engine.clone(), app_handle, cancel_rx));
+        *task = Some(cancel_tx);
+
+        println!("📡 [CONNECTIVITY] Connectivity monitor started");
+    }
+
+    /// Stop the monitor loop
+    pub async fn stop(&self) -> bool {
+        match self.task.lock().await.take() {
+            Some(previous) => {
+                let _ = previous.send(true);
+                println!("⏹️ [CONNECTIVITY] Connectivity monitor stopped");
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get the last known connectivity
+    pub async fn status(&self) -> ConnectivityStatus {
+        let connectivity = self.engine.connectivity();
+
+        ConnectivityStatus {
+            is_online: connectivity.is_online(),
+            changed_at: connectivity.changed_at(),
+            monitoring: self.task.lock().await.is_some(),
+        }
+    }
+}
+
+// ====================================================================
+// MONITOR LOOP
+// ====================================================================
+
+async fn run_monitor(
+    engine: SharedSyncEngine,
+    app_handle: tauri::AppHandle<tauri::Wry>,
+    mut cancel_rx: watch::Receiver<bool>,
+) {
+    let connectivity = engine.connectivity();
+    let mut flips = FlipConfirmation::default();
+    let mut interval = Duration::ZERO;
+
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = cancel_rx.changed() => break,
+        }
+
+        if *cancel_rx.borrow() {
+            break;
+        }
+
+        let online = probe(&engine).await;
+        let previous = connectivity.is_online();
+        let accepted = flips.observe(previous, online);
+
+        interval = if flips.is_confirming() { CONFIRM_INTERVAL } else { PROBE_INTERVAL };
+        if !accepted {
+            continue;
+        }
+
+        connectivity.set(online);
+
+        println!("📡 [CONNECTIVITY] Now {}", if online { "online" } else { "offline" });
+        emit_status(&app_handle, &connectivity);
+
+        if flushes_queue(previous, online) {
+            flush_queue(&engine).await;
+        }
+    }
+
+    println!("⏹️ [CONNECTIVITY] Monitor loop exited");
+}
+
+/// Counts probes that disagree with the current state until enough agree to flip it
+#[derive(Default)]
+struct FlipConfirmation {
+    disagreeing_probes: u32,
+}
+
+impl FlipConfirmation {
+    /// Whether `online` should become the new state
+    fn observe(&mut self, previous: Option<bool>, online: bool) -> bool {
+        if previous == Some(online) {
+            self.disagreeing_probes = 0;
+            return false;
+        }
+
+        // The first probe sets the state; later flips need confirming
+        self.disagreeing_probes += 1;
+        if previous.is_some() && self.disagreeing_probes < CONFIRMATIONS_REQUIRED {
+            return false;
+        }
+
+        self.disagreeing_probes = 0;
+        true
+    }
+
+    fn is_confirming(&self) -> bool {
+        self.disagreeing_probes > 0
+    }
+}
+
+/// Whether an accepted state should push the queue
+///
+/// Coming online from unknown counts too, so changes queued before a
+/// restart go out without waiting for the next background tick.
+fn flushes_queue(previous: Option<bool>, online: bool) -> bool {
+    online && previous != Some(true)
+}
+
+async fn probe(engine: &SharedSyncEngine) -> bool {
+    match engine.get().await {
+        Ok(engine) => engine.check_online().await,
+        Err(_) => false,
+    }
+}
+
+fn emit_status(app_handle: &tauri::AppHandle<tauri::Wry>, connectivity: &ConnectivityState) {
+    let status = ConnectivityStatus {
+        is_online: connectivity.is_online(),
+        changed_at: connectivity.changed_at(),
+        monitoring: true,
+    };
+
+    if let Err(e) = app_handle.emit(CONNECTIVITY_EVENT, &status) {
+        println!("⚠️ [CONNECTIVITY] Failed to emit connectivity event: {}", e);
+    }
+}
+
+/// Push the changes made while offline
+async fn flush_queue(engine: &SharedSyncEngine) {
+    match reconnect_sync(engine).await {
+        Ok(Some(report)) => {
+            println!("✅ [CONNECTIVITY] Reconnect sync processed {} records", report.records_processed);
+        }
+        Ok(None) => {}
+        Err(SyncError::AlreadyRunning) => {
+            println!("⏭️ [CONNECTIVITY] Sync already running, it will push the queued changes");
+        }
+        Err(e) => {
+            println!("❌ [CONNECTIVITY] Reconnect sync failed: {}", e);
+        }
+    }
+}
+
+/// Sync the workspace when there are changes waiting; `None` when there was nothing to do
+async fn reconnect_sync(engine: &SharedSyncEngine) -> Result<Option<SyncReport>, SyncError> {
+    let engine = engine.get().await?;
+
+    let unsynced = engine.count_unsynced_changes().await?;
+    if unsynced == 0 {
+        return Ok(None);
+    }
+
+    let workspace_id = match reconnect_workspace(&engine).await? {
+        Some(workspace_id) => workspace_id,
+        None => {
+            println!("⏸️ [CONNECTIVITY] {} changes waiting but no workspace has been synced yet", unsynced);
+            return Ok(None);
+        }
+    };
+
+    println!("📤 [CONNECTIVITY] Online, flushing {} changes for workspace {}", unsynced, workspace_id);
+    engine
+        .sync_workspace_triggered_by(&workspace_id, SyncTrigger::Reconnect)
+        .await
+        .map(Some)
+}
+
+/// The background sync workspace, or else the one synced most recently
+async fn reconnect_workspace(engine: &SyncEngine) -> Result<Option<String>, SyncError> {
+    let db_manager = get_database_manager().map_err(SyncError::Configuration)?;
+    let sqlite_pool = db_manager.get_sqlite_pool().await.map_err(SyncError::Configuration)?;
+
+    if let Some(workspace_id) = SyncSettingsStore::new(sqlite_pool).get_background_workspace().await? {
+        return Ok(Some(workspace_id));
+    }
+
+    engine.last_synced_workspace().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_first_probe_sets_the_state_immediately() {
+        let mut flips = FlipConfirmation::default();
+
+        assert!(flips.observe(None, false));
+        assert!(!flips.is_confirming());
+    }
+
+    #[test]
+    fn a_flip_needs_consecutive_disagreeing_probes() {
+        let mut flips = FlipConfirmation::default();
+
+        assert!(!flips.observe(Some(true), false));
+        assert!(flips.is_confirming());
+
+        // An agreeing probe in between starts the count over
+        assert!(!flips.observe(Some(true), true));
+        assert!(!flips.observe(Some(true), false));
+        assert!(flips.observe(Some(true), false));
+        assert!(!flips.is_confirming());
+    }
+
+    #[test]
+    fn coming_online_after_startup_or_an_outage_flushes_the_queue() {
+        assert!(flushes_queue(None, true));
+        assert!(flushes_queue(Some(false), true));
+        assert!(!flushes_queue(None, false));
+        assert!(!flushes_queue(Some(true), false));
+    }
+}
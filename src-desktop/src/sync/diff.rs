@@ -0,0 +1,285 @@
+// ====================================================================
+// SYNC RECORD DIFF
+// ====================================================================
+//
+// This module computes field-by-field differences between versions of
+// a synced record. Columns that hold JSON encoded as text (e.g.
+// `custom_fields`, `tags`) are decoded so that nested values are
+// addressed by dotted paths such as `custom_fields.region`.
+// ====================================================================
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub path: String,
+    pub base_value: Value,
+    pub local_value: Value,
+    pub remote_value: Value,
+    pub change: FieldChange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FieldChange {
+    /// Only the local side changed since the last sync
+    LocalOnly,
+    /// Only the remote side changed since the last sync
+    RemoteOnly,
+    /// Both sides changed to different values
+    Conflict,
+}
+
+/// A field a queued change would push, against the last synced version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingFieldChange {
+    pub path: String,
+    pub synced_value: Value,
+    pub pending_value: Value,
+    /// One readable line, e.g. `job_title: "CEO" → "CTO"`
+    pub summary: String,
+}
+
+/// Choice made by the user for a single field of a conflict
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "choice", content = "value", rename_all = "snake_case")]
+pub enum FieldChoice {
+    Local,
+    Remote,
+    Custom(Value),
+}
+
+/// Flatten a record into dotted paths, decoding JSON-encoded text columns
+pub fn flatten_record(record: &Value) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+
+    if let Value::Object(object) = record {
+        for (key, value) in object {
+            flatten_value(key.clone(), &decode_json_text(value), &mut fields);
+        }
+    }
+
+    fields
+}
+
+/// Field-level diff of local and remote copies against their common base
+///
+/// Only paths where local and remote disagree are returned; paths under
+/// any of the `ignored` fields are skipped.
+pub fn diff_records(base: &Value, local: &Value, remote: &Value, ignored: &[&str]) -> Vec<FieldDiff> {
+    let base_fields = flatten_record(base);
+    let local_fields = flatten_record(local);
+    let remote_fields = flatten_record(remote);
+
+    let mut paths: Vec<&String> = local_fields.keys().chain(remote_fields.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    let mut diffs = Vec::new();
+
+    for path in paths.into_iter().filter(|path| !is_ignored(path, ignored)) {
+        let base_value = base_fields.get(path).cloned().unwrap_or(Value::Null);
+        let local_value = local_fields.get(path).cloned().unwrap_or(Value::Null);
+        let remote_value = remote_fields.get(path).cloned().unwrap_or(Value::Null);
+
+        if local_value == remote_value {
+            continue;
+        }
+
+        let change = if remote_value == base_value {
+            FieldChange::LocalOnly
+        } else if local_value == base_value {
+            FieldChange::RemoteOnly
+        } else {
+            FieldChange::Conflict
+        };
+
+        diffs.push(FieldDiff {
+            path: path.clone(),
+            base_value,
+            local_value,
+            remote_value,
+            change,
+        });
+    }
+
+    diffs
+}
+
+/// Field-level diff of a pending record against its last synced version
+///
+/// Paths under any of the `ignored` fields are skipped. A `Null` pending
+/// record (a delete) clears every synced field.
+pub fn diff_pending(synced: &Value, pending: &Value, ignored: &[&str]) -> Vec<PendingFieldChange> {
+    let synced_fields = flatten_record(synced);
+    let pending_fields = flatten_record(pending);
+
+    let mut paths: Vec<&String> = synced_fields.keys().chain(pending_fields.keys()).collect();
+    paths.sort();
+    paths.dedup();
+
+    paths
+        .into_iter()
+        .filter(|path| !is_ignored(path, ignored))
+        .filter_map(|path| {
+            let synced_value = synced_fields.get(path).cloned().unwrap_or(Value::Null);
+            let pending_value = pending_fields.get(path).cloned().unwrap_or(Value::Null);
+
+            if synced_value == pending_value {
+                return None;
+            }
+
+            Some(PendingFieldChange {
+                summary: format!("{}: {} → {}", path, display_value(&synced_value), display_value(&pending_value)),
+                path: path.clone(),
+                synced_value,
+                pending_value,
+            })
+        })
+        .collect()
+}
+
+/// Set a value at a dotted path, re-encoding JSON text columns as text
+pub fn set_path(record: &mut Map<String, Value>, path: &str, value: Value) {
+    let mut parts = path.splitn(2, '.');
+    let field = parts.next().unwrap_or_default().to_string();
+
+    match parts.next() {
+        None => {
+            // Decoded arrays/objects go back into their TEXT column as JSON
+            let stored_as_text = matches!(record.get(&field), Some(Value::String(_)));
+            let value = match value {
+                Value::Array(_) | Value::Object(_) if stored_as_text => Value::String(value.to_string()),
+                other => other,
+            };
+            record.insert(field, value);
+        }
+        Some(rest) => {
+            let current = record.get(&field).cloned().unwrap_or(Value::Null);
+            // Local JSON columns are TEXT, so only keep native objects as-is
+            let keep_native = current.is_object();
+            let mut nested = match decode_json_text(&current) {
+                Value::Object(object) => Value::Object(object),
+                _ => Value::Object(Map::new()),
+            };
+
+            set_nested(&mut nested, rest, value);
+
+            let encoded = if keep_native {
+                nested
+            } else {
+                Value::String(nested.to_string())
+            };
+            record.insert(field, encoded);
+        }
+    }
+}
+
+// ====================================================================
+// PRIVATE HELPERS
+// ====================================================================
+
+/// Whether a dotted path lies under one of the `ignored` top-level fields
+fn is_ignored(path: &str, ignored: &[&str]) -> bool {
+    ignored.contains(&path.split('.').next().unwrap_or_default())
+}
+
+fn flatten_value(path: String, value: &Value, fields: &mut BTreeMap<String, Value>) {
+    match value {
+        Value::Object(object) if !object.is_empty() => {
+            for (key, nested) in object {
+                flatten_value(format!("{}.{}", path, key), nested, fields);
+            }
+        }
+        _ => {
+            fields.insert(path, value.clone());
+        }
+    }
+}
+
+/// Decode text that holds a JSON object or array; other values pass through
+fn decode_json_text(value: &Value) -> Value {
+    if let Value::String(text) = value {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            if let Ok(decoded) = serde_json::from_str::<Value>(text) {
+                return decoded;
+            }
+        }
+    }
+
+    value.clone()
+}
+
+/// Short form of a value for diff summaries
+fn display_value(value: &Value) -> String {
+    const MAX_CHARS: usize = 60;
+
+    let text = match value {
+        Value::Null => return "(empty)".to_string(),
+        Value::String(text) => format!("\"{}\"", text),
+        other => other.to_string(),
+    };
+
+    if text.chars().count() > MAX_CHARS {
+        format!("{}…", text.chars().take(MAX_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
+
+fn set_nested(target: &mut Value, path: &str, value: Value) {
+    let mut parts = path.splitn(2, '.');
+    let key = parts.next().unwrap_or_default().to_string();
+
+    if !target.is_object() {
+        *target = Value::Object(Map::new());
+    }
+
+    if let Value::Object(object) = target {
+        match parts.next() {
+            None => {
+                object.insert(key, value);
+            }
+            Some(rest) => {
+                let child = object.entry(key).or_insert_with(|| Value::Object(Map::new()));
+                set_nested(child, rest, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn json_text_columns_are_diffed_by_nested_path() {
+        let base = json!({ "name": "Acme", "custom_fields": r#"{"region":"EU","tier":1}"# });
+        let local = json!({ "name": "Acme", "custom_fields": r#"{"region":"US","tier":1}"# });
+        let remote = json!({ "name": "Acme Inc", "custom_fields": r#"{"region":"APAC","tier":1}"# });
+
+        let diffs = diff_records(&base, &local, &remote, &[]);
+
+        let changes: Vec<(&str, &FieldChange)> = diffs.iter().map(|diff| (diff.path.as_str(), &diff.change)).collect();
+        assert_eq!(
+            changes,
+            [("custom_fields.region", &FieldChange::Conflict), ("name", &FieldChange::RemoteOnly)]
+        );
+    }
+
+    #[test]
+    fn nested_values_are_written_back_as_json_text() {
+        let mut record = json!({ "custom_fields": r#"{"region":"EU","tier":1}"# })
+            .as_object()
+            .cloned()
+            .unwrap();
+
+        set_path(&mut record, "custom_fields.region", json!("US"));
+
+        let stored = record["custom_fields"].as_str().unwrap();
+        assert_eq!(serde_json::from_str::<Value>(stored).unwrap(), json!({ "region": "US", "tier": 1 }));
+    }
+}
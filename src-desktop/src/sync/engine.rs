@@ -7,80 +7,287 @@
 // ====================================================================
 
 use super::*;
-use crate::database::models::*;
-use sqlx::{SqlitePool, PgPool};
+use super::bundle::{self, BundledChange};
+use super::conflict_resolver::{ConflictCheck, ConflictDiff};
+use super::diff::{diff_pending, FieldChoice};
+use super::capture::{begin_sync_write, commit_sync_write};
+use super::clock;
+use super::tombstones;
+use super::mapping::{LocalRecord, RecordMapper, VersionUpdate, SYNC_METADATA_COLUMNS};
+use serde_json::Value;
+use sqlx::{SqliteConnection, SqlitePool, PgPool};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use reqwest::Client;
-use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+
+/// Serializes syncs and carries cancellation requests
+///
+/// Shared by every engine built by `SharedSyncEngine`, so rebuilding the
+/// engine after a settings change never lets two syncs overlap.
+pub struct SyncControl {
+    lock: Mutex<()>,
+    cancel_requested: AtomicBool,
+}
+
+impl SyncControl {
+    pub fn new() -> Self {
+        Self {
+            lock: Mutex::new(()),
+            cancel_requested: AtomicBool::new(false),
+        }
+    }
+
+    /// Claim the sync lock, failing if another sync holds it
+    fn begin(&self) -> Result<MutexGuard<'_, ()>, SyncError> {
+        let guard = self.lock.try_lock().map_err(|_| SyncError::AlreadyRunning)?;
+        self.cancel_requested.store(false, Ordering::SeqCst);
+        Ok(guard)
+    }
+
+    /// Wait for the sync lock, for writes that must not interleave with a sync
+    async fn wait(&self) -> MutexGuard<'_, ()> {
+        self.lock.lock().await
+    }
+
+    fn check_cancelled(&self) -> Result<(), SyncError> {
+        if self.cancel_requested.load(Ordering::SeqCst) {
+            return Err(SyncError::Cancelled);
+        }
+        Ok(())
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.lock.try_lock().is_err()
+    }
+
+    /// Request cancellation; returns false when no sync is running
+    pub fn request_cancel(&self) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+        self.cancel_requested.store(true, Ordering::SeqCst);
+        true
+    }
+}
+
+impl Default for SyncControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Outcome of applying a single pulled record
+enum PullOutcome {
+    /// Holds the fields the local table had no column for
+    Applied(Vec<String>),
+    /// Both sides changed different fields; holds the merged record and
+    /// the fields the local table had no column for
+    Merged(String, Vec<String>),
+    ConflictResolved,
+    ConflictHeld,
+    /// An older write to a record this device holds a tombstone for
+    Superseded,
+    /// A record referencing a parent row the replication scope leaves out
+    OutOfScope,
+}
+
+impl PullOutcome {
+    /// Count an applied change into a pull's result
+    fn tally(self, result: &mut SyncResult, table_name: &str, change: &SyncRecord) {
+        match self {
+            PullOutcome::Applied(dropped_fields) | PullOutcome::Merged(_, dropped_fields) => {
+                result.add_dropped_fields(table_name, &dropped_fields);
+                result.records_processed += 1;
+                match change.operation {
+                    SyncOperation::Insert => result.records_created += 1,
+                    SyncOperation::Update => result.records_updated += 1,
+                    SyncOperation::Delete => result.records_deleted += 1,
+                }
+            }
+            PullOutcome::ConflictResolved => {
+                result.records_processed += 1;
+                result.conflicts_found += 1;
+            }
+            PullOutcome::ConflictHeld => {
+                result.conflicts_found += 1;
+            }
+            PullOutcome::Superseded | PullOutcome::OutOfScope => {}
+        }
+    }
+}
 
 pub struct SyncEngine {
     sqlite_pool: SqlitePool,
-    postgres_pool: Option<PgPool>,
-    http_client: Client,
+    transport: Arc<dyn SyncTransport>,
     config: SyncConfig,
     status_manager: Arc<SyncStatusManager>,
     queue_manager: Arc<SyncQueue>,
     conflict_resolver: Arc<ConflictResolver>,
+    table_registry: Arc<SyncTableRegistry>,
+    record_mapper: Arc<RecordMapper>,
+    control: Arc<SyncControl>,
+    events: Arc<SyncEventEmitter>,
+    transfer_stats: Arc<TransferStats>,
+    history: Arc<SyncHistoryStore>,
+    connectivity: Arc<ConnectivityState>,
 }
 
 impl SyncEngine {
+    /// Create an engine that syncs over the HTTP API
+    ///
+    /// Admin builds with the `direct-postgres-sync` feature talk to the
+    /// Postgres pool directly when one is available.
     pub fn new(
         sqlite_pool: SqlitePool,
         postgres_pool: Option<PgPool>,
         config: SyncConfig,
     ) -> Self {
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(config.timeout_seconds as u64))
-            .build()
-            .expect("Failed to create HTTP client");
+        let transfer_stats = Arc::new(TransferStats::default());
+        let transport = default_transport(postgres_pool, &config, transfer_stats.clone());
 
         let status_manager = Arc::new(SyncStatusManager::new(sqlite_pool.clone()));
-        let queue_manager = Arc::new(SyncQueue::new(sqlite_pool.clone()));
+        let retry_policy = RetryPolicy {
+            max_attempts: config.max_retry_attempts.max(1),
+            ..RetryPolicy::default()
+        };
+        let queue_manager = Arc::new(SyncQueue::with_retry_policy(sqlite_pool.clone(), retry_policy));
         let conflict_resolver = Arc::new(ConflictResolver::new(sqlite_pool.clone()));
+        let history = Arc::new(SyncHistoryStore::new(sqlite_pool.clone()));
 
         Self {
             sqlite_pool,
-            postgres_pool,
-            http_client,
+            transport,
             config,
             status_manager,
             queue_manager,
             conflict_resolver,
+            table_registry: Arc::new(SyncTableRegistry::new()),
+            record_mapper: Arc::new(RecordMapper::new()),
+            control: Arc::new(SyncControl::new()),
+            events: Arc::new(SyncEventEmitter::default()),
+            transfer_stats,
+            history,
+            connectivity: Arc::new(ConnectivityState::new()),
         }
     }
 
+    /// Sync through a different transport, e.g. the in-memory fake server
+    pub fn with_transport(mut self, transport: Arc<dyn SyncTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Share the sync lock and cancellation flag with other engine instances
+    pub fn with_control(mut self, control: Arc<SyncControl>) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Report progress through the given emitter instead of a silent one
+    pub fn with_events(mut self, events: Arc<SyncEventEmitter>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Read connectivity from the shared monitor state instead of probing on every status request
+    pub fn with_connectivity(mut self, connectivity: Arc<ConnectivityState>) -> Self {
+        self.connectivity = connectivity;
+        self
+    }
+
     /// Main sync method - orchestrates full workspace sync
     pub async fn sync_workspace(&self, workspace_id: &str) -> Result<SyncReport, SyncError> {
+        self.sync_workspace_triggered_by(workspace_id, SyncTrigger::Manual).await
+    }
+
+    /// Full workspace sync, recorded in the run history under the given trigger
+    pub async fn sync_workspace_triggered_by(
+        &self,
+        workspace_id: &str,
+        trigger: SyncTrigger,
+    ) -> Result<SyncReport, SyncError> {
+        let _running = self.control.begin()?;
+        self.transfer_stats.reset();
+        let started_at = chrono::Utc::now();
+
+        let outcome = self.run_workspace_sync(workspace_id).await;
+        match &outcome {
+            Ok(report) => self.events.sync_completed(report),
+            Err(e) => self.events.sync_failed(e),
+        }
+
+        // A clean sync has pulled every change made before it started
+        if matches!(&outcome, Ok(report) if report.success) {
+            self.purge_acknowledged_tombstones(workspace_id, started_at).await;
+        }
+
+        let mut run = SyncRun::finished(trigger, workspace_id, None, started_at);
+        match &outcome {
+            Ok(report) => run.apply_report(report),
+            Err(e) => run.apply_error(e),
+        }
+        self.record_run(run).await;
+
+        outcome
+    }
+
+    /// Sync every registered table while the caller holds the sync lock
+    async fn run_workspace_sync(&self, workspace_id: &str) -> Result<SyncReport, SyncError> {
         let start_time = std::time::Instant::now();
         let mut report = SyncReport::new();
 
         println!("🔄 [SYNC] Starting workspace sync for: {}", workspace_id);
 
-        // Check if we're online
-        let is_online = SyncUtils::is_online(&self.config.remote_api_base).await;
+        // We hold the sync lock, so IN_PROGRESS rows were left by an interrupted run
+        self.queue_manager.release_in_progress_changes().await?;
+
+        // Check if we're online. Local edits stay queued and the connectivity
+        // monitor flushes them once the connection returns.
+        let is_online = self.transport.health_check().await;
         if !is_online {
-            return Err(SyncError::Network("No internet connection".to_string()));
+            let unsynced = self.count_unsynced_changes().await?;
+            return Err(SyncError::Network(format!(
+                "No internet connection; {} local changes will sync when the connection returns",
+                unsynced
+            )));
         }
 
-        // Get list of tables to sync
-        let tables = self.get_syncable_tables().await?;
+        // Parents sync before the tables that reference them
+        let tables: Vec<String> = self
+            .table_registry
+            .sync_order()?
+            .iter()
+            .map(|table| table.name.to_string())
+            .collect();
         report.tables_synced = tables.clone();
+        self.events.sync_started(workspace_id, tables.len() as i32);
 
         // Sync each table
         for table_name in tables {
-            match self.sync_table(&table_name, workspace_id).await {
+            if let Err(e) = self.control.check_cancelled() {
+                println!("⏹️ [SYNC] Workspace sync cancelled before table: {}", table_name);
+                return Err(e);
+            }
+
+            match self.run_table_sync(&table_name, workspace_id).await {
                 Ok(result) => {
                     report.records_processed += result.records_processed;
                     report.records_created += result.records_created;
                     report.records_updated += result.records_updated;
                     report.records_deleted += result.records_deleted;
+                    report.dropped_fields.extend(result.dropped_fields);
                     
                     if !result.errors.is_empty() {
                         report.errors.extend(result.errors);
                     }
                 }
+                Err(SyncError::Cancelled) => {
+                    println!("⏹️ [SYNC] Workspace sync cancelled during table: {}", table_name);
+                    return Err(SyncError::Cancelled);
+                }
                 Err(e) => {
                     report.add_error(format!("Failed to sync table {}: {}", table_name, e));
                 }
@@ -105,13 +312,94 @@ impl SyncEngine {
 
     /// Sync a specific table
     pub async fn sync_table(&self, table_name: &str, workspace_id: &str) -> Result<SyncResult, SyncError> {
+        let _running = self.control.begin()?;
+        self.transfer_stats.reset();
+        let started_at = chrono::Utc::now();
+        self.queue_manager.release_in_progress_changes().await?;
+
+        let outcome = self.run_table_sync(table_name, workspace_id).await;
+        match &outcome {
+            Ok(_) => self.events.reset(),
+            Err(e) => self.events.sync_failed(e),
+        }
+
+        let mut run = SyncRun::finished(SyncTrigger::Manual, workspace_id, Some(table_name), started_at);
+        match &outcome {
+            Ok(result) => run.apply_result(result),
+            Err(e) => run.apply_error(e),
+        }
+        self.record_run(run).await;
+
+        outcome
+    }
+
+    /// Add the run to the history; a failure here never fails the sync itself
+    async fn record_run(&self, mut run: SyncRun) {
+        self.transfer_stats.apply_to_run(&mut run);
+
+        if let Err(e) = self.history.record_run(&run).await {
+            println!("⚠️ [SYNC] Failed to record sync run in history: {}", e);
+        }
+    }
+
+    /// Purge tombstones every device has pulled past; a failure here never fails the sync itself
+    async fn purge_acknowledged_tombstones(&self, workspace_id: &str, pulled_through: chrono::DateTime<chrono::Utc>) {
+        match self.try_purge_tombstones(workspace_id, pulled_through).await {
+            Ok(0) => {}
+            Ok(purged) => println!("🪦 [SYNC] Purged {} acknowledged tombstones", purged),
+            Err(e) => println!("⚠️ [SYNC] Failed to purge tombstones: {}", e),
+        }
+    }
+
+    async fn try_purge_tombstones(
+        &self,
+        workspace_id: &str,
+        pulled_through: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64, SyncError> {
+        let device_id = {
+            let mut conn = self.sqlite_pool.acquire().await?;
+            clock::device_id(&mut conn).await?
+        };
+
+        let horizon = match self
+            .transport
+            .acknowledge_pulls(workspace_id, &device_id, &pulled_through.to_rfc3339())
+            .await?
+        {
+            Some(horizon) => horizon,
+            None => return Ok(0),
+        };
+
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+        let purged = tombstones::purge_tombstones(&mut tx, &self.table_registry, &horizon).await?;
+        commit_sync_write(tx).await?;
+
+        Ok(purged)
+    }
+
+    /// Sync a table while the caller holds the sync lock
+    async fn run_table_sync(&self, table_name: &str, workspace_id: &str) -> Result<SyncResult, SyncError> {
+        let table = self.table_registry.require(table_name)?;
         let start_time = std::time::Instant::now();
         let mut result = SyncResult::new();
 
+        let scope = SyncSettingsStore::new(self.sqlite_pool.clone())
+            .load_replication_scope(workspace_id)
+            .await?;
+        scope.validate(&self.table_registry)?;
+        let table_scope = scope.table(table_name);
+
         println!("🔄 [SYNC] Syncing table: {}", table_name);
+        self.events.table_started(table_name);
 
         // First, push local changes
-        match self.push_table_changes(table_name, workspace_id).await {
+        let push = if table.direction.pushes() {
+            self.push_table_changes(table, workspace_id).await
+        } else {
+            Ok(SyncResult::new())
+        };
+
+        match push {
             Ok(push_result) => {
                 result.records_processed += push_result.records_processed;
                 result.records_created += push_result.records_created;
@@ -119,40 +407,65 @@ impl SyncEngine {
                 result.records_deleted += push_result.records_deleted;
                 result.errors.extend(push_result.errors);
             }
+            Err(SyncError::Cancelled) => return Err(SyncError::Cancelled),
             Err(e) => {
                 result.add_error(format!("Failed to push changes for {}: {}", table_name, e));
             }
         }
 
-        // Then, pull remote changes
-        match self.pull_table_changes(table_name, workspace_id).await {
+        // Then, pull remote changes inside the replication scope
+        let pull = if table.direction.pulls() && !table_scope.is_some_and(|scope| scope.skip_pull) {
+            self.pull_table_changes(table, workspace_id, &scope).await
+        } else {
+            Ok(SyncResult::new())
+        };
+
+        match pull {
             Ok(pull_result) => {
                 result.records_processed += pull_result.records_processed;
                 result.records_created += pull_result.records_created;
                 result.records_updated += pull_result.records_updated;
                 result.records_deleted += pull_result.records_deleted;
+                result.conflicts_found += pull_result.conflicts_found;
                 result.errors.extend(pull_result.errors);
+                result.dropped_fields.extend(pull_result.dropped_fields);
             }
+            Err(SyncError::Cancelled) => return Err(SyncError::Cancelled),
             Err(e) => {
                 result.add_error(format!("Failed to pull changes for {}: {}", table_name, e));
             }
         }
 
+        // Finally, drop local rows the scope no longer covers
+        if table.direction.pulls() && scope.narrows(&self.table_registry, table_name) {
+            if let Err(e) = self.evict_unscoped_rows(table, &scope).await {
+                result.add_error(format!("Failed to evict out-of-scope {} records: {}", table_name, e));
+            }
+        }
+
         result.success = result.errors.is_empty();
         result.duration_ms = start_time.elapsed().as_millis() as i64;
 
         // Update sync status for this table
         self.status_manager.update_table_sync_status(table_name, &result).await?;
+        self.events.table_finished(table_name, &result);
 
         Ok(result)
     }
 
     /// Push local changes to remote server
-    async fn push_table_changes(&self, table_name: &str, workspace_id: &str) -> Result<SyncResult, SyncError> {
+    async fn push_table_changes(&self, table: &SyncTable, workspace_id: &str) -> Result<SyncResult, SyncError> {
+        let table_name = table.name;
         let mut result = SyncResult::new();
 
-        // Get pending changes from sync queue
-        let pending_changes = self.queue_manager.get_pending_changes(table_name).await?;
+        // Pick up local writes logged by the capture triggers
+        self.queue_manager.collect_captured_changes().await?;
+
+        // Get pending changes, plus failed ones whose backoff has elapsed
+        let pending_changes = self
+            .queue_manager
+            .get_changes_to_push(table_name, self.config.enable_auto_retry)
+            .await?;
 
         if pending_changes.is_empty() {
             return Ok(result);
@@ -160,18 +473,29 @@ impl SyncEngine {
 
         println!("📤 [SYNC] Pushing {} changes for table: {}", pending_changes.len(), table_name);
 
+        let batch_size = (self.config.batch_size as usize).max(1);
+        self.events.push_planned(pending_changes.len().div_ceil(batch_size) as i32);
+
         // Process changes in batches
-        for batch in pending_changes.chunks(self.config.batch_size as usize) {
-            match self.send_batch_to_server(table_name, workspace_id, batch).await {
-                Ok(batch_result) => {
-                    result.records_processed += batch_result.records_processed;
-                    result.records_created += batch_result.records_created;
-                    result.records_updated += batch_result.records_updated;
-                    result.records_deleted += batch_result.records_deleted;
-
-                    // Mark changes as synced
+        for batch in pending_changes.chunks(batch_size) {
+            self.control.check_cancelled()?;
+
+            // In-flight changes are no longer coalesced with new local edits
+            for change in batch {
+                self.queue_manager.mark_as_in_progress(change.id).await?;
+            }
+
+            let batch = &self.with_current_row_payloads(table, batch).await?;
+
+            match self.transport.push_batch(table_name, workspace_id, batch).await {
+                Ok(outcomes) => {
+                    self.apply_push_results(table, batch, outcomes, &mut result).await?;
+                }
+                Err(e) if batch.len() > 1 => {
+                    // Retry one by one so a single bad record doesn't fail the whole batch
+                    println!("⚠️ [SYNC] Batch failed for {}, retrying changes individually: {}", table_name, e);
                     for change in batch {
-                        self.queue_manager.mark_as_synced(change.id).await?;
+                        self.push_single_change(table, workspace_id, change, &mut result).await?;
                     }
                 }
                 Err(e) => {
@@ -183,238 +507,720 @@ impl SyncEngine {
                     }
                 }
             }
+
+            self.events.batch_pushed(table_name, batch.len() as i32);
         }
 
         result.success = result.errors.is_empty();
         Ok(result)
     }
 
-    /// Pull remote changes from server
-    async fn pull_table_changes(&self, table_name: &str, workspace_id: &str) -> Result<SyncResult, SyncError> {
-        let mut result = SyncResult::new();
+    /// Build push payloads from the typed rows as they are now
+    ///
+    /// Inserts and updates send the full current row, so the server gets
+    /// typed column values and the fields kept in `custom_fields`. Rows
+    /// deleted since the change was queued keep the queued payload.
+    async fn with_current_row_payloads(
+        &self,
+        table: &SyncTable,
+        changes: &[SyncQueueItem],
+    ) -> Result<Vec<SyncQueueItem>, SyncError> {
+        let mut conn = self.sqlite_pool.acquire().await?;
+        let mut prepared = Vec::with_capacity(changes.len());
+
+        for change in changes {
+            let mut change = change.clone();
+
+            if change.operation != SyncOperation::Delete {
+                if let Some(mut local) = self.load_local_record(&mut conn, table, &change.record_id).await? {
+                    // The clock travels with the record so other devices can order the write
+                    if let (Some(stamp), Value::Object(fields)) =
+                        (clock::record_clock(&mut conn, table.name, &change.record_id).await?, &mut local.data)
+                    {
+                        fields.insert(CLOCK_FIELD.to_string(), Value::String(stamp.to_string()));
+                    }
+                    change.data = Some(serde_json::to_string(&local.data)?);
+                }
+            }
 
-        // Get last sync timestamp for this table
-        let last_sync = self.status_manager.get_last_sync_timestamp(table_name).await?;
+            prepared.push(change);
+        }
 
-        // Fetch changes from server
-        let remote_changes = self.fetch_changes_from_server(table_name, workspace_id, last_sync).await?;
+        Ok(prepared)
+    }
 
-        if remote_changes.is_empty() {
-            return Ok(result);
+    /// Push a single queued change, scheduling a retry if it fails
+    async fn push_single_change(
+        &self,
+        table: &SyncTable,
+        workspace_id: &str,
+        change: &SyncQueueItem,
+        result: &mut SyncResult,
+    ) -> Result<(), SyncError> {
+        let changes = std::slice::from_ref(change);
+
+        match self.transport.push_batch(table.name, workspace_id, changes).await {
+            Ok(outcomes) => {
+                self.apply_push_results(table, changes, outcomes, result).await?;
+            }
+            Err(e) => {
+                result.add_error(format!("Failed to send change for record {}: {}", change.record_id, e));
+                self.queue_manager.mark_as_failed(change.id, &e.to_string()).await?;
+            }
         }
 
-        println!("📥 [SYNC] Pulling {} changes for table: {}", remote_changes.len(), table_name);
+        Ok(())
+    }
+
+    /// Settle each pushed change according to the server's per-record verdict
+    ///
+    /// Accepted changes are done, rejected ones are scheduled for retry, and
+    /// conflicts are handed to the `ConflictResolver` with the remote version.
+    async fn apply_push_results(
+        &self,
+        table: &SyncTable,
+        changes: &[SyncQueueItem],
+        outcomes: Vec<PushRecordResult>,
+        result: &mut SyncResult,
+    ) -> Result<(), SyncError> {
+        let mut outcomes: HashMap<String, PushRecordResult> = outcomes
+            .into_iter()
+            .map(|outcome| (outcome.client_mutation_id.clone(), outcome))
+            .collect();
+
+        for change in changes {
+            let outcome = outcomes.remove(&change.client_mutation_id);
 
-        // Apply changes to local database
-        for change in remote_changes {
-            match self.apply_remote_change(table_name, &change).await {
-                Ok(()) => {
+            let rejection = match outcome {
+                Some(PushRecordResult { status: PushStatus::Accepted, .. }) => {
                     result.records_processed += 1;
                     match change.operation {
                         SyncOperation::Insert => result.records_created += 1,
                         SyncOperation::Update => result.records_updated += 1,
                         SyncOperation::Delete => result.records_deleted += 1,
                     }
+
+                    self.queue_manager.mark_as_synced(change.id).await?;
+                    self.refresh_base_version(table, &change.record_id).await?;
+                    self.clear_dirty_flag(table, &change.record_id).await?;
+                    self.acknowledge_delete(table, &change.record_id).await?;
+                    continue;
                 }
-                Err(e) => {
-                    result.add_error(format!("Failed to apply change {}: {}", change.id, e));
+                Some(PushRecordResult { status: PushStatus::Conflict, remote: Some(remote), .. }) => {
+                    result.conflicts_found += 1;
+                    self.handle_push_conflict(table, change, &remote).await?;
+                    continue;
+                }
+                Some(PushRecordResult { status: PushStatus::Conflict, remote: None, .. }) => {
+                    "Server reported a conflict without the remote version".to_string()
+                }
+                Some(PushRecordResult { status: PushStatus::Rejected, reason, .. }) => {
+                    reason.unwrap_or_else(|| "Rejected by server".to_string())
+                }
+                None => "Server returned no result for this change".to_string(),
+            };
+
+            result.add_error(format!("Server rejected change for record {}: {}", change.record_id, rejection));
+            self.queue_manager.mark_as_failed(change.id, &rejection).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Hand a pushed change the server refused as conflicting to the resolver
+    ///
+    /// The remote version goes through the same detection as a pulled record.
+    /// Whatever the outcome, the queued change is done: the conflict (or the
+    /// requeued merge/resolution) now carries the local edits.
+    async fn handle_push_conflict(
+        &self,
+        table: &SyncTable,
+        change: &SyncQueueItem,
+        remote: &SyncRecord,
+    ) -> Result<(), SyncError> {
+        println!("⚠️ [SYNC] Push of {}/{} conflicts with the server version", table.name, change.record_id);
+
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+        let remote = &*self.observe_remote_clock(&mut tx, remote).await?;
+
+        match self.load_local_record(&mut tx, table, &change.record_id).await? {
+            Some(local) => {
+                if let PullOutcome::Merged(merged, _) =
+                    self.detect_or_resolve_conflict(&mut tx, table, local, remote).await?
+                {
+                    // The merge still holds local edits the server hasn't seen
+                    SyncQueue::enqueue_change_in(&mut tx, table.name, &change.record_id, SyncOperation::Update, Some(merged))
+                        .await?;
                 }
             }
+            None => {
+                // Deleted locally but edited remotely: keep the remote edit
+                tombstones::clear_tombstone(&mut tx, table.name, &change.record_id).await?;
+                self.apply_remote_change(&mut tx, table, remote).await?;
+            }
         }
 
-        result.success = result.errors.is_empty();
-        Ok(result)
+        commit_sync_write(tx).await?;
+        self.queue_manager.mark_as_synced(change.id).await?;
+
+        Ok(())
     }
 
-    /// Send a batch of changes to the server
-    async fn send_batch_to_server(
+    /// Pull remote changes from server
+    ///
+    /// Pages through the server's change feed. Each page is applied in one
+    /// transaction that also advances the watermark, so an interrupted pull
+    /// resumes from the last committed page. A change that fails to apply is
+    /// set aside in `sync_pull_failures` and retried on the next pull, so one
+    /// bad record doesn't hold the table's feed back. Records below a parent
+    /// row the replication scope leaves out are passed over.
+    async fn pull_table_changes(
         &self,
-        table_name: &str,
+        table: &SyncTable,
         workspace_id: &str,
-        changes: &[SyncQueueItem],
+        scope: &ReplicationScope,
     ) -> Result<SyncResult, SyncError> {
+        let table_name = table.name;
         let mut result = SyncResult::new();
+        let filter = {
+            let mut conn = self.sqlite_pool.acquire().await?;
+            ScopeFilter::load(&mut conn, scope, &self.table_registry).await?
+        };
+
+        let mut watermark = match self.status_manager.get_watermark(table_name).await? {
+            Some(watermark) => watermark,
+            None => SyncWatermark {
+                table_name: table_name.to_string(),
+                last_sync_timestamp: String::new(),
+                last_sync_version: 0,
+                record_count: 0,
+                cursor: None,
+            },
+        };
+
+        // Without a cursor, pull what changed since the last completed pull. Tables
+        // synced before watermarks existed start from their last sync time.
+        let since = if watermark.cursor.is_some() {
+            None
+        } else if !watermark.last_sync_timestamp.is_empty() {
+            Some(watermark.last_sync_timestamp.clone())
+        } else {
+            self.status_manager.get_last_sync_timestamp(table_name).await?
+        };
+
+        // Every page stamps the time the pull started, so changes made while
+        // later pages were fetched are still in the next `since` pull
+        let requested_at = chrono::Utc::now().to_rfc3339();
+
+        // Changes set aside by earlier pulls go first; their parents may have arrived since
+        self.retry_pull_failures(table, &filter, &mut result).await?;
+
+        loop {
+            self.control.check_cancelled()?;
+
+            let page = self
+                .transport
+                .pull_page(
+                    table_name,
+                    workspace_id,
+                    watermark.cursor.as_deref(),
+                    since.as_deref(),
+                    scope.table(table_name),
+                    self.config.batch_size,
+                )
+                .await?;
+
+            for malformed in &page.malformed {
+                result.add_error(format!("Malformed {} record from server: {}", table_name, malformed));
+            }
 
-        // Convert changes to API format
-        let batch_data = self.convert_changes_to_api_format(changes)?;
+            if !page.records.is_empty() {
+                println!("📥 [SYNC] Pulling {} changes for table: {}", page.records.len(), table_name);
+            }
 
-        // Send to server
-        let url = format!("{}/sync/{}/{}", self.config.remote_api_base, workspace_id, table_name);
-        let response = self.http_client
-            .post(&url)
-            .json(&batch_data)
-            .send()
-            .await
-            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+            // The whole page and the watermark commit together, or not at all.
+            // Pulled writes are not local edits, so capture is suppressed.
+            let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+            let mut page_result = SyncResult::new();
+
+            // Apply changes to local database
+            for change in &page.records {
+                match self.apply_pulled_change(&mut tx, table, &filter, change).await? {
+                    Ok(outcome) => outcome.tally(&mut page_result, table_name, change),
+                    Err(e) => {
+                        result.add_error(format!("Failed to apply change {}, set aside for retry: {}", change.id, e));
+                    }
+                }
+            }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(SyncError::Network(format!("Server error: {}", error_text)));
-        }
+            let cursor_advanced = page.next_cursor.is_some() && page.next_cursor != watermark.cursor;
+
+            let mut next_watermark = watermark.clone();
+            next_watermark.last_sync_timestamp = requested_at.clone();
+            next_watermark.last_sync_version = page
+                .records
+                .iter()
+                .map(|r| r.sync_version)
+                .fold(watermark.last_sync_version, i32::max);
+            next_watermark.record_count += page.records.len() as i32;
+            if page.next_cursor.is_some() {
+                next_watermark.cursor = page.next_cursor.clone();
+            } else if !page.has_more {
+                // The server gave no position past the end of the feed; keeping the
+                // old cursor would pull this last page again on every sync
+                next_watermark.cursor = None;
+            }
 
-        // Parse response
-        let response_data: Value = response.json().await
-            .map_err(|e| SyncError::Serialization(e))?;
+            self.status_manager.save_watermark(&mut tx, &next_watermark).await?;
+            commit_sync_write(tx).await?;
+            self.events.page_pulled(table_name, page.records.len() as i32);
+
+            watermark = next_watermark;
+            result.records_processed += page_result.records_processed;
+            result.records_created += page_result.records_created;
+            result.records_updated += page_result.records_updated;
+            result.records_deleted += page_result.records_deleted;
+            result.conflicts_found += page_result.conflicts_found;
+            for field in page_result.dropped_fields {
+                if !result.dropped_fields.contains(&field) {
+                    result.dropped_fields.push(field);
+                }
+            }
+
+            if !page.has_more {
+                break;
+            }
 
-        // Update result based on response
-        if let Some(processed) = response_data["records_processed"].as_i64() {
-            result.records_processed = processed as i32;
+            if !cursor_advanced {
+                result.add_error(format!("Server reported more {} changes without advancing the cursor", table_name));
+                break;
+            }
         }
 
-        result.success = true;
+        result.success = result.errors.is_empty();
         Ok(result)
     }
 
-    /// Fetch changes from server
-    async fn fetch_changes_from_server(
+    /// Apply a pulled change in its own savepoint inside the page transaction
+    ///
+    /// A change that fails is rolled back on its own and set aside in
+    /// `sync_pull_failures`; the inner `Err` is why it failed. The outer
+    /// `Err` is for failures the page can't recover from.
+    async fn apply_pulled_change(
         &self,
-        table_name: &str,
-        workspace_id: &str,
-        since: Option<String>,
-    ) -> Result<Vec<SyncRecord>, SyncError> {
-        let mut url = format!("{}/{}/{}", self.config.remote_api_base, workspace_id, table_name);
-        
-        if let Some(timestamp) = since {
-            url.push_str(&format!("?since={}", timestamp));
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        filter: &ScopeFilter<'_>,
+        change: &SyncRecord,
+    ) -> Result<Result<PullOutcome, SyncError>, SyncError> {
+        // Rows below a parent row the scope leaves out stay on the server
+        if change.operation != SyncOperation::Delete {
+            // Unreadable data is left for the apply to fail and set aside
+            let data = serde_json::from_str::<Value>(&change.data).unwrap_or(Value::Null);
+            if !filter.parents_in_scope(&mut *conn, table, &data).await? {
+                self.status_manager.clear_pull_failure(&mut *conn, table.name, &change.id).await?;
+                return Ok(Ok(PullOutcome::OutOfScope));
+            }
         }
 
-        let response = self.http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+        let mut savepoint = sqlx::Connection::begin(&mut *conn).await?;
+
+        match self.apply_or_detect_conflict(&mut savepoint, table, change).await {
+            Ok(outcome) => {
+                savepoint.commit().await?;
+                self.status_manager.clear_pull_failure(&mut *conn, table.name, &change.id).await?;
+                Ok(Ok(outcome))
+            }
+            Err(SyncError::Cancelled) => Err(SyncError::Cancelled),
+            Err(e) => {
+                savepoint.rollback().await?;
+                self.status_manager
+                    .record_pull_failure(&mut *conn, table.name, change, &e.to_string())
+                    .await?;
+                Ok(Err(e))
+            }
+        }
+    }
 
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
-            return Err(SyncError::Network(format!("Server error: {}", error_text)));
+    /// Apply the changes earlier pulls of a table set aside
+    async fn retry_pull_failures(
+        &self,
+        table: &SyncTable,
+        filter: &ScopeFilter<'_>,
+        result: &mut SyncResult,
+    ) -> Result<(), SyncError> {
+        let failures = self.status_manager.get_pull_failures(table.name).await?;
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        println!("🔁 [SYNC] Retrying {} set-aside changes for table: {}", failures.len(), table.name);
+
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+        for failure in &failures {
+            match self.apply_pulled_change(&mut tx, table, filter, &failure.record).await? {
+                Ok(outcome) => outcome.tally(result, table.name, &failure.record),
+                Err(e) => result.add_error(format!(
+                    "Change {} still fails to apply after {} attempts: {}",
+                    failure.record.id,
+                    failure.attempts + 1,
+                    e
+                )),
+            }
         }
+        commit_sync_write(tx).await?;
 
-        let response_data: Value = response.json().await
-            .map_err(|e| SyncError::Serialization(e))?;
+        Ok(())
+    }
 
-        // Parse response into SyncRecord objects
-        let mut records = Vec::new();
-        if let Some(data_array) = response_data["data"].as_array() {
-            for item in data_array {
-                if let Ok(record) = serde_json::from_value::<SyncRecord>(item.clone()) {
-                    records.push(record);
+    /// Apply a remote change unless it would overwrite unsynced local edits
+    ///
+    /// When the local row is dirty, a `SyncConflict` is recorded and either
+    /// auto-resolved with the configured strategy or held for the user.
+    async fn apply_or_detect_conflict(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        change: &SyncRecord,
+    ) -> Result<PullOutcome, SyncError> {
+        let change = &*self.observe_remote_clock(&mut *conn, change).await?;
+
+        // A delete this device already has outranks older writes arriving late
+        if change.operation != SyncOperation::Delete {
+            if let Some(tombstone) = tombstones::find_tombstone(&mut *conn, table.name, &change.id).await? {
+                if tombstone.supersedes(change, remote_clock(change).as_ref()) {
+                    println!("🪦 [SYNC] Skipping stale write to deleted record {}/{}", table.name, change.id);
+                    return Ok(PullOutcome::Superseded);
                 }
             }
         }
 
-        Ok(records)
+        let local = match self.load_local_record(&mut *conn, table, &change.id).await? {
+            Some(local) if local.is_dirty => local,
+            _ => {
+                let dropped_fields = self.apply_remote_change(&mut *conn, table, change).await?;
+                return Ok(PullOutcome::Applied(dropped_fields));
+            }
+        };
+
+        self.detect_or_resolve_conflict(conn, table, local, change).await
     }
 
-    /// Apply a remote change to the local database
-    async fn apply_remote_change(&self, table_name: &str, change: &SyncRecord) -> Result<(), SyncError> {
-        match change.operation {
-            SyncOperation::Insert => {
-                self.insert_record(table_name, &change.id, &change.data).await
+    /// Compare a remote change with a local copy that has unsynced edits
+    async fn detect_or_resolve_conflict(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        local: LocalRecord,
+        change: &SyncRecord,
+    ) -> Result<PullOutcome, SyncError> {
+        let local_data = serde_json::to_string(&local.data)?;
+        let conflict = match change.operation {
+            SyncOperation::Delete => {
+                self.conflict_resolver
+                    .detect_delete_conflict(
+                        &mut *conn,
+                        table.name,
+                        &local_data,
+                        &change.id,
+                        change.sync_version,
+                        remote_clock(change),
+                    )
+                    .await?
             }
-            SyncOperation::Update => {
-                self.update_record(table_name, &change.id, &change.data).await
+            SyncOperation::Insert | SyncOperation::Update => {
+                match self.conflict_resolver
+                    .detect_conflicts(&mut *conn, table.name, &local_data, &change.data, &change.id)
+                    .await?
+                {
+                    ConflictCheck::Conflict(conflict) => *conflict,
+                    ConflictCheck::NoConflict => {
+                        // Both sides already hold the same content
+                        let dropped_fields = self.apply_remote_change(&mut *conn, table, change).await?;
+                        return Ok(PullOutcome::Applied(dropped_fields));
+                    }
+                    ConflictCheck::AutoMerged(merged) => {
+                        // Fields changed on one side only, keep both sets of edits.
+                        // The merge is a new local write that follows both.
+                        let dropped_fields = self.apply_merged_record(&mut *conn, table, &change.id, &merged).await?;
+                        let stamp = clock::tick(&mut *conn).await?;
+                        clock::set_record_clock(&mut *conn, table.name, &change.id, &stamp).await?;
+                        self.conflict_resolver
+                            .record_base_version(&mut *conn, table.name, &change.id, &change.data, change.sync_version)
+                            .await?;
+                        return Ok(PullOutcome::Merged(merged, dropped_fields));
+                    }
+                }
             }
-            SyncOperation::Delete => {
-                self.delete_record(table_name, &change.id).await
+        };
+
+        println!("⚠️ [SYNC] Conflict detected for {}/{}", table.name, change.id);
+        self.events.conflict_found(table.name, &change.id);
+
+        match ConflictResolver::resolution_for_strategy(&conflict, &self.config.conflict_resolution_strategy) {
+            Some(resolution) => {
+                self.conflict_resolver.resolve_loaded_conflict(conn, conflict, resolution).await?;
+                Ok(PullOutcome::ConflictResolved)
             }
+            None => Ok(PullOutcome::ConflictHeld),
         }
     }
 
-    /// Insert a new record
-    async fn insert_record(&self, table_name: &str, id: &str, data: &str) -> Result<(), SyncError> {
-        let query = format!("INSERT INTO {} (id, data, sync_version, last_synced_at, is_dirty) VALUES (?, ?, ?, ?, ?)", table_name);
-        
-        sqlx::query(&query)
-            .bind(id)
-            .bind(data)
-            .bind(1)
-            .bind(SyncUtils::current_timestamp())
-            .bind(false)
-            .execute(&self.sqlite_pool)
-            .await
-            .map_err(SyncError::Database)?;
+    /// Advance the local clock past the clock a remote change carries
+    ///
+    /// A clock too far ahead to trust is replaced in the change, so it is
+    /// applied, compared and stored with the time it was received instead.
+    async fn observe_remote_clock<'a>(
+        &self,
+        conn: &mut SqliteConnection,
+        change: &'a SyncRecord,
+    ) -> Result<Cow<'a, SyncRecord>, SyncError> {
+        let Some(stamp) = remote_clock(change) else {
+            return Ok(Cow::Borrowed(change));
+        };
+
+        let observed = clock::observe(conn, &stamp).await?;
+        if observed == stamp {
+            return Ok(Cow::Borrowed(change));
+        }
+
+        let mut data: Value = serde_json::from_str(&change.data)?;
+        if let Value::Object(fields) = &mut data {
+            fields.insert(CLOCK_FIELD.to_string(), Value::String(observed.to_string()));
+        }
+
+        Ok(Cow::Owned(SyncRecord {
+            data: data.to_string(),
+            ..change.clone()
+        }))
+    }
+
+    /// After a successful push the server holds the local copy, so it becomes the base
+    async fn refresh_base_version(&self, table: &SyncTable, id: &str) -> Result<(), SyncError> {
+        let mut conn = self.sqlite_pool.acquire().await?;
+
+        match self.load_local_record(&mut conn, table, id).await? {
+            Some(local) => {
+                let data = serde_json::to_string(&local.data)?;
+                self.conflict_resolver
+                    .record_base_version(&mut conn, table.name, id, &data, local.sync_version)
+                    .await?;
+            }
+            None => self.conflict_resolver.clear_base_version(&mut conn, table.name, id).await?,
+        }
 
         Ok(())
     }
 
-    /// Update an existing record
-    async fn update_record(&self, table_name: &str, id: &str, data: &str) -> Result<(), SyncError> {
-        let query = format!("UPDATE {} SET data = ?, sync_version = sync_version + 1, last_synced_at = ?, is_dirty = ? WHERE id = ?", table_name);
-        
-        sqlx::query(&query)
-            .bind(data)
-            .bind(SyncUtils::current_timestamp())
-            .bind(false)
-            .bind(id)
-            .execute(&self.sqlite_pool)
-            .await
-            .map_err(SyncError::Database)?;
+    /// The server has this record's delete now, if the record is deleted
+    async fn acknowledge_delete(&self, table: &SyncTable, id: &str) -> Result<(), SyncError> {
+        let mut conn = self.sqlite_pool.acquire().await?;
+        tombstones::acknowledge_tombstone(&mut conn, table.name, id).await?;
+        Ok(())
+    }
+
+    /// Delete clean local rows outside a table's replication scope
+    async fn evict_unscoped_rows(&self, table: &SyncTable, scope: &ReplicationScope) -> Result<(), SyncError> {
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+
+        let has_dirty_flag = self.record_mapper.schema(&mut tx, table.name).await?.has_column("is_dirty");
+        let children = self.table_registry.children(table.name);
+        let filter = ScopeFilter::load(&mut tx, scope, &self.table_registry).await?;
+        let evicted = evict_out_of_scope(&mut tx, table, &children, &filter, has_dirty_flag).await?;
+
+        commit_sync_write(tx).await?;
+
+        if evicted > 0 {
+            println!("🧹 [SYNC] Evicted {} out-of-scope records from {}", evicted, table.name);
+        }
 
         Ok(())
     }
 
-    /// Delete a record
-    async fn delete_record(&self, table_name: &str, id: &str) -> Result<(), SyncError> {
-        let query = format!("DELETE FROM {} WHERE id = ?", table_name);
-        
-        sqlx::query(&query)
-            .bind(id)
-            .execute(&self.sqlite_pool)
-            .await
-            .map_err(SyncError::Database)?;
+    /// Mark a pushed row clean, unless newer local edits are still waiting
+    async fn clear_dirty_flag(&self, table: &SyncTable, id: &str) -> Result<(), SyncError> {
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+
+        if self.record_mapper.schema(&mut tx, table.name).await?.has_column("is_dirty") {
+            let query = format!(
+                r#"
+                UPDATE {table} SET is_dirty = 0
+                WHERE {pk} = ?
+                  AND NOT EXISTS (
+                      SELECT 1 FROM sync_queue
+                      WHERE table_name = ? AND record_id = ? AND status IN ('PENDING', 'FAILED', 'IN_PROGRESS')
+                  )
+                  AND NOT EXISTS (
+                      SELECT 1 FROM sync_change_log WHERE table_name = ? AND record_id = ?
+                  )
+                "#,
+                table = table.name,
+                pk = table.primary_key
+            );
+
+            sqlx::query(&query)
+                .bind(id)
+                .bind(table.name)
+                .bind(id)
+                .bind(table.name)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
 
+        commit_sync_write(tx).await?;
         Ok(())
     }
 
-    /// Convert sync queue items to API format
-    fn convert_changes_to_api_format(&self, changes: &[SyncQueueItem]) -> Result<Value, SyncError> {
-        let mut batch = Vec::new();
-        
-        for change in changes {
-            let mut record = serde_json::Map::new();
-            record.insert("id".to_string(), Value::String(change.record_id.clone()));
-            record.insert("operation".to_string(), Value::String(format!("{:?}", change.operation)));
-            
-            if let Some(data) = &change.data {
-                record.insert("data".to_string(), serde_json::from_str(data)?);
-            }
-            
-            batch.push(Value::Object(record));
-        }
-        
-        Ok(Value::Array(batch))
-    }
-
-    /// Get list of tables that can be synced
-    async fn get_syncable_tables(&self) -> Result<Vec<String>, SyncError> {
-        let tables = vec![
-            "workspaces".to_string(),
-            "users".to_string(),
-            "companies".to_string(),
-            "people".to_string(),
-            "actions".to_string(),
-            "research_data".to_string(),
-            "api_cost_tracking".to_string(),
-            "ai_conversations".to_string(),
-            "ai_messages".to_string(),
-            "chronicle_reports".to_string(),
-            "buyer_groups".to_string(),
-            "buyer_group_members".to_string(),
-            "audit_logs".to_string(),
-            "email_messages".to_string(),
-        ];
-        
-        Ok(tables)
-    }
-
-    /// Resolve a sync conflict
+    /// Load the local copy of a record along with its dirty flag
+    async fn load_local_record(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        id: &str,
+    ) -> Result<Option<LocalRecord>, SyncError> {
+        let local = self.record_mapper.read_record(conn, table.name, id).await?;
+        Ok(local)
+    }
+
+    /// Apply a remote change to the local database
+    ///
+    /// Returns the fields the local table had no column for.
+    async fn apply_remote_change(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        change: &SyncRecord,
+    ) -> Result<Vec<String>, SyncError> {
+        let dropped_fields = match change.operation {
+            SyncOperation::Insert | SyncOperation::Update => {
+                let dropped_fields = self
+                    .record_mapper
+                    .write_record(&mut *conn, table.name, &change.id, &change.data, VersionUpdate::Set(change.sync_version), false)
+                    .await?;
+
+                if let Some(stamp) = remote_clock(change) {
+                    clock::set_record_clock(&mut *conn, table.name, &change.id, &stamp).await?;
+                }
+
+                // Soft deletes arrive as updates; anything else brings the record back
+                if is_soft_deleted(change) {
+                    self.delete_record(&mut *conn, table, change).await?;
+                } else {
+                    tombstones::clear_tombstone(&mut *conn, table.name, &change.id).await?;
+                }
+
+                dropped_fields
+            }
+            SyncOperation::Delete => {
+                self.delete_record(&mut *conn, table, change).await?;
+                self.conflict_resolver.clear_base_version(&mut *conn, table.name, &change.id).await?;
+                return Ok(Vec::new());
+            }
+        };
+
+        // What the server sent is the new common ancestor for future merges
+        self.conflict_resolver
+            .record_base_version(&mut *conn, table.name, &change.id, &change.data, change.sync_version)
+            .await?;
+
+        Ok(dropped_fields)
+    }
+
+    /// Write an auto-merged record, keeping it dirty so local edits still get pushed
+    async fn apply_merged_record(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        id: &str,
+        data: &str,
+    ) -> Result<Vec<String>, SyncError> {
+        let dropped_fields = self
+            .record_mapper
+            .write_record(conn, table.name, id, data, VersionUpdate::Keep, true)
+            .await?;
+
+        Ok(dropped_fields)
+    }
+
+    /// Delete a record, leaving a tombstone so older writes can't bring it back
+    ///
+    /// Tables with a `deleted_at` column keep the row soft-deleted until the
+    /// tombstone is purged.
+    async fn delete_record(&self, conn: &mut SqliteConnection, table: &SyncTable, change: &SyncRecord) -> Result<(), SyncError> {
+        let soft_delete = self
+            .record_mapper
+            .schema(&mut *conn, table.name)
+            .await?
+            .has_column(tombstones::SOFT_DELETE_COLUMN);
+
+        tombstones::apply_delete(
+            &mut *conn,
+            table.name,
+            table.primary_key,
+            &change.id,
+            soft_delete,
+            change.sync_version,
+            remote_clock(change).as_ref(),
+            Some(&change.last_modified),
+        )
+        .await
+        .map_err(SyncError::Database)?;
+
+        Ok(())
+    }
+
+    /// Resolve a sync conflict, after any running sync finishes
     pub async fn resolve_conflict(&self, conflict_id: i64, resolution: ConflictResolution) -> Result<(), SyncError> {
-        self.conflict_resolver.resolve_conflict(conflict_id, resolution).await
+        let _running = self.control.wait().await;
+        self.conflict_resolver.resolve_conflict(conflict_id, resolution).await.map_err(SyncError::Database)
+    }
+
+    /// Get conflicts still waiting for the user
+    pub async fn get_unresolved_conflicts(&self) -> Result<Vec<SyncConflict>, SyncError> {
+        Ok(self.conflict_resolver.get_unresolved_conflicts().await?)
+    }
+
+    /// Field-by-field diff of a conflict
+    pub async fn get_conflict_diff(&self, conflict_id: i64) -> Result<Option<ConflictDiff>, SyncError> {
+        self.conflict_resolver.get_conflict_diff(conflict_id).await.map_err(SyncError::Database)
+    }
+
+    /// Resolve a conflict field by field, after any running sync finishes
+    pub async fn resolve_conflict_fields(
+        &self,
+        conflict_id: i64,
+        choices: HashMap<String, FieldChoice>,
+    ) -> Result<String, SyncError> {
+        let _running = self.control.wait().await;
+        self.conflict_resolver.resolve_conflict_fields(conflict_id, choices).await.map_err(SyncError::Database)
+    }
+
+    /// Probe the sync server
+    pub async fn check_online(&self) -> bool {
+        self.transport.health_check().await
+    }
+
+    /// Count local changes not yet pushed, including ones waiting for a retry
+    pub async fn count_unsynced_changes(&self) -> Result<i32, SyncError> {
+        self.queue_manager.collect_captured_changes().await?;
+        let stats = self.queue_manager.get_queue_stats().await?;
+        Ok(stats.pending + stats.failed)
+    }
+
+    /// Workspace of the most recent sync run
+    pub async fn last_synced_workspace(&self) -> Result<Option<String>, SyncError> {
+        let latest = self.history.latest_run().await?;
+        Ok(latest.map(|run| run.workspace_id))
     }
 
     /// Get current sync status
     pub async fn get_sync_status(&self) -> Result<SyncStatusResponse, SyncError> {
-        let is_online = SyncUtils::is_online(&self.config.remote_api_base).await;
+        // The connectivity monitor keeps this current; probe only before its first check
+        let is_online = match self.connectivity.is_online() {
+            Some(is_online) => is_online,
+            None => self.transport.health_check().await,
+        };
         let tables = self.status_manager.get_all_table_status().await?;
         let pending_changes = self.queue_manager.count_pending_changes().await?;
         let conflicts = self.conflict_resolver.count_conflicts().await?;
@@ -428,16 +1234,511 @@ impl SyncEngine {
         })
     }
 
+    /// Retry failed changes now, skipping their backoff, after any running sync finishes
+    pub async fn retry_failed_changes(&self) -> Result<i32, SyncError> {
+        let _running = self.control.wait().await;
+        Ok(self.queue_manager.retry_failed_changes().await?)
+    }
+
+    /// Clear all failed changes, after any running sync finishes
+    pub async fn clear_failed_changes(&self) -> Result<(), SyncError> {
+        let _running = self.control.wait().await;
+        Ok(self.queue_manager.clear_failed_changes().await?)
+    }
+
+    /// Get performance metrics, including the bytes moved by the latest sync
+    pub async fn get_sync_performance(&self) -> Result<SyncPerformanceMetrics, SyncError> {
+        let mut metrics = self.status_manager.get_sync_performance().await?;
+        self.transfer_stats.apply_to(&mut metrics);
+
+        if let Some(latest) = self.history.latest_run().await? {
+            metrics.sync_duration_ms = latest.duration_ms;
+            metrics.records_per_second = latest.records_per_second();
+        }
+
+        Ok(metrics)
+    }
+
+    /// Get past sync runs with aggregated throughput trends
+    pub async fn get_sync_history(&self, query: &SyncHistoryQuery) -> Result<SyncHistory, SyncError> {
+        let history = self.history.get_history(query).await?;
+        Ok(history)
+    }
+
+    /// List queued changes with the fields each would push
+    pub async fn list_queued_changes(&self, filter: &SyncQueueFilter) -> Result<Vec<SyncQueueEntry>, SyncError> {
+        self.queue_manager.collect_captured_changes().await?;
+        let changes = self.queue_manager.list_changes(filter).await?;
+
+        let mut conn = self.sqlite_pool.acquire().await?;
+        let mut entries = Vec::with_capacity(changes.len());
+        for change in changes {
+            entries.push(self.describe_queued_change(&mut conn, change).await?);
+        }
+
+        Ok(entries)
+    }
+
+    /// Diff what a queued change would push against the last synced version
+    async fn describe_queued_change(
+        &self,
+        conn: &mut SqliteConnection,
+        change: SyncQueueItem,
+    ) -> Result<SyncQueueEntry, SyncError> {
+        let local_row = self
+            .record_mapper
+            .read_record(&mut *conn, &change.table_name, &change.record_id)
+            .await?
+            .map(|local| local.data);
+        let synced = self
+            .conflict_resolver
+            .get_base_version(&mut *conn, &change.table_name, &change.record_id)
+            .await?
+            .and_then(|data| serde_json::from_str::<Value>(&data).ok());
+
+        // Pushes send the current row, or the queued payload once the row is gone
+        let pending = match change.operation {
+            SyncOperation::Delete => Value::Null,
+            SyncOperation::Insert | SyncOperation::Update => local_row
+                .clone()
+                .or_else(|| change.data.as_deref().and_then(|data| serde_json::from_str(data).ok()))
+                .unwrap_or(Value::Null),
+        };
+        let diff = diff_pending(synced.as_ref().unwrap_or(&Value::Null), &pending, SYNC_METADATA_COLUMNS);
+
+        Ok(SyncQueueEntry {
+            change,
+            local_row,
+            diff,
+            has_synced_version: synced.is_some(),
+        })
+    }
+
+    /// Replace the payload of a failed change and queue it again, after any running sync finishes
+    ///
+    /// Pushes send the local row, so the edited fields are written over it
+    /// as a regular local edit, which the capture folds back into this
+    /// change. Returns the edited fields the table has no column for.
+    pub async fn edit_queued_change(&self, id: i64, data: &Value) -> Result<Vec<String>, SyncError> {
+        let _running = self.control.wait().await;
+        let change = self.require_queued_change(id).await?;
+
+        if !matches!(change.status, SyncQueueStatus::Failed | SyncQueueStatus::DeadLetter) {
+            return Err(SyncError::Queue(format!("Change {} has not failed, only failed changes can be edited", id)));
+        }
+        if change.operation == SyncOperation::Delete {
+            return Err(SyncError::Queue(format!("Change {} is a delete and has no payload to edit", id)));
+        }
+        if !data.is_object() {
+            return Err(SyncError::Queue("An edited payload must be a JSON object".to_string()));
+        }
+
+        let table = self.table_registry.require(&change.table_name)?;
+        let mut tx = self.sqlite_pool.begin().await?;
+
+        // The edited fields go over the row, so a partial edit keeps the rest
+        let mut record = match self.record_mapper.read_record(&mut tx, table.name, &change.record_id).await? {
+            Some(local) => local.data,
+            None => Value::Object(Default::default()),
+        };
+        if let (Value::Object(fields), Value::Object(edits)) = (&mut record, data) {
+            fields.extend(edits.clone());
+        }
+        let data = record.to_string();
+
+        // Pending first, so the captured edit coalesces into this change
+        SyncQueue::replace_failed_payload_in(&mut tx, id, &data).await?;
+        let dropped_fields = self
+            .record_mapper
+            .write_record(&mut tx, table.name, &change.record_id, &data, VersionUpdate::Keep, true)
+            .await?;
+        tx.commit().await?;
+
+        self.queue_manager.collect_captured_changes().await?;
+        println!("✏️ [SYNC] Edited queued change {} for {}/{}", id, table.name, change.record_id);
+        Ok(dropped_fields)
+    }
+
+    /// Retry a failed or dead-lettered change on the next sync, after any running sync finishes
+    pub async fn requeue_queued_change(&self, id: i64) -> Result<(), SyncError> {
+        let _running = self.control.wait().await;
+        self.queue_manager.requeue_change(id).await?;
+        Ok(())
+    }
+
+    /// Drop a local change and put back the last synced version of its record
+    ///
+    /// Later changes to the record are dropped with it. An insert that was
+    /// never sent removes the row, since the server never had it. Without a
+    /// synced version to restore, the server's copy of the record is fetched
+    /// instead, so the discard fails while the server can't be reached.
+    pub async fn discard_queued_change(&self, id: i64) -> Result<(), SyncError> {
+        let _running = self.control.wait().await;
+        let change = self.require_queued_change(id).await?;
+        let table = self.table_registry.require(&change.table_name)?;
+
+        let mut conn = self.sqlite_pool.acquire().await?;
+        let synced = self
+            .conflict_resolver
+            .get_base_version(&mut conn, table.name, &change.record_id)
+            .await?;
+        let sent = SyncQueue::was_sent_in(&mut conn, change.id).await?;
+        drop(conn);
+
+        // A sent insert may have reached the server
+        let server_copy = if synced.is_none() && (change.operation != SyncOperation::Insert || sent) {
+            Some(self.fetch_server_copy(table, &change.record_id).await?)
+        } else {
+            None
+        };
+
+        // Restores are sync writes: the server already has what is written back
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+        SyncQueue::discard_change_in(&mut tx, &change).await?;
+        SyncQueue::cancel_unpushed_changes_in(&mut tx, table.name, &change.record_id).await?;
+
+        match (synced, server_copy) {
+            (Some(synced), _) => {
+                self.record_mapper
+                    .write_record(&mut tx, table.name, &change.record_id, &synced, VersionUpdate::Keep, false)
+                    .await?;
+
+                let synced_clock = serde_json::from_str::<Value>(&synced)
+                    .ok()
+                    .and_then(|data| HybridTimestamp::from_record(&data));
+                if let Some(stamp) = synced_clock {
+                    clock::set_record_clock(&mut tx, table.name, &change.record_id, &stamp).await?;
+                }
+                tombstones::clear_tombstone(&mut tx, table.name, &change.record_id).await?;
+            }
+            (None, Some(Some(remote))) => {
+                self.apply_remote_change(&mut tx, table, &remote).await?;
+            }
+            (None, _) => {
+                let query = format!("DELETE FROM {} WHERE {} = ?", table.name, table.primary_key);
+                sqlx::query(&query).bind(&change.record_id).execute(&mut *tx).await?;
+                tombstones::clear_tombstone(&mut tx, table.name, &change.record_id).await?;
+            }
+        }
+
+        commit_sync_write(tx).await?;
+
+        println!("↩️ [SYNC] Discarded queued change {} and restored {}/{}", id, table.name, change.record_id);
+        Ok(())
+    }
+
+    /// The server's copy of a record, `None` when the server doesn't have it
+    async fn fetch_server_copy(&self, table: &SyncTable, record_id: &str) -> Result<Option<SyncRecord>, SyncError> {
+        let workspace_id = self
+            .last_synced_workspace()
+            .await?
+            .ok_or_else(|| SyncError::Queue(format!("No synced version of {}/{} to restore", table.name, record_id)))?;
+
+        self.transport.fetch_record(table.name, &workspace_id, record_id).await
+    }
+
+    async fn require_queued_change(&self, id: i64) -> Result<SyncQueueItem, SyncError> {
+        self.queue_manager
+            .get_change(id)
+            .await?
+            .ok_or_else(|| SyncError::Queue(format!("Queued change {} not found", id)))
+    }
+
+    /// Write every unpushed change to a signed bundle file
+    ///
+    /// The queue is left as it is: if this device reaches the server later,
+    /// its pushes carry the same mutation IDs and aren't applied twice.
+    pub async fn export_sync_bundle(&self, workspace_id: &str, path: &Path) -> Result<BundleManifest, SyncError> {
+        let key = bundle::signing_key()?;
+        self.queue_manager.collect_captured_changes().await?;
+
+        let mut changes = Vec::new();
+        for table in self.table_registry.sync_order()? {
+            let queued = self.queue_manager.get_unpushed_changes(table.name).await?;
+            if queued.is_empty() {
+                continue;
+            }
+
+            // The bundle carries what a push would send, plus the row's version and clock
+            let prepared = self.with_current_row_payloads(table, &queued).await?;
+            let mut conn = self.sqlite_pool.acquire().await?;
+            for change in prepared {
+                let sync_version = self
+                    .load_local_record(&mut conn, table, &change.record_id)
+                    .await?
+                    .map(|local| local.sync_version);
+                let hlc = clock::record_clock(&mut conn, table.name, &change.record_id)
+                    .await?
+                    .map(|stamp| stamp.to_string());
+
+                changes.push(BundledChange { change, sync_version, hlc });
+            }
+        }
+
+        let mut conn = self.sqlite_pool.acquire().await?;
+        let device_id = clock::device_id(&mut conn).await?;
+        let bundle = SyncBundle::new(workspace_id, &device_id, changes)?;
+
+        tokio::fs::write(path, bundle.to_bytes(&key)?)
+            .await
+            .map_err(|e| SyncError::Bundle(format!("Failed to write {}: {}", path.display(), e)))?;
+
+        println!(
+            "📦 [SYNC] Exported {} changes to bundle {}",
+            bundle.manifest.change_count, bundle.manifest.bundle_id
+        );
+        Ok(bundle.manifest)
+    }
+
+    /// Stage the changes of a bundle exported on another device, then push them
+    ///
+    /// Each change is written to the local row as the source device had it
+    /// and queued under its original mutation ID, so the push goes through
+    /// the same conflict handling as a change made here. Records this device
+    /// has unsynced edits to are skipped rather than overwritten. While
+    /// offline, staged changes wait in the queue for the next sync.
+    ///
+    /// Bundles exported from another workspace are refused.
+    pub async fn import_sync_bundle(&self, workspace_id: &str, path: &Path) -> Result<BundleImportReport, SyncError> {
+        let key = bundle::signing_key()?;
+        let bytes = tokio::fs::read(path)
+            .await
+            .map_err(|e| SyncError::Bundle(format!("Failed to read {}: {}", path.display(), e)))?;
+        let bundle = SyncBundle::from_bytes(&bytes, &key)?;
+
+        if bundle.manifest.workspace_id != workspace_id {
+            return Err(SyncError::Bundle(format!(
+                "Bundle belongs to workspace {}, not {}",
+                bundle.manifest.workspace_id, workspace_id
+            )));
+        }
+
+        let mut report = BundleImportReport {
+            bundle_id: bundle.manifest.bundle_id.clone(),
+            workspace_id: bundle.manifest.workspace_id.clone(),
+            source_device_id: bundle.manifest.device_id.clone(),
+            changes_staged: 0,
+            changes_already_imported: 0,
+            conflicts: 0,
+            skipped_records: Vec::new(),
+            sync_report: None,
+        };
+
+        {
+            let _running = self.control.begin()?;
+
+            let mut conn = self.sqlite_pool.acquire().await?;
+            if clock::device_id(&mut conn).await? == bundle.manifest.device_id {
+                return Err(SyncError::Bundle("This bundle was exported from this device".to_string()));
+            }
+            drop(conn);
+
+            for bundled in &bundle.changes {
+                self.table_registry.require(&bundled.change.table_name)?;
+            }
+
+            // Parents are staged before the records that reference them
+            for table in self.table_registry.sync_order()? {
+                for bundled in bundle.changes.iter().filter(|bundled| bundled.change.table_name == table.name) {
+                    self.stage_bundled_change(table, bundled, &mut report).await?;
+                }
+            }
+        }
+
+        println!(
+            "📦 [SYNC] Staged {} changes from bundle {} ({} already imported, {} conflicts, {} skipped)",
+            report.changes_staged,
+            report.bundle_id,
+            report.changes_already_imported,
+            report.conflicts,
+            report.skipped_records.len()
+        );
+
+        if self.transport.health_check().await {
+            match self.sync_workspace_triggered_by(&report.workspace_id, SyncTrigger::Import).await {
+                Ok(sync_report) => report.sync_report = Some(sync_report),
+                Err(e) => println!("⚠️ [SYNC] Imported changes stay queued, sync failed: {}", e),
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Write one bundled change to its local row and queue it
+    ///
+    /// A record with unsynced edits here is compared with the change the way
+    /// a pulled change is, so it is merged or held as a conflict.
+    async fn stage_bundled_change(
+        &self,
+        table: &SyncTable,
+        bundled: &BundledChange,
+        report: &mut BundleImportReport,
+    ) -> Result<(), SyncError> {
+        let change = &bundled.change;
+
+        // Sync write: the change is queued below under its original mutation ID
+        let mut tx = begin_sync_write(&self.sqlite_pool).await?;
+
+        if SyncQueue::has_mutation_in(&mut tx, &change.client_mutation_id).await? {
+            report.changes_already_imported += 1;
+            return Ok(());
+        }
+
+        let local = self.load_local_record(&mut tx, table, &change.record_id).await?;
+        let edited_here = match &local {
+            Some(local) if local.is_dirty => true,
+            _ => SyncQueue::has_unpushed_change_in(&mut tx, table.name, &change.record_id).await?,
+        };
+
+        // The change as a write from the source device, carrying its clock
+        let mut data = match change.data.as_deref() {
+            Some(data) => serde_json::from_str::<Value>(data)?,
+            None => Value::Object(Default::default()),
+        };
+        if let (Some(stamp), Value::Object(fields)) = (&bundled.hlc, &mut data) {
+            fields.insert(CLOCK_FIELD.to_string(), Value::String(stamp.clone()));
+        }
+        let record = SyncRecord {
+            id: change.record_id.clone(),
+            operation: change.operation.clone(),
+            data: data.to_string(),
+            sync_version: bundled.sync_version.or(local.map(|local| local.sync_version)).unwrap_or(0),
+            last_modified: change.created_at.clone(),
+        };
+
+        if edited_here {
+            match self.apply_or_detect_conflict(&mut tx, table, &record).await? {
+                PullOutcome::Superseded => {
+                    report.skipped_records.push(format!("{}/{}", table.name, change.record_id));
+                    return Ok(());
+                }
+                PullOutcome::ConflictResolved | PullOutcome::ConflictHeld => report.conflicts += 1,
+                PullOutcome::Applied(_) | PullOutcome::Merged(..) => {}
+            }
+
+            // The server hasn't seen a delete the change brought either
+            if tombstones::find_tombstone(&mut tx, table.name, &record.id).await?.is_some() {
+                tombstones::unacknowledge_tombstone(&mut tx, table.name, &record.id).await?;
+            }
+        } else {
+            self.stage_bundled_record(&mut tx, table, &record, bundled.sync_version).await?;
+        }
+
+        SyncQueue::import_change_in(&mut tx, change).await?;
+        commit_sync_write(tx).await?;
+
+        report.changes_staged += 1;
+        Ok(())
+    }
+
+    /// Write a bundled change to a record without unsynced edits here
+    async fn stage_bundled_record(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        record: &SyncRecord,
+        sync_version: Option<i32>,
+    ) -> Result<(), SyncError> {
+        let record = &*self.observe_remote_clock(&mut *conn, record).await?;
+
+        let deletes = match record.operation {
+            SyncOperation::Insert | SyncOperation::Update => {
+                let version = sync_version.map_or(VersionUpdate::Keep, VersionUpdate::Set);
+                self.record_mapper
+                    .write_record(&mut *conn, table.name, &record.id, &record.data, version, true)
+                    .await?;
+
+                if let Some(stamp) = remote_clock(record) {
+                    clock::set_record_clock(&mut *conn, table.name, &record.id, &stamp).await?;
+                }
+
+                is_soft_deleted(record)
+            }
+            SyncOperation::Delete => true,
+        };
+
+        if deletes {
+            // The server hasn't seen this delete yet, so its tombstone must stay
+            self.delete_record(&mut *conn, table, record).await?;
+            tombstones::unacknowledge_tombstone(&mut *conn, table.name, &record.id).await?;
+        } else {
+            tombstones::clear_tombstone(&mut *conn, table.name, &record.id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get a workspace's replication scope
+    pub async fn get_replication_scope(&self, workspace_id: &str) -> Result<ReplicationScope, SyncError> {
+        let settings = SyncSettingsStore::new(self.sqlite_pool.clone());
+        let scope = settings.load_replication_scope(workspace_id).await?;
+        Ok(scope)
+    }
+
+    /// Replace a workspace's replication scope
+    ///
+    /// Tables whose scope changed, and the tables below them, are pulled again
+    /// from the start on the next sync, so rows a widened scope now covers are
+    /// fetched; rows a narrowed scope no longer covers are evicted after that pull.
+    pub async fn set_replication_scope(&self, scope: &ReplicationScope) -> Result<(), SyncError> {
+        scope.validate(&self.table_registry)?;
+
+        // A running sync would save its watermarks over the reset
+        let _running = self.control.wait().await;
+
+        let settings = SyncSettingsStore::new(self.sqlite_pool.clone());
+        let previous = settings.load_replication_scope(&scope.workspace_id).await?;
+        settings.save_replication_scope(scope).await?;
+
+        for table in self.table_registry.tables() {
+            if scope.changes_rows_of(&previous, &self.table_registry, table.name) {
+                self.status_manager.reset_pull_position(table.name).await?;
+            }
+        }
+
+        println!("🎯 [SYNC] Replication scope updated for workspace {}", scope.workspace_id);
+        Ok(())
+    }
+
+    /// Check whether a sync is currently running
+    pub fn is_sync_in_progress(&self) -> bool {
+        self.control.is_running()
+    }
+
+    /// Stop the running sync at the next batch boundary
+    ///
+    /// Committed pages and pushed batches are kept; the rest is picked up by
+    /// the next sync. Returns false when no sync is running.
+    pub fn cancel_sync(&self) -> bool {
+        self.control.request_cancel()
+    }
+
     /// Enable background sync
+    ///
+    /// Persists the interval so the `BackgroundSyncScheduler` picks it up,
+    /// including after an app restart.
     pub async fn enable_background_sync(&self, interval_minutes: u32) -> Result<(), SyncError> {
-        // This would typically start a background task
-        // For now, we'll just update the config
+        if interval_minutes == 0 {
+            return Err(SyncError::Configuration("Background sync interval must be at least 1 minute".to_string()));
+        }
+
+        let settings = SyncSettingsStore::new(self.sqlite_pool.clone());
+        let mut config = settings.load_config().await?;
+        config.enable_background_sync = true;
+        config.sync_interval_minutes = interval_minutes;
+        settings.save_config(&config).await?;
+
         println!("🔄 [SYNC] Background sync enabled with {} minute interval", interval_minutes);
         Ok(())
     }
 
     /// Disable background sync
     pub async fn disable_background_sync(&self) -> Result<(), SyncError> {
+        let settings = SyncSettingsStore::new(self.sqlite_pool.clone());
+        let mut config = settings.load_config().await?;
+        config.enable_background_sync = false;
+        settings.save_config(&config).await?;
+
         println!("⏹️ [SYNC] Background sync disabled");
         Ok(())
     }
@@ -453,12 +1754,12 @@ impl SyncEngineTrait for SyncEngine {
         self.sync_table(table_name, workspace_id).await.map_err(|e| e.to_string())
     }
 
-    async fn push_changes(&self, workspace_id: &str) -> Result<SyncResult, String> {
+    async fn push_changes(&self, _workspace_id: &str) -> Result<SyncResult, String> {
         // Implementation would push all pending changes
         Ok(SyncResult::new())
     }
 
-    async fn pull_changes(&self, workspace_id: &str) -> Result<SyncResult, String> {
+    async fn pull_changes(&self, _workspace_id: &str) -> Result<SyncResult, String> {
         // Implementation would pull all remote changes
         Ok(SyncResult::new())
     }
@@ -479,3 +1780,596 @@ impl SyncEngineTrait for SyncEngine {
         self.disable_background_sync().await.map_err(|e| e.to_string())
     }
 }
+
+/// The hybrid timestamp a remote change was written at, when it carries one
+fn remote_clock(change: &SyncRecord) -> Option<HybridTimestamp> {
+    serde_json::from_str::<Value>(&change.data)
+        .ok()
+        .and_then(|data| HybridTimestamp::from_record(&data))
+}
+
+/// Whether a remote insert or update carries a `deleted_at`
+fn is_soft_deleted(change: &SyncRecord) -> bool {
+    serde_json::from_str::<Value>(&change.data)
+        .ok()
+        .and_then(|data| data.get(tombstones::SOFT_DELETE_COLUMN).map(|deleted_at| !deleted_at.is_null()))
+        .unwrap_or(false)
+}
+
+// ====================================================================
+// TRANSPORT SELECTION
+// ====================================================================
+
+/// Pick the transport for this build
+fn default_transport(
+    postgres_pool: Option<PgPool>,
+    config: &SyncConfig,
+    transfer_stats: Arc<TransferStats>,
+) -> Arc<dyn SyncTransport> {
+    #[cfg(feature = "direct-postgres-sync")]
+    if let Some(pool) = postgres_pool {
+        return Arc::new(PostgresTransport::new(pool));
+    }
+    #[cfg(not(feature = "direct-postgres-sync"))]
+    let _ = postgres_pool;
+
+    Arc::new(HttpTransport::new(config, transfer_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+
+    /// An engine on a fresh cache, holding c1 as an unsynced local edit
+    async fn engine_with_local_edit(strategy: ConflictResolutionStrategy) -> SyncEngine {
+        let config = SyncConfig {
+            conflict_resolution_strategy: strategy,
+            ..SyncConfig::default()
+        };
+        let engine = SyncEngine::new(test_cache_pool().await, None, config);
+
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO companies (id, workspace_id, name, is_dirty) VALUES ('c1', 'ws-1', 'Local Acme', 1);",
+        )
+        .execute(&engine.sqlite_pool)
+        .await
+        .unwrap();
+        engine
+            .queue_manager
+            .enqueue_change("companies", "c1", SyncOperation::Update, Some(r#"{"name":"Local Acme"}"#.to_string()))
+            .await
+            .unwrap();
+
+        engine
+    }
+
+    fn remote_change(operation: SyncOperation, name: &str) -> SyncRecord {
+        SyncRecord {
+            id: "c1".to_string(),
+            operation,
+            data: serde_json::json!({ "id": "c1", "workspace_id": "ws-1", "name": name }).to_string(),
+            sync_version: 2,
+            last_modified: SyncUtils::current_timestamp(),
+        }
+    }
+
+    async fn company_name(engine: &SyncEngine) -> Option<String> {
+        sqlx::query_scalar("SELECT name FROM companies WHERE id = 'c1'")
+            .fetch_optional(&engine.sqlite_pool)
+            .await
+            .unwrap()
+    }
+
+    async fn is_deleted(engine: &SyncEngine) -> bool {
+        sqlx::query_scalar("SELECT deleted_at IS NOT NULL FROM companies WHERE id = 'c1'")
+            .fetch_one(&engine.sqlite_pool)
+            .await
+            .unwrap()
+    }
+
+    fn companies(engine: &SyncEngine) -> &SyncTable {
+        engine.table_registry.require("companies").unwrap()
+    }
+
+    async fn pull(engine: &SyncEngine, change: SyncRecord) -> PullOutcome {
+        let mut conn = engine.sqlite_pool.acquire().await.unwrap();
+        engine.apply_or_detect_conflict(&mut conn, companies(engine), &change).await.unwrap()
+    }
+
+    /// A server whose companies feed ends without a cursor, recording how each pull started
+    #[derive(Default)]
+    struct CursorlessServer {
+        pulls: std::sync::Mutex<Vec<(Option<String>, Option<String>)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SyncTransport for CursorlessServer {
+        async fn push_batch(&self, _: &str, _: &str, _: &[SyncQueueItem]) -> Result<Vec<PushRecordResult>, SyncError> {
+            Ok(Vec::new())
+        }
+
+        async fn pull_page(
+            &self,
+            _: &str,
+            _: &str,
+            cursor: Option<&str>,
+            since: Option<&str>,
+            _: Option<&TableScope>,
+            _: u32,
+        ) -> Result<PulledPage, SyncError> {
+            self.pulls.lock().unwrap().push((cursor.map(str::to_string), since.map(str::to_string)));
+            Ok(PulledPage { records: Vec::new(), malformed: Vec::new(), next_cursor: None, has_more: false })
+        }
+
+        async fn fetch_record(&self, _: &str, _: &str, _: &str) -> Result<Option<SyncRecord>, SyncError> {
+            Ok(None)
+        }
+
+        async fn acknowledge_pulls(&self, _: &str, _: &str, _: &str) -> Result<Option<String>, SyncError> {
+            Ok(None)
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn local_edits_are_pushed_through_the_transport() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+
+        let result = engine.push_table_changes(companies(&engine), "ws-1").await.unwrap();
+
+        assert_eq!(result.records_updated, 1);
+        assert!(server.record("companies", "c1").unwrap().contains("Local Acme"));
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn changes_stay_queued_while_the_server_is_unreachable() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+        server.set_online(false);
+
+        assert!(matches!(engine.sync_workspace("ws-1").await, Err(SyncError::Network(_))));
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 1);
+        assert_eq!(server.record("companies", "c1"), None);
+    }
+
+    #[tokio::test]
+    async fn a_final_page_without_a_cursor_is_not_pulled_again() {
+        let server = Arc::new(CursorlessServer::default());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+        {
+            let mut conn = engine.sqlite_pool.acquire().await.unwrap();
+            let watermark = SyncWatermark {
+                table_name: "companies".to_string(),
+                last_sync_timestamp: SyncUtils::current_timestamp(),
+                last_sync_version: 1,
+                record_count: 10,
+                cursor: Some("page-7".to_string()),
+            };
+            engine.status_manager.save_watermark(&mut conn, &watermark).await.unwrap();
+        }
+
+        engine.pull_table_changes(companies(&engine), "ws-1", &ReplicationScope::full("ws-1")).await.unwrap();
+        engine.pull_table_changes(companies(&engine), "ws-1", &ReplicationScope::full("ws-1")).await.unwrap();
+
+        let pulls = server.pulls.lock().unwrap().clone();
+        assert_eq!(pulls[0], (Some("page-7".to_string()), None));
+        // The second pull asks for what changed since the first, not for page-7 again
+        assert_eq!(pulls[1].0, None);
+        assert!(pulls[1].1.is_some());
+    }
+
+    /// A server whose companies feed has two empty pages, recording when each was requested
+    #[derive(Default)]
+    struct TwoPageServer {
+        requested_at: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl SyncTransport for TwoPageServer {
+        async fn push_batch(&self, _: &str, _: &str, _: &[SyncQueueItem]) -> Result<Vec<PushRecordResult>, SyncError> {
+            Ok(Vec::new())
+        }
+
+        async fn pull_page(
+            &self,
+            _: &str,
+            _: &str,
+            cursor: Option<&str>,
+            _: Option<&str>,
+            _: Option<&TableScope>,
+            _: u32,
+        ) -> Result<PulledPage, SyncError> {
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+            self.requested_at.lock().unwrap().push(chrono::Utc::now().to_rfc3339());
+            let first = cursor.is_none();
+            Ok(PulledPage {
+                records: Vec::new(),
+                malformed: Vec::new(),
+                next_cursor: Some(if first { "page-1" } else { "page-2" }.to_string()),
+                has_more: first,
+            })
+        }
+
+        async fn fetch_record(&self, _: &str, _: &str, _: &str) -> Result<Option<SyncRecord>, SyncError> {
+            Ok(None)
+        }
+
+        async fn acknowledge_pulls(&self, _: &str, _: &str, _: &str) -> Result<Option<String>, SyncError> {
+            Ok(None)
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn a_multi_page_pull_is_stamped_with_when_it_started() {
+        let server = Arc::new(TwoPageServer::default());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+
+        engine.pull_table_changes(companies(&engine), "ws-1", &ReplicationScope::full("ws-1")).await.unwrap();
+
+        let requested_at = server.requested_at.lock().unwrap().clone();
+        assert_eq!(requested_at.len(), 2);
+        let watermark = engine.status_manager.get_watermark("companies").await.unwrap().unwrap();
+        let stamped = chrono::DateTime::parse_from_rfc3339(&watermark.last_sync_timestamp).unwrap();
+        assert!(stamped <= chrono::DateTime::parse_from_rfc3339(&requested_at[0]).unwrap());
+    }
+
+    #[tokio::test]
+    async fn a_change_that_fails_to_apply_is_set_aside_and_retried() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+        // c2 belongs to a workspace this device doesn't have yet, so its foreign key fails
+        server.remote_upsert("companies", "c2", &serde_json::json!({ "id": "c2", "workspace_id": "ws-2", "name": "Orphan" }).to_string());
+        server.remote_upsert("companies", "c3", &serde_json::json!({ "id": "c3", "workspace_id": "ws-1", "name": "Fine" }).to_string());
+
+        let first = engine.pull_table_changes(companies(&engine), "ws-1", &ReplicationScope::full("ws-1")).await.unwrap();
+
+        assert_eq!(first.records_created, 1);
+        assert_eq!(first.errors.len(), 1);
+        let failures = engine.status_manager.get_pull_failures("companies").await.unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].record.id, "c2");
+        // The rest of the page committed and the cursor moved past it
+        let cursor = engine.status_manager.get_watermark("companies").await.unwrap().unwrap().cursor;
+        assert_eq!(cursor.as_deref(), Some("2"));
+
+        sqlx::query("INSERT INTO workspaces (id, name, slug) VALUES ('ws-2', 'Other', 'ws-2')")
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+        let retried = engine.pull_table_changes(companies(&engine), "ws-1", &ReplicationScope::full("ws-1")).await.unwrap();
+
+        assert!(retried.errors.is_empty());
+        assert_eq!(retried.records_created, 1);
+        assert!(engine.status_manager.get_pull_failures("companies").await.unwrap().is_empty());
+        let orphan: Option<String> = sqlx::query_scalar("SELECT name FROM companies WHERE id = 'c2'")
+            .fetch_optional(&engine.sqlite_pool)
+            .await
+            .unwrap();
+        assert_eq!(orphan.as_deref(), Some("Orphan"));
+    }
+
+    #[tokio::test]
+    async fn pulled_changes_to_a_dirty_row_are_held_as_a_conflict() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+
+        let outcome = pull(&engine, remote_change(SyncOperation::Update, "Remote Acme")).await;
+
+        assert!(matches!(outcome, PullOutcome::ConflictHeld));
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Local Acme"));
+        assert_eq!(engine.conflict_resolver.count_conflicts().await.unwrap(), 1);
+        // The local edit waits for the conflict instead of being pushed over the remote side
+        assert!(engine.queue_manager.get_pending_changes("companies").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn a_rolled_back_page_leaves_no_conflict_behind() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+
+        let mut tx = engine.sqlite_pool.begin().await.unwrap();
+        let outcome = engine
+            .apply_or_detect_conflict(&mut tx, companies(&engine), &remote_change(SyncOperation::Update, "Remote Acme"))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, PullOutcome::ConflictHeld));
+        tx.rollback().await.unwrap();
+
+        assert_eq!(engine.conflict_resolver.count_conflicts().await.unwrap(), 0);
+        assert_eq!(engine.queue_manager.get_pending_changes("companies").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_winning_remote_delete_drops_the_queued_local_edit() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::RemoteWins).await;
+
+        let outcome = pull(&engine, remote_change(SyncOperation::Delete, "Local Acme")).await;
+
+        assert!(matches!(outcome, PullOutcome::ConflictResolved));
+        assert!(is_deleted(&engine).await);
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn a_pulled_delete_outranks_older_writes_arriving_late() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::RemoteWins).await;
+        pull(&engine, remote_change(SyncOperation::Delete, "Local Acme")).await;
+
+        // Neither write carries a clock, so their updated_at is compared with the delete
+        let before_delete = (chrono::Utc::now() - chrono::Duration::minutes(5)).to_rfc3339();
+        let stale = SyncRecord {
+            data: serde_json::json!({ "id": "c1", "workspace_id": "ws-1", "name": "Stale Acme", "updated_at": before_delete })
+                .to_string(),
+            sync_version: 3,
+            ..remote_change(SyncOperation::Update, "Stale Acme")
+        };
+        assert!(matches!(pull(&engine, stale).await, PullOutcome::Superseded));
+        assert!(is_deleted(&engine).await);
+
+        let newer = remote_change(SyncOperation::Update, "Revived Acme");
+        assert!(!matches!(pull(&engine, newer).await, PullOutcome::Superseded));
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Revived Acme"));
+    }
+
+    #[tokio::test]
+    async fn local_wins_keeps_edits_made_while_the_conflict_was_held() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        pull(&engine, remote_change(SyncOperation::Update, "Remote Acme")).await;
+        sqlx::query("UPDATE companies SET name = 'Local Acme 2', is_dirty = 1 WHERE id = 'c1'")
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+
+        let conflict = engine.get_unresolved_conflicts().await.unwrap().remove(0);
+        engine.resolve_conflict(conflict.id, ConflictResolution::LocalWins).await.unwrap();
+
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Local Acme 2"));
+        let pending = engine.queue_manager.get_pending_changes("companies").await.unwrap();
+        assert!(pending.iter().any(|change| change.data.as_deref().unwrap_or_default().contains("Local Acme 2")));
+    }
+
+    #[test]
+    fn a_second_sync_is_refused_while_one_is_running() {
+        let control = SyncControl::new();
+
+        let running = control.begin().unwrap();
+        assert!(control.is_running());
+        assert!(matches!(control.begin(), Err(SyncError::AlreadyRunning)));
+
+        drop(running);
+        assert!(!control.is_running());
+        assert!(control.begin().is_ok());
+    }
+
+    #[tokio::test]
+    async fn queue_writes_wait_for_the_running_sync() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        let change = engine.queue_manager.get_pending_changes("companies").await.unwrap().remove(0);
+        engine.queue_manager.mark_as_failed(change.id, "timed out").await.unwrap();
+
+        let running = engine.control.begin().unwrap();
+        let retrying = engine.retry_failed_changes();
+        let waited = tokio::time::timeout(std::time::Duration::from_millis(100), retrying).await;
+        assert!(waited.is_err());
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 0);
+
+        drop(running);
+        assert_eq!(engine.retry_failed_changes().await.unwrap(), 1);
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn a_scope_change_waits_for_the_running_sync() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        let watermark = SyncWatermark {
+            table_name: "actions".to_string(),
+            last_sync_timestamp: SyncUtils::current_timestamp(),
+            last_sync_version: 1,
+            record_count: 1,
+            cursor: Some("page-2".to_string()),
+        };
+        let mut conn = engine.sqlite_pool.acquire().await.unwrap();
+        engine.status_manager.save_watermark(&mut conn, &watermark).await.unwrap();
+        drop(conn);
+
+        let mut scope = ReplicationScope::full("ws-1");
+        scope.tables.push(TableScope {
+            table_name: "actions".to_string(),
+            skip_pull: false,
+            max_age_days: Some(30),
+            age_column: None,
+            seller_user_id: None,
+        });
+
+        let running = engine.control.begin().unwrap();
+        let waited = tokio::time::timeout(std::time::Duration::from_millis(100), engine.set_replication_scope(&scope)).await;
+        assert!(waited.is_err());
+        let cursor = engine.status_manager.get_watermark("actions").await.unwrap().unwrap().cursor;
+        assert_eq!(cursor.as_deref(), Some("page-2"));
+
+        drop(running);
+        engine.set_replication_scope(&scope).await.unwrap();
+        assert!(engine.status_manager.get_watermark("actions").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn people_of_companies_outside_the_scope_are_not_pulled() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = SyncEngine::new(test_cache_pool().await, None, SyncConfig::default()).with_transport(server.clone());
+        sqlx::query("INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1')")
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+        server.remote_upsert("users", "u1", r#"{"id":"u1","email":"u1@example.com","name":"One"}"#);
+        server.remote_upsert("users", "u2", r#"{"id":"u2","email":"u2@example.com","name":"Two"}"#);
+        server.remote_upsert("companies", "mine", r#"{"id":"mine","workspace_id":"ws-1","name":"Mine","main_seller_id":"u1"}"#);
+        server.remote_upsert("companies", "theirs", r#"{"id":"theirs","workspace_id":"ws-1","name":"Theirs","main_seller_id":"u2"}"#);
+        for (id, company) in [("p1", "\"mine\""), ("p2", "\"theirs\""), ("p3", "null")] {
+            let person = format!(
+                r#"{{"id":"{}","workspace_id":"ws-1","company_id":{},"first_name":"Pat","last_name":"Lee","full_name":"Pat Lee"}}"#,
+                id, company
+            );
+            server.remote_upsert("people", id, &person);
+        }
+
+        let mut scope = ReplicationScope::full("ws-1");
+        scope.tables.push(TableScope {
+            table_name: "companies".to_string(),
+            seller_user_id: Some("u1".to_string()),
+            ..TableScope::default()
+        });
+        engine.set_replication_scope(&scope).await.unwrap();
+        let report = engine.sync_workspace("ws-1").await.unwrap();
+
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        let people: Vec<String> = sqlx::query_scalar("SELECT id FROM people ORDER BY id")
+            .fetch_all(&engine.sqlite_pool)
+            .await
+            .unwrap();
+        assert_eq!(people, vec!["p1", "p3"]);
+    }
+
+    #[tokio::test]
+    async fn an_invalid_stored_scope_fails_the_sync_instead_of_replicating_everything() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        let mut scope = ReplicationScope::full("ws-1");
+        scope.tables.push(TableScope {
+            table_name: "workspaces".to_string(),
+            seller_user_id: Some("u1".to_string()),
+            ..TableScope::default()
+        });
+        SyncSettingsStore::new(engine.sqlite_pool.clone())
+            .save_replication_scope(&scope)
+            .await
+            .unwrap();
+
+        let outcome = engine.run_table_sync("companies", "ws-1").await;
+
+        assert!(matches!(outcome, Err(SyncError::Configuration(_))));
+    }
+
+    async fn queued_change_id(engine: &SyncEngine) -> i64 {
+        sqlx::query_scalar("SELECT id FROM sync_queue WHERE record_id = 'c1'")
+            .fetch_one(&engine.sqlite_pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn an_edited_dead_letter_pushes_the_edit_over_the_row() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+        server.reject_record("companies", "c1", "name is reserved");
+        engine.sync_workspace("ws-1").await.unwrap();
+
+        let id = queued_change_id(&engine).await;
+        sqlx::query("UPDATE sync_queue SET status = 'DEAD_LETTER' WHERE id = ?")
+            .bind(id)
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+        server.accept_record("companies", "c1");
+        let dropped = engine.edit_queued_change(id, &serde_json::json!({ "name": "Acme Corp" })).await.unwrap();
+        engine.sync_workspace("ws-1").await.unwrap();
+
+        assert!(dropped.is_empty());
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Acme Corp"));
+        let pushed: Value = serde_json::from_str(&server.record("companies", "c1").unwrap()).unwrap();
+        assert_eq!(pushed["name"], "Acme Corp");
+        assert_eq!(pushed["workspace_id"], "ws-1");
+    }
+
+    #[tokio::test]
+    async fn discarding_without_a_synced_version_fetches_only_that_record() {
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server.clone());
+        server.reject_record("companies", "c1", "name is reserved");
+        engine.sync_workspace("ws-1").await.unwrap();
+        let watermark = engine.status_manager.get_watermark("companies").await.unwrap();
+
+        server.remote_upsert("companies", "c1", r#"{"id":"c1","workspace_id":"ws-1","name":"Server Acme"}"#);
+        engine.discard_queued_change(queued_change_id(&engine).await).await.unwrap();
+
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Server Acme"));
+        assert_eq!(engine.queue_manager.count_pending_changes().await.unwrap(), 0);
+        let kept = engine.status_manager.get_watermark("companies").await.unwrap();
+        assert_eq!(kept.map(|w| w.cursor), watermark.map(|w| w.cursor));
+    }
+
+    #[tokio::test]
+    async fn discarding_waits_for_the_running_sync() {
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        let id = queued_change_id(&engine).await;
+
+        let running = engine.control.begin().unwrap();
+        let discarding = engine.discard_queued_change(id);
+        let waited = tokio::time::timeout(std::time::Duration::from_millis(100), discarding).await;
+        assert!(waited.is_err());
+        assert!(engine.queue_manager.get_change(id).await.unwrap().is_some());
+
+        drop(running);
+        let error = engine.discard_queued_change(id).await.unwrap_err();
+        assert!(error.to_string().contains("No synced version"));
+    }
+
+    #[tokio::test]
+    async fn a_bundle_carries_offline_changes_to_a_device_in_the_same_workspace() {
+        std::env::set_var(bundle::BUNDLE_SIGNING_KEY_ENV, "test signing key");
+        let path = std::env::temp_dir().join(format!("sync-bundle-test-{}.zip", uuid::Uuid::new_v4()));
+        let offline = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        offline.export_sync_bundle("ws-1", &path).await.unwrap();
+
+        let server = Arc::new(InMemoryTransport::new());
+        let engine = SyncEngine::new(test_cache_pool().await, None, SyncConfig::default()).with_transport(server.clone());
+        sqlx::query("INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1')")
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+
+        let other_workspace = engine.import_sync_bundle("ws-other", &path).await;
+        assert!(matches!(other_workspace, Err(SyncError::Bundle(_))));
+        assert_eq!(engine.count_unsynced_changes().await.unwrap(), 0);
+
+        let report = engine.import_sync_bundle("ws-1", &path).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(report.changes_staged, 1);
+        assert!(server.record("companies", "c1").unwrap().contains("Local Acme"));
+    }
+
+    #[tokio::test]
+    async fn a_bundled_change_to_a_record_edited_here_is_held_as_a_conflict() {
+        std::env::set_var(bundle::BUNDLE_SIGNING_KEY_ENV, "test signing key");
+        let path = std::env::temp_dir().join(format!("sync-bundle-test-{}.zip", uuid::Uuid::new_v4()));
+        let offline = engine_with_local_edit(ConflictResolutionStrategy::Manual).await;
+        offline.export_sync_bundle("ws-1", &path).await.unwrap();
+
+        let server = Arc::new(InMemoryTransport::new());
+        server.set_online(false);
+        let engine = engine_with_local_edit(ConflictResolutionStrategy::Manual).await.with_transport(server);
+        sqlx::query("UPDATE companies SET name = 'Desk Acme' WHERE id = 'c1'")
+            .execute(&engine.sqlite_pool)
+            .await
+            .unwrap();
+
+        let report = engine.import_sync_bundle("ws-1", &path).await.unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(report.changes_staged, 1);
+        assert_eq!(report.conflicts, 1);
+        assert!(report.skipped_records.is_empty());
+        assert_eq!(company_name(&engine).await.as_deref(), Some("Desk Acme"));
+        let conflicts = engine.get_unresolved_conflicts().await.unwrap();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].remote_data.as_deref().unwrap().contains("Local Acme"));
+    }
+}
@@ -0,0 +1,307 @@
+// ====================================================================
+// SYNC PROGRESS EVENTS
+// ====================================================================
+//
+// This module streams sync progress to the frontend as Tauri events.
+// Every phase of a sync (started, table started, batch pushed, page
+// pulled, conflict found, table finished, completed) is emitted on
+// `SYNC_PROGRESS_EVENT` with a `SyncNotification` payload whose `data`
+// holds a `SyncProgress` snapshot, including ETA estimates.
+// ====================================================================
+
+use super::*;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::Emitter;
+
+/// Event name the frontend listens on
+pub const SYNC_PROGRESS_EVENT: &str = "sync://progress";
+
+/// Snapshot of a running sync, serialized into `SyncNotification.data`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncProgress {
+    pub workspace_id: Option<String>,
+    pub table_name: Option<String>,
+    pub tables_total: i32,
+    pub tables_completed: i32,
+    pub batches_total: i32,
+    pub batches_completed: i32,
+    pub pages_pulled: i32,
+    pub records_processed: i32,
+    pub conflicts_found: i32,
+    pub elapsed_ms: i64,
+    /// Estimated time left in the current table's push, from the batch rate
+    pub table_eta_ms: Option<i64>,
+    /// Estimated time left in the whole sync, from the table rate
+    pub eta_ms: Option<i64>,
+}
+
+#[derive(Default)]
+struct ProgressState {
+    workspace_id: Option<String>,
+    table_name: Option<String>,
+    started_at: Option<Instant>,
+    table_started_at: Option<Instant>,
+    tables_total: i32,
+    tables_completed: i32,
+    batches_total: i32,
+    batches_completed: i32,
+    pages_pulled: i32,
+    records_processed: i32,
+    conflicts_found: i32,
+}
+
+/// Emits progress events for the sync engine
+///
+/// Without an app handle (e.g. engines built outside Tauri) progress is
+/// still tracked but nothing is emitted.
+pub struct SyncEventEmitter {
+    app_handle: Option<tauri::AppHandle<tauri::Wry>>,
+    state: Mutex<ProgressState>,
+}
+
+impl SyncEventEmitter {
+    pub fn new(app_handle: Option<tauri::AppHandle<tauri::Wry>>) -> Self {
+        Self {
+            app_handle,
+            state: Mutex::new(ProgressState::default()),
+        }
+    }
+
+    pub fn sync_started(&self, workspace_id: &str, tables_total: i32) {
+        self.update(|state| {
+            *state = ProgressState {
+                workspace_id: Some(workspace_id.to_string()),
+                started_at: Some(Instant::now()),
+                tables_total,
+                ..ProgressState::default()
+            };
+        });
+
+        self.emit(
+            SyncNotificationType::SyncStarted,
+            "Sync started",
+            format!("Syncing {} tables", tables_total),
+        );
+    }
+
+    pub fn table_started(&self, table_name: &str) {
+        self.update(|state| {
+            if state.started_at.is_none() {
+                // Single-table sync without a workspace run around it
+                state.started_at = Some(Instant::now());
+                state.tables_total = state.tables_total.max(1);
+            }
+            state.table_name = Some(table_name.to_string());
+            state.table_started_at = Some(Instant::now());
+            state.batches_total = 0;
+            state.batches_completed = 0;
+            state.pages_pulled = 0;
+        });
+
+        self.emit(
+            SyncNotificationType::TableStarted,
+            "Syncing table",
+            format!("Syncing {}", table_name),
+        );
+    }
+
+    pub fn push_planned(&self, batches_total: i32) {
+        self.update(|state| state.batches_total = batches_total);
+    }
+
+    pub fn batch_pushed(&self, table_name: &str, records: i32) {
+        let (done, total) = self.update(|state| {
+            state.batches_completed += 1;
+            state.records_processed += records;
+            (state.batches_completed, state.batches_total)
+        });
+
+        self.emit(
+            SyncNotificationType::BatchPushed,
+            "Changes pushed",
+            format!("Pushed batch {} of {} for {}", done, total, table_name),
+        );
+    }
+
+    pub fn page_pulled(&self, table_name: &str, records: i32) {
+        let page = self.update(|state| {
+            state.pages_pulled += 1;
+            state.records_processed += records;
+            state.pages_pulled
+        });
+
+        self.emit(
+            SyncNotificationType::PagePulled,
+            "Changes pulled",
+            format!("Pulled page {} ({} records) for {}", page, records, table_name),
+        );
+    }
+
+    pub fn conflict_found(&self, table_name: &str, record_id: &str) {
+        self.update(|state| state.conflicts_found += 1);
+
+        self.emit(
+            SyncNotificationType::ConflictDetected,
+            "Conflict detected",
+            format!("{} {} was changed both locally and remotely", table_name, record_id),
+        );
+    }
+
+    pub fn table_finished(&self, table_name: &str, result: &SyncResult) {
+        self.update(|state| {
+            state.tables_completed += 1;
+            state.table_started_at = None;
+        });
+
+        let message = if result.errors.is_empty() {
+            format!("{} synced ({} records)", table_name, result.records_processed)
+        } else {
+            format!("{} synced with {} errors", table_name, result.errors.len())
+        };
+
+        self.emit(SyncNotificationType::TableCompleted, "Table synced", message);
+    }
+
+    pub fn sync_completed(&self, report: &SyncReport) {
+        let (type_, title) = if report.success {
+            (SyncNotificationType::SyncCompleted, "Sync completed")
+        } else {
+            (SyncNotificationType::SyncFailed, "Sync completed with errors")
+        };
+
+        self.emit(
+            type_,
+            title,
+            format!("{} records synced, {} conflicts", report.records_processed, report.conflicts_found),
+        );
+        self.reset();
+    }
+
+    pub fn sync_failed(&self, error: &SyncError) {
+        let type_ = match error {
+            SyncError::Cancelled => SyncNotificationType::SyncCancelled,
+            _ => SyncNotificationType::SyncFailed,
+        };
+
+        self.emit(type_, "Sync stopped", error.to_string());
+        self.reset();
+    }
+
+    /// Forget the progress of a finished run
+    pub fn reset(&self) {
+        self.update(|state| *state = ProgressState::default());
+    }
+
+    // ====================================================================
+    // PRIVATE HELPER METHODS
+    // ====================================================================
+
+    fn update<T>(&self, f: impl FnOnce(&mut ProgressState) -> T) -> T {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(&mut state)
+    }
+
+    fn snapshot(&self) -> SyncProgress {
+        self.update(|state| {
+            let elapsed_ms = state.started_at.map(|t| t.elapsed().as_millis() as i64).unwrap_or(0);
+
+            let table_eta_ms = match state.table_started_at {
+                Some(table_started_at) => estimate_remaining(
+                    table_started_at.elapsed().as_millis() as i64,
+                    state.batches_completed,
+                    state.batches_total,
+                ),
+                None => None,
+            };
+
+            SyncProgress {
+                workspace_id: state.workspace_id.clone(),
+                table_name: state.table_name.clone(),
+                tables_total: state.tables_total,
+                tables_completed: state.tables_completed,
+                batches_total: state.batches_total,
+                batches_completed: state.batches_completed,
+                pages_pulled: state.pages_pulled,
+                records_processed: state.records_processed,
+                conflicts_found: state.conflicts_found,
+                elapsed_ms,
+                table_eta_ms,
+                eta_ms: estimate_remaining(elapsed_ms, state.tables_completed, state.tables_total),
+            }
+        })
+    }
+
+    fn emit(&self, type_: SyncNotificationType, title: &str, message: String) {
+        let app_handle = match &self.app_handle {
+            Some(app_handle) => app_handle,
+            None => return,
+        };
+
+        let notification = SyncNotification {
+            id: SyncUtils::generate_ulid(),
+            type_,
+            title: title.to_string(),
+            message,
+            data: serde_json::to_string(&self.snapshot()).ok(),
+            created_at: SyncUtils::current_timestamp(),
+            read: false,
+        };
+
+        if let Err(e) = app_handle.emit(SYNC_PROGRESS_EVENT, &notification) {
+            println!("⚠️ [SYNC EVENTS] Failed to emit {:?} event: {}", notification.type_, e);
+        }
+    }
+}
+
+impl Default for SyncEventEmitter {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+/// Linear estimate of the time left from the rate so far
+fn estimate_remaining(elapsed_ms: i64, completed: i32, total: i32) -> Option<i64> {
+    if total > 0 && completed >= total {
+        return Some(0);
+    }
+    if completed <= 0 {
+        return None;
+    }
+
+    Some(elapsed_ms / completed as i64 * (total - completed) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eta_follows_the_rate_so_far() {
+        assert_eq!(estimate_remaining(1_000, 0, 4), None);
+        assert_eq!(estimate_remaining(1_000, 1, 4), Some(3_000));
+        assert_eq!(estimate_remaining(1_000, 4, 4), Some(0));
+    }
+
+    #[test]
+    fn progress_accumulates_across_tables_and_resets_after_the_run() {
+        let events = SyncEventEmitter::default();
+
+        events.sync_started("ws-1", 2);
+        events.table_started("companies");
+        events.push_planned(2);
+        events.batch_pushed("companies", 50);
+        events.page_pulled("companies", 10);
+        events.table_finished("companies", &SyncResult::new());
+        events.table_started("people");
+
+        let progress = events.snapshot();
+        assert_eq!(progress.table_name.as_deref(), Some("people"));
+        assert_eq!((progress.tables_completed, progress.tables_total), (1, 2));
+        assert_eq!(progress.records_processed, 60);
+        assert_eq!(progress.batches_total, 0);
+
+        events.sync_completed(&SyncReport::new());
+        assert_eq!(events.snapshot().workspace_id, None);
+    }
+}
@@ -0,0 +1,397 @@
+// ====================================================================
+// SYNC RUN HISTORY
+// ====================================================================
+//
+// This module persists every `sync_workspace` / `sync_table` run to the
+// `sync_runs` table and aggregates the history into throughput trends,
+// so a slow or failing sync can be diagnosed after the fact. History
+// older than `RUN_RETENTION_DAYS` is pruned as new runs are recorded.
+// ====================================================================
+
+use super::models::*;
+use super::SyncError;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Row, Sqlite, SqlitePool};
+
+/// How long run history is kept
+const RUN_RETENTION_DAYS: i32 = 90;
+
+/// Errors kept per run; the full count is in `error_count`
+const MAX_STORED_ERRORS: usize = 20;
+
+/// Filter shared by the history queries, bound by `bind_filter`
+const RUN_FILTER: &str = r#"
+    (? IS NULL OR julianday(started_at) >= julianday(?))
+    AND (? IS NULL OR julianday(started_at) <= julianday(?))
+    AND (? IS NULL OR workspace_id = ?)
+    AND (? IS NULL OR triggered_by = ?)
+"#;
+
+impl SyncTrigger {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncTrigger::Manual => "manual",
+            SyncTrigger::Background => "background",
+            SyncTrigger::Reconnect => "reconnect",
+            SyncTrigger::Import => "import",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "background" => SyncTrigger::Background,
+            "reconnect" => SyncTrigger::Reconnect,
+            "import" => SyncTrigger::Import,
+            _ => SyncTrigger::Manual,
+        }
+    }
+}
+
+impl SyncRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncRunStatus::Success => "success",
+            SyncRunStatus::Partial => "partial",
+            SyncRunStatus::Failed => "failed",
+            SyncRunStatus::Cancelled => "cancelled",
+        }
+    }
+
+    fn from_db(value: &str) -> Self {
+        match value {
+            "success" => SyncRunStatus::Success,
+            "partial" => SyncRunStatus::Partial,
+            "cancelled" => SyncRunStatus::Cancelled,
+            _ => SyncRunStatus::Failed,
+        }
+    }
+}
+
+impl SyncHistoryBucket {
+    /// `strftime` format truncating a timestamp to the bucket start
+    fn strftime_format(&self) -> &'static str {
+        match self {
+            SyncHistoryBucket::Hour => "%Y-%m-%dT%H:00:00Z",
+            SyncHistoryBucket::Day => "%Y-%m-%dT00:00:00Z",
+        }
+    }
+}
+
+impl SyncRun {
+    /// Describe a run that started at `started_at` and has just finished
+    pub fn finished(
+        triggered_by: SyncTrigger,
+        workspace_id: &str,
+        table_name: Option<&str>,
+        started_at: DateTime<Utc>,
+    ) -> Self {
+        let finished_at = Utc::now();
+
+        Self {
+            id: 0, // Will be set by database
+            triggered_by,
+            workspace_id: workspace_id.to_string(),
+            table_name: table_name.map(str::to_string),
+            status: SyncRunStatus::Success,
+            started_at: started_at.to_rfc3339(),
+            finished_at: finished_at.to_rfc3339(),
+            duration_ms: (finished_at - started_at).num_milliseconds(),
+            records_processed: 0,
+            records_created: 0,
+            records_updated: 0,
+            records_deleted: 0,
+            conflicts_found: 0,
+            request_bytes: 0,
+            request_bytes_uncompressed: 0,
+            response_bytes: 0,
+            response_bytes_uncompressed: 0,
+            error_count: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn apply_report(&mut self, report: &SyncReport) {
+        self.records_processed = report.records_processed;
+        self.records_created = report.records_created;
+        self.records_updated = report.records_updated;
+        self.records_deleted = report.records_deleted;
+        self.conflicts_found = report.conflicts_found;
+        self.set_errors(&report.errors);
+    }
+
+    pub fn apply_result(&mut self, result: &SyncResult) {
+        self.records_processed = result.records_processed;
+        self.records_created = result.records_created;
+        self.records_updated = result.records_updated;
+        self.records_deleted = result.records_deleted;
+        self.conflicts_found = result.conflicts_found;
+        self.set_errors(&result.errors);
+    }
+
+    /// Record a run that ended with an error instead of a report
+    pub fn apply_error(&mut self, error: &SyncError) {
+        self.set_errors(std::slice::from_ref(&error.to_string()));
+        self.status = match error {
+            SyncError::Cancelled => SyncRunStatus::Cancelled,
+            _ => SyncRunStatus::Failed,
+        };
+    }
+
+    pub fn records_per_second(&self) -> f64 {
+        if self.duration_ms > 0 {
+            self.records_processed as f64 * 1000.0 / self.duration_ms as f64
+        } else {
+            0.0
+        }
+    }
+
+    fn set_errors(&mut self, errors: &[String]) {
+        self.error_count = errors.len() as i32;
+        self.errors = errors.iter().take(MAX_STORED_ERRORS).cloned().collect();
+        if !errors.is_empty() {
+            self.status = SyncRunStatus::Partial;
+        }
+    }
+}
+
+pub struct SyncHistoryStore {
+    pool: SqlitePool,
+}
+
+impl SyncHistoryStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Persist a finished run and prune history past the retention window
+    pub async fn record_run(&self, run: &SyncRun) -> Result<i64, sqlx::Error> {
+        let errors = serde_json::to_string(&run.errors)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize sync run errors: {}", e)))?;
+
+        let query = r#"
+            INSERT INTO sync_runs (
+                triggered_by, workspace_id, table_name, status, started_at, finished_at, duration_ms,
+                records_processed, records_created, records_updated, records_deleted, conflicts_found,
+                request_bytes, request_bytes_uncompressed, response_bytes, response_bytes_uncompressed,
+                error_count, errors
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(query)
+            .bind(run.triggered_by.as_str())
+            .bind(&run.workspace_id)
+            .bind(&run.table_name)
+            .bind(run.status.as_str())
+            .bind(&run.started_at)
+            .bind(&run.finished_at)
+            .bind(run.duration_ms)
+            .bind(run.records_processed)
+            .bind(run.records_created)
+            .bind(run.records_updated)
+            .bind(run.records_deleted)
+            .bind(run.conflicts_found)
+            .bind(run.request_bytes)
+            .bind(run.request_bytes_uncompressed)
+            .bind(run.response_bytes)
+            .bind(run.response_bytes_uncompressed)
+            .bind(run.error_count)
+            .bind(errors)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query("DELETE FROM sync_runs WHERE julianday(started_at) < julianday('now', ?)")
+            .bind(format!("-{} days", RUN_RETENTION_DAYS))
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+
+        Ok(result.last_insert_rowid())
+    }
+
+    /// Get the most recent run
+    pub async fn latest_run(&self) -> Result<Option<SyncRun>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM sync_runs ORDER BY id DESC LIMIT 1")
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.as_ref().map(row_to_run))
+    }
+
+    /// Get matching runs with their totals and per-bucket throughput
+    pub async fn get_history(&self, filter: &SyncHistoryQuery) -> Result<SyncHistory, sqlx::Error> {
+        let runs_query = format!("SELECT * FROM sync_runs WHERE {} ORDER BY id DESC LIMIT ?", RUN_FILTER);
+        let runs = bind_filter(sqlx::query(&runs_query), filter)
+            .bind(filter.limit)
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(row_to_run)
+            .collect();
+
+        let totals_query = format!(
+            r#"
+            SELECT
+                COUNT(*) AS runs,
+                COALESCE(SUM(CASE WHEN status IN ('failed', 'partial') THEN 1 ELSE 0 END), 0) AS failed_runs,
+                COALESCE(AVG(duration_ms), 0.0) AS avg_duration_ms,
+                COALESCE(SUM(records_processed), 0) AS records_processed,
+                COALESCE(SUM(duration_ms), 0) AS total_duration_ms,
+                COALESCE(SUM(request_bytes + response_bytes), 0) AS bytes_transferred
+            FROM sync_runs
+            WHERE {}
+            "#,
+            RUN_FILTER
+        );
+        let totals = bind_filter(sqlx::query(&totals_query), filter)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let trends_query = format!(
+            r#"
+            SELECT
+                strftime(?, started_at) AS bucket_start,
+                COUNT(*) AS runs,
+                SUM(CASE WHEN status IN ('failed', 'partial') THEN 1 ELSE 0 END) AS failed_runs,
+                AVG(duration_ms) AS avg_duration_ms,
+                SUM(records_processed) AS records_processed,
+                SUM(duration_ms) AS total_duration_ms,
+                SUM(request_bytes + response_bytes) AS bytes_transferred
+            FROM sync_runs
+            WHERE {}
+            GROUP BY bucket_start
+            ORDER BY bucket_start
+            "#,
+            RUN_FILTER
+        );
+        let trends = bind_filter(sqlx::query(&trends_query).bind(filter.bucket.strftime_format()), filter)
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| SyncThroughputPoint {
+                bucket_start: row.get("bucket_start"),
+                runs: row.get("runs"),
+                failed_runs: row.get("failed_runs"),
+                records_processed: row.get("records_processed"),
+                avg_duration_ms: row.get("avg_duration_ms"),
+                records_per_second: throughput(row.get("records_processed"), row.get("total_duration_ms")),
+                bytes_transferred: row.get("bytes_transferred"),
+            })
+            .collect();
+
+        Ok(SyncHistory {
+            runs,
+            total_runs: totals.get("runs"),
+            failed_runs: totals.get("failed_runs"),
+            avg_duration_ms: totals.get("avg_duration_ms"),
+            records_per_second: throughput(totals.get("records_processed"), totals.get("total_duration_ms")),
+            bytes_transferred: totals.get("bytes_transferred"),
+            trends,
+        })
+    }
+}
+
+// ====================================================================
+// PRIVATE HELPERS
+// ====================================================================
+
+/// Bind the `RUN_FILTER` parameters; each one is checked for NULL first
+fn bind_filter<'q>(
+    query: sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>,
+    filter: &SyncHistoryQuery,
+) -> sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>> {
+    let triggered_by = filter.triggered_by.map(|trigger| trigger.as_str());
+
+    query
+        .bind(filter.from.clone())
+        .bind(filter.from.clone())
+        .bind(filter.to.clone())
+        .bind(filter.to.clone())
+        .bind(filter.workspace_id.clone())
+        .bind(filter.workspace_id.clone())
+        .bind(triggered_by)
+        .bind(triggered_by)
+}
+
+fn throughput(records: i64, duration_ms: i64) -> f64 {
+    if duration_ms > 0 {
+        records as f64 * 1000.0 / duration_ms as f64
+    } else {
+        0.0
+    }
+}
+
+fn row_to_run(row: &SqliteRow) -> SyncRun {
+    let errors: Option<String> = row.get("errors");
+
+    SyncRun {
+        id: row.get("id"),
+        triggered_by: SyncTrigger::from_db(&row.get::<String, _>("triggered_by")),
+        workspace_id: row.get("workspace_id"),
+        table_name: row.get("table_name"),
+        status: SyncRunStatus::from_db(&row.get::<String, _>("status")),
+        started_at: row.get("started_at"),
+        finished_at: row.get("finished_at"),
+        duration_ms: row.get("duration_ms"),
+        records_processed: row.get("records_processed"),
+        records_created: row.get("records_created"),
+        records_updated: row.get("records_updated"),
+        records_deleted: row.get("records_deleted"),
+        conflicts_found: row.get("conflicts_found"),
+        request_bytes: row.get("request_bytes"),
+        request_bytes_uncompressed: row.get("request_bytes_uncompressed"),
+        response_bytes: row.get("response_bytes"),
+        response_bytes_uncompressed: row.get("response_bytes_uncompressed"),
+        error_count: row.get("error_count"),
+        errors: errors
+            .and_then(|errors| serde_json::from_str(&errors).ok())
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+
+    fn run(triggered_by: SyncTrigger, records_processed: i32, duration_ms: i64) -> SyncRun {
+        let mut run = SyncRun::finished(triggered_by, "ws-1", None, Utc::now());
+        run.records_processed = records_processed;
+        run.duration_ms = duration_ms;
+        run
+    }
+
+    #[tokio::test]
+    async fn runs_are_listed_newest_first_with_their_totals() {
+        let store = SyncHistoryStore::new(test_cache_pool().await);
+        store.record_run(&run(SyncTrigger::Manual, 100, 1_000)).await.unwrap();
+        let mut failed = run(SyncTrigger::Background, 0, 500);
+        failed.apply_error(&SyncError::Network("offline".to_string()));
+        store.record_run(&failed).await.unwrap();
+
+        let history = store.get_history(&SyncHistoryQuery::default()).await.unwrap();
+
+        assert_eq!(history.total_runs, 2);
+        assert_eq!(history.failed_runs, 1);
+        assert_eq!(history.runs[0].status, SyncRunStatus::Failed);
+        assert_eq!(history.runs[0].errors, vec!["Network error: offline".to_string()]);
+        assert!((history.records_per_second - 100.0 / 1.5).abs() < 0.01);
+        assert_eq!(history.trends.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn history_can_be_filtered_by_trigger() {
+        let store = SyncHistoryStore::new(test_cache_pool().await);
+        store.record_run(&run(SyncTrigger::Manual, 10, 100)).await.unwrap();
+        store.record_run(&run(SyncTrigger::Background, 20, 100)).await.unwrap();
+
+        let query = SyncHistoryQuery { triggered_by: Some(SyncTrigger::Background), ..SyncHistoryQuery::default() };
+        let history = store.get_history(&query).await.unwrap();
+
+        assert_eq!(history.total_runs, 1);
+        assert_eq!(history.runs[0].records_processed, 20);
+    }
+}
@@ -0,0 +1,435 @@
+// ====================================================================
+// SYNC RECORD MAPPING
+// ====================================================================
+//
+// This module maps synced records between their JSON wire form and the
+// typed columns of the local tables. Column lists come from SQLite
+// schema introspection, so every table from migration 003 onward maps
+// without per-table code:
+// - Known fields are written to their columns
+// - Unknown fields are preserved in `custom_fields` under `_unmapped`
+//   and flattened back out when the row is read for a push
+// - Sync metadata columns are only written when the table has them
+// ====================================================================
+
+use super::clock::CLOCK_FIELD;
+use serde_json::Value;
+use sqlx::sqlite::{SqliteArguments, SqliteRow};
+use sqlx::{Column, Row, Sqlite, SqliteConnection, ValueRef};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Sync metadata columns and fields that are not part of a record's content
+pub const SYNC_METADATA_COLUMNS: &[&str] = &["last_synced_at", "sync_version", "is_dirty", CLOCK_FIELD];
+
+/// Column holding free-form fields, where unknown remote fields are kept
+pub const CUSTOM_FIELDS_COLUMN: &str = "custom_fields";
+
+/// Key inside `custom_fields` for remote fields that have no local column
+pub const UNMAPPED_FIELDS_KEY: &str = "_unmapped";
+
+/// How a write changes the row's `sync_version`
+#[derive(Debug, Clone, Copy)]
+pub enum VersionUpdate {
+    /// Take the version the server sent
+    Set(i32),
+    /// Bump the local version
+    Increment,
+    /// Leave it as it is
+    Keep,
+}
+
+/// Local copy of a record with its sync state
+pub struct LocalRecord {
+    pub data: Value,
+    pub is_dirty: bool,
+    pub sync_version: i32,
+}
+
+#[derive(Debug, Clone)]
+struct ColumnInfo {
+    name: String,
+    declared_type: String,
+}
+
+/// Columns of a local table, as reported by `PRAGMA table_info`
+#[derive(Debug, Clone)]
+pub struct TableSchema {
+    primary_key: String,
+    columns: Vec<ColumnInfo>,
+}
+
+impl TableSchema {
+    pub fn primary_key(&self) -> &str {
+        &self.primary_key
+    }
+
+    pub fn has_column(&self, name: &str) -> bool {
+        self.columns.iter().any(|column| column.name == name)
+    }
+
+    fn declared_type(&self, name: &str) -> Option<&str> {
+        self.columns
+            .iter()
+            .find(|column| column.name == name)
+            .map(|column| column.declared_type.as_str())
+    }
+
+    /// Content columns, i.e. everything but the sync metadata
+    fn content_columns(&self) -> impl Iterator<Item = &str> {
+        self.columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .filter(|name| !SYNC_METADATA_COLUMNS.contains(name))
+    }
+}
+
+/// Maps records onto table columns, caching each table's schema
+pub struct RecordMapper {
+    schemas: RwLock<HashMap<String, Arc<TableSchema>>>,
+}
+
+impl RecordMapper {
+    pub fn new() -> Self {
+        Self {
+            schemas: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Get a table's columns, introspecting the schema on first use
+    pub async fn schema(&self, conn: &mut SqliteConnection, table_name: &str) -> Result<Arc<TableSchema>, sqlx::Error> {
+        if let Some(schema) = self.schemas.read().unwrap_or_else(|p| p.into_inner()).get(table_name) {
+            return Ok(schema.clone());
+        }
+
+        let rows = sqlx::query(&format!("PRAGMA table_info({})", table_name))
+            .fetch_all(&mut *conn)
+            .await?;
+
+        if rows.is_empty() {
+            return Err(sqlx::Error::Protocol(format!("Table {} does not exist", table_name)));
+        }
+
+        let primary_key = rows
+            .iter()
+            .find(|row| row.get::<i64, _>("pk") == 1)
+            .map(|row| row.get::<String, _>("name"))
+            .unwrap_or_else(|| "id".to_string());
+
+        let schema = Arc::new(TableSchema {
+            primary_key,
+            columns: rows
+                .iter()
+                .map(|row| ColumnInfo {
+                    name: row.get::<String, _>("name"),
+                    declared_type: row.get::<String, _>("type").to_uppercase(),
+                })
+                .collect(),
+        });
+
+        self.schemas
+            .write()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(table_name.to_string(), schema.clone());
+
+        Ok(schema)
+    }
+
+    /// Write a JSON record into the table's columns, inserting or updating the row
+    ///
+    /// Fields missing from `data` keep their current value on update. Returns
+    /// the fields that were not stored because the table has no column for
+    /// them and no `custom_fields` column to keep them in.
+    pub async fn write_record(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        id: &str,
+        data: &str,
+        version: VersionUpdate,
+        is_dirty: bool,
+    ) -> Result<Vec<String>, sqlx::Error> {
+        let schema = self.schema(&mut *conn, table_name).await?;
+        let primary_key = schema.primary_key();
+        let record: serde_json::Map<String, Value> = serde_json::from_str(data)
+            .map_err(|e| sqlx::Error::Protocol(format!("Invalid {} record {}: {}", table_name, id, e)))?;
+
+        let mut columns: Vec<(String, Value)> = vec![(primary_key.to_string(), Value::String(id.to_string()))];
+        let (mapped, dropped_fields) = map_to_columns(&schema, primary_key, record);
+        columns.extend(mapped);
+
+        let mut updates: Vec<String> = columns
+            .iter()
+            .skip(1)
+            .map(|(name, _)| format!("{name} = excluded.{name}"))
+            .collect();
+
+        // Sync metadata, for the tables that track it
+        if schema.has_column("sync_version") {
+            match version {
+                VersionUpdate::Set(sync_version) => {
+                    columns.push(("sync_version".to_string(), Value::from(sync_version)));
+                    updates.push("sync_version = excluded.sync_version".to_string());
+                }
+                VersionUpdate::Increment => {
+                    columns.push(("sync_version".to_string(), Value::from(1)));
+                    updates.push("sync_version = sync_version + 1".to_string());
+                }
+                VersionUpdate::Keep => {}
+            }
+        }
+        if schema.has_column("last_synced_at") {
+            columns.push(("last_synced_at".to_string(), Value::String(chrono::Utc::now().to_rfc3339())));
+            updates.push("last_synced_at = excluded.last_synced_at".to_string());
+        }
+        if schema.has_column("is_dirty") {
+            columns.push(("is_dirty".to_string(), Value::Bool(is_dirty)));
+            updates.push("is_dirty = excluded.is_dirty".to_string());
+        }
+
+        let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+        let placeholders = vec!["?"; columns.len()].join(", ");
+        let on_conflict = if updates.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            format!("DO UPDATE SET {}", updates.join(", "))
+        };
+
+        let query = format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT({}) {}",
+            table_name,
+            names.join(", "),
+            placeholders,
+            primary_key,
+            on_conflict
+        );
+
+        let mut statement = sqlx::query(&query);
+        for (_, value) in &columns {
+            statement = bind_json(statement, value);
+        }
+        statement.execute(&mut *conn).await?;
+
+        if !dropped_fields.is_empty() {
+            println!("⚠️ [SYNC MAPPING] {} {} has no column for {:?}, fields dropped", table_name, id, dropped_fields);
+        }
+
+        Ok(dropped_fields)
+    }
+
+    /// Read a row back into the JSON form used for conflicts and pushes
+    pub async fn read_record(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        id: &str,
+    ) -> Result<Option<LocalRecord>, sqlx::Error> {
+        let schema = self.schema(&mut *conn, table_name).await?;
+        let query = format!("SELECT * FROM {} WHERE {} = ?", table_name, schema.primary_key());
+
+        let row = sqlx::query(&query)
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(row.map(|row| LocalRecord {
+            is_dirty: row.try_get::<bool, _>("is_dirty").unwrap_or(false),
+            sync_version: row.try_get::<i32, _>("sync_version").unwrap_or(0),
+            data: row_to_record(&schema, &row),
+        }))
+    }
+}
+
+impl Default for RecordMapper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ====================================================================
+// MAPPING HELPERS
+// ====================================================================
+
+/// Split a remote record into column values, folding unknown fields into `custom_fields`
+///
+/// Also returns the unknown fields that had nowhere to go.
+fn map_to_columns(
+    schema: &TableSchema,
+    primary_key: &str,
+    record: serde_json::Map<String, Value>,
+) -> (Vec<(String, Value)>, Vec<String>) {
+    let mut columns = Vec::new();
+    let mut custom_fields: Option<Value> = None;
+    let mut unmapped = serde_json::Map::new();
+
+    for (key, value) in record {
+        if key == primary_key || SYNC_METADATA_COLUMNS.contains(&key.as_str()) {
+            continue;
+        }
+
+        if key == CUSTOM_FIELDS_COLUMN && schema.has_column(CUSTOM_FIELDS_COLUMN) {
+            custom_fields = Some(parse_json_text(value));
+        } else if schema.has_column(&key) {
+            columns.push((key, value));
+        } else {
+            unmapped.insert(key, value);
+        }
+    }
+
+    let mut dropped = Vec::new();
+    if !unmapped.is_empty() {
+        if schema.has_column(CUSTOM_FIELDS_COLUMN) {
+            let mut fields = match custom_fields.take() {
+                Some(Value::Object(fields)) => fields,
+                _ => serde_json::Map::new(),
+            };
+            fields.insert(UNMAPPED_FIELDS_KEY.to_string(), Value::Object(unmapped));
+            custom_fields = Some(Value::Object(fields));
+        } else {
+            dropped = unmapped.into_iter().map(|(key, _)| key).collect();
+        }
+    }
+
+    if let Some(fields) = custom_fields {
+        columns.push((CUSTOM_FIELDS_COLUMN.to_string(), fields));
+    }
+
+    (columns, dropped)
+}
+
+/// Convert a row into a record, restoring the fields kept in `custom_fields`
+fn row_to_record(schema: &TableSchema, row: &SqliteRow) -> Value {
+    let mut object = serde_json::Map::new();
+    let mut unmapped = serde_json::Map::new();
+
+    for (index, column) in row.columns().iter().enumerate() {
+        let name = column.name();
+        if SYNC_METADATA_COLUMNS.contains(&name) {
+            continue;
+        }
+
+        let declared_type = schema.declared_type(name).unwrap_or("");
+        let value = match row.try_get_raw(index) {
+            Ok(raw) if !raw.is_null() => {
+                if declared_type.contains("BOOL") {
+                    row.try_get::<i64, _>(index).map(|v| Value::Bool(v != 0)).unwrap_or(Value::Null)
+                } else if let Ok(v) = row.try_get::<i64, _>(index) {
+                    Value::from(v)
+                } else if let Ok(v) = row.try_get::<f64, _>(index) {
+                    Value::from(v)
+                } else {
+                    row.try_get::<String, _>(index).map(|v| parse_json_text(Value::String(v))).unwrap_or(Value::Null)
+                }
+            }
+            _ => Value::Null,
+        };
+
+        let value = match value {
+            Value::Object(mut fields) if name == CUSTOM_FIELDS_COLUMN => {
+                if let Some(Value::Object(extra)) = fields.remove(UNMAPPED_FIELDS_KEY) {
+                    unmapped = extra;
+                }
+                Value::Object(fields)
+            }
+            value => value,
+        };
+
+        object.insert(name.to_string(), value);
+    }
+
+    // Real columns win over stale copies of the same field
+    for (key, value) in unmapped {
+        if !schema.content_columns().any(|column| column == key) {
+            object.insert(key, value);
+        }
+    }
+
+    Value::Object(object)
+}
+
+/// JSON arrays and objects are stored as text; give them back their structure
+fn parse_json_text(value: Value) -> Value {
+    match value {
+        Value::String(text) if text.starts_with('{') || text.starts_with('[') => {
+            serde_json::from_str(&text).unwrap_or(Value::String(text))
+        }
+        value => value,
+    }
+}
+
+fn bind_json<'q>(
+    statement: sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>>,
+    value: &Value,
+) -> sqlx::query::Query<'q, Sqlite, SqliteArguments<'q>> {
+    match value {
+        Value::Null => statement.bind(None::<String>),
+        Value::Bool(b) => statement.bind(*b as i64),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => statement.bind(i),
+            None => statement.bind(n.as_f64()),
+        },
+        Value::String(s) => statement.bind(s.clone()),
+        other => statement.bind(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+    use sqlx::SqlitePool;
+
+    async fn write(pool: &SqlitePool, table_name: &str, id: &str, data: Value) -> Vec<String> {
+        let mut conn = pool.acquire().await.unwrap();
+        RecordMapper::new()
+            .write_record(&mut conn, table_name, id, &data.to_string(), VersionUpdate::Set(3), false)
+            .await
+            .unwrap()
+    }
+
+    async fn read(pool: &SqlitePool, table_name: &str, id: &str) -> LocalRecord {
+        let mut conn = pool.acquire().await.unwrap();
+        RecordMapper::new().read_record(&mut conn, table_name, id).await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn unknown_fields_are_kept_in_custom_fields_and_read_back() {
+        let pool = test_cache_pool().await;
+        write(&pool, "workspaces", "ws-1", serde_json::json!({ "name": "Workspace", "slug": "ws-1" })).await;
+
+        let dropped = write(
+            &pool,
+            "companies",
+            "c1",
+            serde_json::json!({ "workspace_id": "ws-1", "name": "Acme", "employee_band": "50-200" }),
+        )
+        .await;
+        let name: String = sqlx::query_scalar("SELECT name FROM companies WHERE id = 'c1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let local = read(&pool, "companies", "c1").await;
+
+        assert!(dropped.is_empty());
+        assert_eq!(name, "Acme");
+        assert_eq!(local.sync_version, 3);
+        assert_eq!(local.data["employee_band"], "50-200");
+        assert!(local.data["custom_fields"].get(UNMAPPED_FIELDS_KEY).is_none());
+    }
+
+    #[tokio::test]
+    async fn fields_with_nowhere_to_go_are_reported_as_dropped() {
+        let pool = test_cache_pool().await;
+
+        let dropped = write(
+            &pool,
+            "workspaces",
+            "ws-1",
+            serde_json::json!({ "name": "Workspace", "slug": "ws-1", "billing_plan": "team" }),
+        )
+        .await;
+
+        assert_eq!(dropped, vec!["billing_plan".to_string()]);
+        assert_eq!(read(&pool, "workspaces", "ws-1").await.data["name"], "Workspace");
+    }
+}
@@ -15,84 +15,74 @@
 // - ConflictResolver: Handles merge conflicts
 // - SyncQueue: Manages offline changes
 // - SyncStatus: Tracks sync state
+// - BackgroundSyncScheduler: Runs periodic syncs on the configured interval
+// - SharedSyncEngine: The single engine instance held in Tauri state
+// - SyncTableRegistry: Declares syncable tables and their dependency order
+// - SyncEventEmitter: Streams sync progress to the frontend
+// - compression: Gzip/zstd sync payloads and transfer size tracking
+// - SyncTransport: HTTP, direct-Postgres and in-memory remotes
+// - RecordMapper: Maps synced JSON records onto typed table columns
+// - capture: Trigger-based capture of local writes into the queue
+// - clock: Hybrid logical clocks that order writes across devices
+// - SyncHistoryStore: Persists sync runs and aggregates throughput trends
+// - ConnectivityMonitor: Tracks online/offline and flushes the queue on reconnect
+// - scopes: Per-workspace replication scopes for pulls and local eviction
+// - tombstones: Tombstones that keep deletes from resurrecting, and their purge
+// - bundle: Signed offline change bundles for carrying the queue between devices
 // ====================================================================
 
 pub mod engine;
 pub mod conflict_resolver;
+pub mod diff;
+pub mod registry;
 pub mod queue;
 pub mod models;
 pub mod status;
+pub mod settings;
+pub mod scheduler;
+pub mod shared;
+pub mod events;
+pub mod compression;
+pub mod transport;
+pub mod mapping;
+pub mod capture;
+pub mod clock;
+pub mod history;
+pub mod connectivity;
+pub mod scopes;
+pub mod tombstones;
+pub mod bundle;
 pub mod commands;
 
 // Re-export main types
-pub use engine::SyncEngine;
+pub use engine::{SyncControl, SyncEngine};
 pub use conflict_resolver::ConflictResolver;
-pub use queue::SyncQueue;
+pub use queue::{RetryPolicy, SyncQueue, SyncQueueEntry, SyncQueueFilter};
 pub use status::SyncStatusManager;
+pub use settings::SyncSettingsStore;
+pub use history::SyncHistoryStore;
+pub use scheduler::BackgroundSyncScheduler;
+pub use connectivity::{ConnectivityMonitor, ConnectivityState, ConnectivityStatus, CONNECTIVITY_EVENT};
+pub use shared::SharedSyncEngine;
+pub use events::{SyncEventEmitter, SyncProgress, SYNC_PROGRESS_EVENT};
+pub use compression::{decompress_body, TransferStats, ACCEPT_ENCODING};
+pub use mapping::{RecordMapper, TableSchema, VersionUpdate};
+pub use capture::{begin_sync_write, commit_sync_write, install_capture_triggers};
+pub use clock::{HybridTimestamp, CLOCK_FIELD};
+pub use scopes::{evict_out_of_scope, ScopeFilter, MAIN_SELLER_COLUMN};
+pub use tombstones::{Tombstone, TOMBSTONE_RETENTION_DAYS};
+pub use bundle::{BundleImportReport, BundleManifest, SyncBundle, BUNDLE_SIGNING_KEY_ENV};
+pub use transport::{HttpTransport, InMemoryTransport, PulledPage, SyncTransport};
+#[cfg(feature = "direct-postgres-sync")]
+pub use transport::PostgresTransport;
+pub use registry::{SyncDirection, SyncTable, SyncTableRegistry};
 pub use models::*;
 pub use commands::*;
 
-use crate::database::models::*;
 use sqlx::{SqlitePool, PgPool};
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
-// ====================================================================
-// SYNC ENGINE CONFIGURATION
-// ====================================================================
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SyncConfig {
-    pub remote_api_base: String,
-    pub sync_interval_minutes: u32,
-    pub max_retry_attempts: u32,
-    pub batch_size: u32,
-    pub conflict_resolution_strategy: ConflictResolutionStrategy,
-    pub enable_background_sync: bool,
-    pub enable_auto_retry: bool,
-}
-
-impl Default for SyncConfig {
-    fn default() -> Self {
-        Self {
-            remote_api_base: "https://adrata.vercel.app/api/v1".to_string(),
-            sync_interval_minutes: 5,
-            max_retry_attempts: 3,
-            batch_size: 100,
-            conflict_resolution_strategy: ConflictResolutionStrategy::LastWriteWins,
-            enable_background_sync: true,
-            enable_auto_retry: true,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum ConflictResolutionStrategy {
-    LastWriteWins,
-    LocalWins,
-    RemoteWins,
-    Manual,
-    Merge,
-}
-
-// ====================================================================
-// SYNC OPERATION TYPES
-// ====================================================================
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SyncOperation {
-    Insert { table: String, data: String },
-    Update { table: String, id: String, data: String },
-    Delete { table: String, id: String },
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum SyncResult {
-    Success { records_processed: i32 },
-    PartialSuccess { records_processed: i32, errors: Vec<String> },
-    Failed { errors: Vec<String> },
-}
-
 // ====================================================================
 // SYNC ENGINE TRAIT
 // ====================================================================
@@ -135,6 +125,15 @@ pub enum SyncError {
     
     #[error("Sync queue error: {0}")]
     Queue(String),
+    
+    #[error("Sync bundle error: {0}")]
+    Bundle(String),
+    
+    #[error("A sync is already running")]
+    AlreadyRunning,
+    
+    #[error("Sync cancelled")]
+    Cancelled,
 }
 
 // ====================================================================
@@ -189,3 +188,25 @@ impl SyncUtils {
         is_dirty || local_version < remote_version
     }
 }
+
+// ====================================================================
+// TEST SUPPORT
+// ====================================================================
+
+/// A fresh in-memory cache.db with every migration applied
+#[cfg(test)]
+pub(crate) async fn test_cache_pool() -> SqlitePool {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+
+    crate::database::migrations::run_migrations(&pool).await.unwrap();
+
+    pool
+}
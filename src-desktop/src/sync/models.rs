@@ -13,7 +13,7 @@ use chrono::{DateTime, Utc};
 // SYNC QUEUE MODELS
 // ====================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SyncQueueItem {
     pub id: i64,
     pub table_name: String,
@@ -24,29 +24,34 @@ pub struct SyncQueueItem {
     pub synced_at: Option<String>,
     pub error_message: Option<String>,
     pub retry_count: i32,
+    pub next_retry_at: Option<String>,
     pub status: SyncQueueStatus,
+    pub client_mutation_id: String, // Idempotency key sent with the change
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum SyncOperation {
     Insert,
     Update,
     Delete,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SyncQueueStatus {
     Pending,
     InProgress,
     Completed,
     Failed,
+    DeadLetter, // Exhausted its retry attempts, waits for the user
 }
 
 // ====================================================================
 // SYNC STATUS MODELS
 // ====================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SyncStatus {
     pub id: i64,
     pub table_name: String,
@@ -71,7 +76,7 @@ pub struct SyncStatusResponse {
     pub conflicts: i32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct TableSyncStatus {
     pub table_name: String,
     pub last_sync: Option<String>,
@@ -82,7 +87,8 @@ pub struct TableSyncStatus {
     pub status: SyncTableStatus,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SyncTableStatus {
     Synced,
     Pending,
@@ -94,7 +100,7 @@ pub enum SyncTableStatus {
 // SYNC CONFLICT MODELS
 // ====================================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SyncConflict {
     pub id: i64,
     pub table_name: String,
@@ -103,6 +109,10 @@ pub struct SyncConflict {
     pub remote_version: i32,
     pub local_data: Option<String>, // JSON object
     pub remote_data: Option<String>, // JSON object
+    pub base_data: Option<String>, // JSON object (last synced version)
+    pub field_conflicts: Option<String>, // JSON array of FieldConflict
+    pub local_clock: Option<String>, // Hybrid timestamp of the local write
+    pub remote_clock: Option<String>, // Hybrid timestamp of the remote write
     pub resolution: Option<ConflictResolution>,
     pub resolved_data: Option<String>, // JSON object
     pub created_at: String,
@@ -110,7 +120,8 @@ pub struct SyncConflict {
     pub resolved_by: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, sqlx::Type)]
+#[sqlx(type_name = "TEXT")]
 pub enum ConflictResolution {
     LocalWins,
     RemoteWins,
@@ -139,6 +150,9 @@ pub struct SyncReport {
     pub records_deleted: i32,
     pub conflicts_found: i32,
     pub errors: Vec<String>,
+    /// Pulled fields with no local column to store them in, as `table.field`
+    #[serde(default)]
+    pub dropped_fields: Vec<String>,
     pub duration_ms: i64,
     pub timestamp: String,
 }
@@ -150,7 +164,11 @@ pub struct SyncResult {
     pub records_created: i32,
     pub records_updated: i32,
     pub records_deleted: i32,
+    pub conflicts_found: i32,
     pub errors: Vec<String>,
+    /// Pulled fields with no local column to store them in, as `table.field`
+    #[serde(default)]
+    pub dropped_fields: Vec<String>,
     pub duration_ms: i64,
 }
 
@@ -159,6 +177,7 @@ pub struct SyncResult {
 // ====================================================================
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SyncConfig {
     pub remote_api_base: String,
     pub sync_interval_minutes: u32,
@@ -168,6 +187,8 @@ pub struct SyncConfig {
     pub enable_background_sync: bool,
     pub enable_auto_retry: bool,
     pub enable_compression: bool,
+    /// Codec used for push bodies when `enable_compression` is set
+    pub compression_codec: CompressionCodec,
     pub timeout_seconds: u32,
 }
 
@@ -182,6 +203,7 @@ impl Default for SyncConfig {
             enable_background_sync: true,
             enable_auto_retry: true,
             enable_compression: true,
+            compression_codec: CompressionCodec::Gzip,
             timeout_seconds: 30,
         }
     }
@@ -196,6 +218,40 @@ pub enum ConflictResolutionStrategy {
     Merge,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum CompressionCodec {
+    Gzip,
+    Zstd,
+}
+
+// ====================================================================
+// REPLICATION SCOPE MODELS
+// ====================================================================
+
+/// Which rows of a workspace are replicated to this device
+///
+/// Tables without an entry are replicated in full.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ReplicationScope {
+    pub workspace_id: String,
+    #[serde(default)]
+    pub tables: Vec<TableScope>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct TableScope {
+    pub table_name: String,
+    /// Don't pull the table at all; local changes are still pushed
+    pub skip_pull: bool,
+    /// Only rows whose `age_column` falls within this many days
+    pub max_age_days: Option<u32>,
+    /// Column the age limit applies to, `updated_at` when not set
+    pub age_column: Option<String>,
+    /// Only rows this user is the main seller (or a co-seller) of
+    pub seller_user_id: Option<String>,
+}
+
 // ====================================================================
 // SYNC BATCH MODELS
 // ====================================================================
@@ -218,6 +274,25 @@ pub struct SyncRecord {
     pub last_modified: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PushStatus {
+    Accepted,
+    Rejected,
+    Conflict,
+}
+
+/// Server verdict on one pushed change, matched by client mutation ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushRecordResult {
+    pub client_mutation_id: String,
+    pub status: PushStatus,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub remote: Option<SyncRecord>, // Server's current version, sent with conflicts
+}
+
 // ====================================================================
 // SYNC METADATA MODELS
 // ====================================================================
@@ -238,6 +313,19 @@ pub struct SyncWatermark {
     pub last_sync_timestamp: String,
     pub last_sync_version: i32,
     pub record_count: i32,
+    #[serde(default)]
+    pub cursor: Option<String>, // Opaque server cursor after the last committed page
+}
+
+/// A pulled change that failed to apply and waits to be retried
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullFailure {
+    pub table_name: String,
+    pub record: SyncRecord,
+    pub error_message: String,
+    pub attempts: i32,
+    pub first_failed_at: String,
+    pub last_failed_at: String,
 }
 
 // ====================================================================
@@ -275,6 +363,12 @@ pub struct SyncPerformanceMetrics {
     pub compression_ratio: f64,
     pub memory_usage_mb: f64,
     pub cpu_usage_percent: f64,
+    /// Bytes sent on the wire, and before compression
+    pub request_bytes: i64,
+    pub request_bytes_uncompressed: i64,
+    /// Bytes received on the wire, and after decompression
+    pub response_bytes: i64,
+    pub response_bytes_uncompressed: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -288,6 +382,112 @@ pub struct SyncPerformanceReport {
     pub performance_trend: Vec<SyncPerformanceMetrics>,
 }
 
+// ====================================================================
+// SYNC HISTORY MODELS
+// ====================================================================
+
+/// What started a sync run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncTrigger {
+    Manual,
+    Background,
+    Reconnect,
+    /// Pushing the changes of an imported offline bundle
+    Import,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncRunStatus {
+    Success,
+    /// Finished, but some tables or records reported errors
+    Partial,
+    Failed,
+    Cancelled,
+}
+
+/// One persisted `sync_workspace` or `sync_table` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncRun {
+    pub id: i64,
+    pub triggered_by: SyncTrigger,
+    pub workspace_id: String,
+    pub table_name: Option<String>, // None for a full workspace sync
+    pub status: SyncRunStatus,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: i64,
+    pub records_processed: i32,
+    pub records_created: i32,
+    pub records_updated: i32,
+    pub records_deleted: i32,
+    pub conflicts_found: i32,
+    pub request_bytes: i64,
+    pub request_bytes_uncompressed: i64,
+    pub response_bytes: i64,
+    pub response_bytes_uncompressed: i64,
+    pub error_count: i32,
+    pub errors: Vec<String>, // The first errors reported, capped
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncHistoryBucket {
+    Hour,
+    Day,
+}
+
+/// Filters for `get_sync_history`; timestamps are RFC 3339
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncHistoryQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub workspace_id: Option<String>,
+    pub triggered_by: Option<SyncTrigger>,
+    pub limit: u32,
+    pub bucket: SyncHistoryBucket,
+}
+
+impl Default for SyncHistoryQuery {
+    fn default() -> Self {
+        Self {
+            from: None,
+            to: None,
+            workspace_id: None,
+            triggered_by: None,
+            limit: 100,
+            bucket: SyncHistoryBucket::Day,
+        }
+    }
+}
+
+/// Aggregated runs over one hour or day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncThroughputPoint {
+    pub bucket_start: String,
+    pub runs: i32,
+    pub failed_runs: i32,
+    pub records_processed: i64,
+    pub avg_duration_ms: f64,
+    pub records_per_second: f64,
+    pub bytes_transferred: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncHistory {
+    /// Most recent runs first, up to the query limit
+    pub runs: Vec<SyncRun>,
+    pub total_runs: i32,
+    pub failed_runs: i32,
+    pub avg_duration_ms: f64,
+    pub records_per_second: f64,
+    pub bytes_transferred: i64,
+    /// Oldest bucket first
+    pub trends: Vec<SyncThroughputPoint>,
+}
+
 // ====================================================================
 // SYNC NOTIFICATION MODELS
 // ====================================================================
@@ -308,6 +508,11 @@ pub enum SyncNotificationType {
     SyncStarted,
     SyncCompleted,
     SyncFailed,
+    SyncCancelled,
+    TableStarted,
+    BatchPushed,
+    PagePulled,
+    TableCompleted,
     ConflictDetected,
     OfflineMode,
     OnlineMode,
@@ -336,12 +541,14 @@ impl SyncQueueItem {
             synced_at: None,
             error_message: None,
             retry_count: 0,
+            next_retry_at: None,
             status: SyncQueueStatus::Pending,
+            client_mutation_id: uuid::Uuid::new_v4().to_string(),
         }
     }
     
     pub fn is_retryable(&self) -> bool {
-        self.retry_count < 3 && self.status == SyncQueueStatus::Failed
+        self.status == SyncQueueStatus::Failed
     }
     
     pub fn increment_retry(&mut self) {
@@ -360,6 +567,7 @@ impl SyncReport {
             records_deleted: 0,
             conflicts_found: 0,
             errors: Vec::new(),
+            dropped_fields: Vec::new(),
             duration_ms: 0,
             timestamp: chrono::Utc::now().to_rfc3339(),
         }
@@ -381,6 +589,12 @@ impl SyncReport {
     }
 }
 
+impl Default for SyncReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SyncResult {
     pub fn new() -> Self {
         Self {
@@ -389,7 +603,9 @@ impl SyncResult {
             records_created: 0,
             records_updated: 0,
             records_deleted: 0,
+            conflicts_found: 0,
             errors: Vec::new(),
+            dropped_fields: Vec::new(),
             duration_ms: 0,
         }
     }
@@ -399,11 +615,27 @@ impl SyncResult {
         self.success = false;
     }
     
+    /// Note fields of `table_name` that were not stored, once each
+    pub fn add_dropped_fields(&mut self, table_name: &str, fields: &[String]) {
+        for field in fields {
+            let field = format!("{}.{}", table_name, field);
+            if !self.dropped_fields.contains(&field) {
+                self.dropped_fields.push(field);
+            }
+        }
+    }
+    
     pub fn is_successful(&self) -> bool {
         self.success && self.errors.is_empty()
     }
 }
 
+impl Default for SyncResult {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SyncConflict {
     pub fn new(
         table_name: String,
@@ -421,6 +653,10 @@ impl SyncConflict {
             remote_version,
             local_data,
             remote_data,
+            base_data: None,
+            field_conflicts: None,
+            local_clock: None,
+            remote_clock: None,
             resolution: None,
             resolved_data: None,
             created_at: chrono::Utc::now().to_rfc3339(),
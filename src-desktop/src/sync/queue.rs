@@ -8,55 +8,243 @@
 // ====================================================================
 
 use super::models::*;
-use sqlx::SqlitePool;
+use super::diff::PendingFieldChange;
+use rand::Rng;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Changes returned by `list_changes` when the filter sets no limit
+const DEFAULT_LIST_LIMIT: u32 = 200;
+
+/// Backoff schedule for failed queue items
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay_secs: u64,
+    pub max_delay_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_secs: 30,
+            max_delay_secs: 60 * 60,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff with jitter: the nth retry waits between half and
+    /// all of `base * 2^(n-1)`, capped at `max_delay_secs`
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(20);
+        let delay_secs = self.base_delay_secs
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_delay_secs);
+        let jitter = rand::thread_rng().gen_range(0.5..=1.0);
+
+        Duration::from_secs_f64(delay_secs as f64 * jitter)
+    }
+}
+
+/// Holds back changes to records with an unresolved conflict: pushing
+/// one would overwrite the remote side before the user has chosen
+const NOT_HELD_BY_CONFLICT: &str = r#"
+    NOT EXISTS (
+        SELECT 1 FROM sync_conflicts
+        WHERE sync_conflicts.table_name = sync_queue.table_name
+          AND sync_conflicts.record_id = sync_queue.record_id
+          AND sync_conflicts.resolved_at IS NULL
+    )
+"#;
 
 pub struct SyncQueue {
     pool: SqlitePool,
+    retry_policy: RetryPolicy,
 }
 
 impl SyncQueue {
     pub fn new(pool: SqlitePool) -> Self {
-        Self { pool }
+        Self::with_retry_policy(pool, RetryPolicy::default())
+    }
+
+    pub fn with_retry_policy(pool: SqlitePool, retry_policy: RetryPolicy) -> Self {
+        Self { pool, retry_policy }
     }
 
     /// Add a change to the sync queue
+    ///
+    /// Changes are coalesced per `(table_name, record_id)`: if the record
+    /// already has a change waiting that was never sent, the two are folded
+    /// into a single net operation. Returns `None` when the changes cancel
+    /// out (a record created and deleted before it was ever sent).
+    ///
+    /// A change that was sent may have been applied even if the push failed,
+    /// so it is retried as it is and the new change is queued behind it.
     pub async fn enqueue_change(
         &self,
         table_name: &str,
         record_id: &str,
         operation: SyncOperation,
         data: Option<String>,
-    ) -> Result<i64, sqlx::Error> {
-        let query = r#"
-            INSERT INTO sync_queue (table_name, record_id, operation, data, created_at, status)
-            VALUES (?, ?, ?, ?, ?, ?)
-        "#;
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let queued_id = Self::enqueue_change_in(&mut tx, table_name, record_id, operation, data).await?;
+        tx.commit().await?;
 
-        let result = sqlx::query(query)
-            .bind(table_name)
-            .bind(record_id)
-            .bind(format!("{:?}", operation))
-            .bind(data)
-            .bind(chrono::Utc::now().to_rfc3339())
-            .bind("PENDING")
-            .execute(&self.pool)
+        Ok(queued_id)
+    }
+
+    /// Add a change to the sync queue on an open connection or transaction
+    pub async fn enqueue_change_in(
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+        operation: SyncOperation,
+        data: Option<String>,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        let existing = sqlx::query(
+            r#"
+            SELECT id, operation, data
+            FROM sync_queue
+            WHERE table_name = ? AND record_id = ? AND status IN ('PENDING', 'FAILED')
+              AND last_attempted_at IS NULL
+            ORDER BY id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(table_name)
+        .bind(record_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+        let queued_id = match existing {
+            Some(row) => {
+                let existing_id = row.get::<i64, _>("id");
+                let existing_operation = row.get::<String, _>("operation");
+                let existing_data = row.get::<Option<String>, _>("data");
+
+                match coalesce_operations(&existing_operation, &operation) {
+                    Some(net_operation) => {
+                        let net_data = if existing_operation == "Delete" || operation == SyncOperation::Delete {
+                            data
+                        } else {
+                            merge_payloads(existing_data.as_deref(), data)
+                        };
+
+                        // Keep the original created_at so push order is preserved. The
+                        // server has never seen the mutation ID, so it stays.
+                        sqlx::query(
+                            r#"
+                            UPDATE sync_queue
+                            SET operation = ?, data = ?, status = 'PENDING',
+                                error_message = NULL, next_retry_at = NULL
+                            WHERE id = ?
+                            "#,
+                        )
+                        .bind(format!("{:?}", net_operation))
+                        .bind(net_data)
+                        .bind(existing_id)
+                        .execute(&mut *conn)
+                        .await?;
+
+                        Some(existing_id)
+                    }
+                    None => {
+                        sqlx::query("DELETE FROM sync_queue WHERE id = ?")
+                            .bind(existing_id)
+                            .execute(&mut *conn)
+                            .await?;
+
+                        println!("🧹 [SYNC QUEUE] {} {} was created and deleted before it was sent, dropped from queue", table_name, record_id);
+                        None
+                    }
+                }
+            }
+            None => {
+                let query = r#"
+                    INSERT INTO sync_queue (table_name, record_id, operation, data, created_at, status, client_mutation_id)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)
+                "#;
+
+                let result = sqlx::query(query)
+                    .bind(table_name)
+                    .bind(record_id)
+                    .bind(format!("{:?}", operation))
+                    .bind(data)
+                    .bind(chrono::Utc::now().to_rfc3339())
+                    .bind("PENDING")
+                    .bind(new_client_mutation_id())
+                    .execute(&mut *conn)
+                    .await?;
+
+                Some(result.last_insert_rowid())
+            }
+        };
+
+        Ok(queued_id)
+    }
+
+    /// Fold changes logged by the capture triggers into the queue
+    ///
+    /// Captured changes carry no payload; it is built from the typed row
+    /// when the change is pushed. Returns the number of log entries folded.
+    pub async fn collect_captured_changes(&self) -> Result<i32, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+
+        let rows = sqlx::query("SELECT id, table_name, record_id, operation FROM sync_change_log ORDER BY id ASC")
+            .fetch_all(&mut *tx)
+            .await?;
+
+        let mut last_id = 0;
+        for row in &rows {
+            let operation = match row.get::<String, _>("operation").as_str() {
+                "Insert" => SyncOperation::Insert,
+                "Delete" => SyncOperation::Delete,
+                _ => SyncOperation::Update,
+            };
+
+            Self::enqueue_change_in(
+                &mut tx,
+                &row.get::<String, _>("table_name"),
+                &row.get::<String, _>("record_id"),
+                operation,
+                None,
+            )
+            .await?;
+            last_id = row.get::<i64, _>("id");
+        }
+
+        sqlx::query("DELETE FROM sync_change_log WHERE id <= ?")
+            .bind(last_id)
+            .execute(&mut *tx)
             .await?;
+        tx.commit().await?;
 
-        Ok(result.last_insert_rowid())
+        Ok(rows.len() as i32)
     }
 
     /// Get pending changes for a specific table
+    ///
+    /// Changes to a record with an unresolved conflict wait until it is
+    /// resolved, and a change queued behind an earlier one for the same
+    /// record waits while that one is backing off or dead-lettered.
     pub async fn get_pending_changes(&self, table_name: &str) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
-        let query = r#"
+        let query = format!(
+            r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
-            WHERE table_name = ? AND status = 'PENDING'
+            WHERE table_name = ? AND status = 'PENDING' AND {}
             ORDER BY created_at ASC
-        "#;
+            "#,
+            NOT_HELD_BY_CONFLICT
+        );
 
-        let rows = sqlx::query_as::<_, SyncQueueItem>(query)
+        let rows = sqlx::query_as::<_, SyncQueueItem>(&query)
             .bind(table_name)
             .fetch_all(&self.pool)
             .await?;
@@ -68,7 +256,8 @@ impl SyncQueue {
     pub async fn get_all_pending_changes(&self) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
             WHERE status = 'PENDING'
             ORDER BY created_at ASC
@@ -81,17 +270,62 @@ impl SyncQueue {
         Ok(rows)
     }
 
-    /// Get failed changes that can be retried
+    /// Get failed changes whose backoff has elapsed
     pub async fn get_retryable_changes(&self) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
-            WHERE status = 'FAILED' AND retry_count < 3
+            WHERE status = 'FAILED' AND (next_retry_at IS NULL OR next_retry_at <= ?)
             ORDER BY created_at ASC
         "#;
 
         let rows = sqlx::query_as::<_, SyncQueueItem>(query)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Get pending changes plus failed changes that are due for retry for a table
+    ///
+    /// Changes to a record with an unresolved conflict wait until it is
+    /// resolved, and a change queued behind an earlier one for the same
+    /// record waits while that one is backing off or dead-lettered.
+    pub async fn get_changes_to_push(&self, table_name: &str, include_retries: bool) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
+        let query = format!(
+            r#"
+            SELECT id, table_name, record_id, operation, data, created_at, 
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
+            FROM sync_queue 
+            WHERE table_name = ?
+              AND (status = 'PENDING'
+                   OR (? AND status = 'FAILED' AND (next_retry_at IS NULL OR next_retry_at <= ?)))
+              AND NOT EXISTS (
+                  SELECT 1 FROM sync_queue AS earlier
+                  WHERE earlier.table_name = sync_queue.table_name
+                    AND earlier.record_id = sync_queue.record_id
+                    AND earlier.id < sync_queue.id
+                    AND (earlier.status IN ('IN_PROGRESS', 'DEAD_LETTER')
+                         OR (earlier.status = 'FAILED'
+                             AND NOT (? AND (earlier.next_retry_at IS NULL OR earlier.next_retry_at <= ?))))
+              )
+              AND {}
+            ORDER BY created_at ASC, id ASC
+            "#,
+            NOT_HELD_BY_CONFLICT
+        );
+
+        let now = chrono::Utc::now().to_rfc3339();
+        let rows = sqlx::query_as::<_, SyncQueueItem>(&query)
+            .bind(table_name)
+            .bind(include_retries)
+            .bind(&now)
+            .bind(include_retries)
+            .bind(&now)
             .fetch_all(&self.pool)
             .await?;
 
@@ -116,15 +350,41 @@ impl SyncQueue {
     }
 
     /// Mark a change as failed
+    ///
+    /// Schedules the next retry with exponential backoff, or moves the change
+    /// to the dead-letter state once `max_attempts` is reached.
     pub async fn mark_as_failed(&self, id: i64, error_message: &str) -> Result<(), sqlx::Error> {
+        let row = sqlx::query("SELECT retry_count FROM sync_queue WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let retry_count = match row {
+            Some(row) => row.get::<i32, _>("retry_count") + 1,
+            None => return Err(sqlx::Error::RowNotFound),
+        };
+
+        let (status, next_retry_at) = if retry_count as u32 >= self.retry_policy.max_attempts {
+            println!("☠️ [SYNC QUEUE] Change {} moved to dead letter after {} attempts", id, retry_count);
+            ("DEAD_LETTER", None)
+        } else {
+            let delay = self.retry_policy.delay_for_attempt(retry_count as u32);
+            let next_retry_at = chrono::Utc::now()
+                + chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::seconds(0));
+            ("FAILED", Some(next_retry_at.to_rfc3339()))
+        };
+
         let query = r#"
             UPDATE sync_queue 
-            SET status = 'FAILED', error_message = ?, retry_count = retry_count + 1
+            SET status = ?, error_message = ?, retry_count = ?, next_retry_at = ?
             WHERE id = ?
         "#;
 
         sqlx::query(query)
+            .bind(status)
             .bind(error_message)
+            .bind(retry_count)
+            .bind(next_retry_at)
             .bind(id)
             .execute(&self.pool)
             .await?;
@@ -133,14 +393,17 @@ impl SyncQueue {
     }
 
     /// Mark a change as in progress
+    ///
+    /// From here on the change counts as sent and is never coalesced again.
     pub async fn mark_as_in_progress(&self, id: i64) -> Result<(), sqlx::Error> {
         let query = r#"
             UPDATE sync_queue 
-            SET status = 'IN_PROGRESS'
+            SET status = 'IN_PROGRESS', last_attempted_at = ?
             WHERE id = ?
         "#;
 
         sqlx::query(query)
+            .bind(chrono::Utc::now().to_rfc3339())
             .bind(id)
             .execute(&self.pool)
             .await?;
@@ -179,6 +442,15 @@ impl SyncQueue {
         Ok(row.get::<i64, _>("count") as i32)
     }
 
+    /// Return changes left IN_PROGRESS by an interrupted push to the queue
+    pub async fn release_in_progress_changes(&self) -> Result<i32, sqlx::Error> {
+        let result = sqlx::query("UPDATE sync_queue SET status = 'PENDING' WHERE status = 'IN_PROGRESS'")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() as i32)
+    }
+
     /// Clear completed changes older than specified days
     pub async fn cleanup_old_changes(&self, days: i32) -> Result<(), sqlx::Error> {
         let cutoff_date = chrono::Utc::now() - chrono::Duration::days(days as i64);
@@ -204,7 +476,8 @@ impl SyncQueue {
                 SUM(CASE WHEN status = 'PENDING' THEN 1 ELSE 0 END) as pending,
                 SUM(CASE WHEN status = 'IN_PROGRESS' THEN 1 ELSE 0 END) as in_progress,
                 SUM(CASE WHEN status = 'COMPLETED' THEN 1 ELSE 0 END) as completed,
-                SUM(CASE WHEN status = 'FAILED' THEN 1 ELSE 0 END) as failed
+                SUM(CASE WHEN status = 'FAILED' THEN 1 ELSE 0 END) as failed,
+                SUM(CASE WHEN status = 'DEAD_LETTER' THEN 1 ELSE 0 END) as dead_letter
             FROM sync_queue
         "#;
 
@@ -218,6 +491,7 @@ impl SyncQueue {
             in_progress: row.get::<i64, _>("in_progress") as i32,
             completed: row.get::<i64, _>("completed") as i32,
             failed: row.get::<i64, _>("failed") as i32,
+            dead_letter: row.get::<i64, _>("dead_letter") as i32,
         })
     }
 
@@ -230,7 +504,8 @@ impl SyncQueue {
                 SUM(CASE WHEN status = 'PENDING' THEN 1 ELSE 0 END) as pending,
                 SUM(CASE WHEN status = 'IN_PROGRESS' THEN 1 ELSE 0 END) as in_progress,
                 SUM(CASE WHEN status = 'COMPLETED' THEN 1 ELSE 0 END) as completed,
-                SUM(CASE WHEN status = 'FAILED' THEN 1 ELSE 0 END) as failed
+                SUM(CASE WHEN status = 'FAILED' THEN 1 ELSE 0 END) as failed,
+                SUM(CASE WHEN status = 'DEAD_LETTER' THEN 1 ELSE 0 END) as dead_letter
             FROM sync_queue
             GROUP BY table_name
             ORDER BY table_name
@@ -243,12 +518,15 @@ impl SyncQueue {
         Ok(rows)
     }
 
-    /// Retry failed changes
+    /// Retry failed changes now, skipping their backoff
+    ///
+    /// Dead-lettered changes are left alone; they are requeued one by one
+    /// from the queue inspector.
     pub async fn retry_failed_changes(&self) -> Result<i32, sqlx::Error> {
         let query = r#"
             UPDATE sync_queue 
-            SET status = 'PENDING', error_message = NULL
-            WHERE status = 'FAILED' AND retry_count < 3
+            SET status = 'PENDING', error_message = NULL, next_retry_at = NULL
+            WHERE status = 'FAILED'
         "#;
 
         let result = sqlx::query(query)
@@ -258,6 +536,230 @@ impl SyncQueue {
         Ok(result.rows_affected() as i32)
     }
 
+    /// List queued changes matching the inspector filters, oldest first
+    pub async fn list_changes(&self, filter: &SyncQueueFilter) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
+        let statuses = if filter.statuses.is_empty() {
+            vec![SyncQueueStatus::Pending, SyncQueueStatus::Failed]
+        } else {
+            filter.statuses.clone()
+        };
+
+        let mut conditions = vec![format!("status IN ({})", vec!["?"; statuses.len()].join(", "))];
+        let mut binds: Vec<String> = statuses.iter().map(|status| status_value(status).to_string()).collect();
+
+        if let Some(table_name) = &filter.table_name {
+            conditions.push("table_name = ?".to_string());
+            binds.push(table_name.clone());
+        }
+        if let Some(minutes) = filter.min_age_minutes {
+            conditions.push("julianday(created_at) <= julianday(?)".to_string());
+            binds.push((chrono::Utc::now() - chrono::Duration::minutes(minutes as i64)).to_rfc3339());
+        }
+        if let Some(minutes) = filter.max_age_minutes {
+            conditions.push("julianday(created_at) >= julianday(?)".to_string());
+            binds.push((chrono::Utc::now() - chrono::Duration::minutes(minutes as i64)).to_rfc3339());
+        }
+
+        let query = format!(
+            r#"
+            SELECT id, table_name, record_id, operation, data, created_at, 
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
+            FROM sync_queue 
+            WHERE {}
+            ORDER BY created_at ASC
+            LIMIT ?
+            "#,
+            conditions.join(" AND ")
+        );
+
+        let mut statement = sqlx::query_as::<_, SyncQueueItem>(&query);
+        for bind in binds {
+            statement = statement.bind(bind);
+        }
+
+        let rows = statement
+            .bind(filter.limit.unwrap_or(DEFAULT_LIST_LIMIT) as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Get a single queued change
+    pub async fn get_change(&self, id: i64) -> Result<Option<SyncQueueItem>, sqlx::Error> {
+        let query = r#"
+            SELECT id, table_name, record_id, operation, data, created_at, 
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
+            FROM sync_queue 
+            WHERE id = ?
+        "#;
+
+        let row = sqlx::query_as::<_, SyncQueueItem>(query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row)
+    }
+
+    /// Queue a failed or dead-lettered change for an immediate retry
+    pub async fn requeue_change(&self, id: i64) -> Result<(), sqlx::Error> {
+        let query = r#"
+            UPDATE sync_queue 
+            SET status = 'PENDING', error_message = NULL, retry_count = 0, next_retry_at = NULL
+            WHERE id = ? AND status IN ('FAILED', 'DEAD_LETTER')
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Replace the payload of a failed or dead-lettered change and make it pending again
+    ///
+    /// The new payload is a new mutation and gets a new mutation ID.
+    pub async fn replace_failed_payload_in(conn: &mut SqliteConnection, id: i64, data: &str) -> Result<(), sqlx::Error> {
+        let query = r#"
+            UPDATE sync_queue 
+            SET status = 'PENDING', data = ?, error_message = NULL,
+                retry_count = 0, next_retry_at = NULL, client_mutation_id = ?,
+                last_attempted_at = NULL
+            WHERE id = ? AND status IN ('FAILED', 'DEAD_LETTER')
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(data)
+            .bind(new_client_mutation_id())
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Whether a change has been handed to the transport, so the server may have applied it
+    pub async fn was_sent_in(conn: &mut SqliteConnection, id: i64) -> Result<bool, sqlx::Error> {
+        let sent: Option<bool> = sqlx::query_scalar("SELECT last_attempted_at IS NOT NULL FROM sync_queue WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?;
+
+        Ok(sent.unwrap_or(false))
+    }
+
+    /// Drop a change that hasn't been pushed, along with its uncollected capture log entries
+    pub async fn discard_change_in(conn: &mut SqliteConnection, change: &SyncQueueItem) -> Result<(), sqlx::Error> {
+        let result = sqlx::query("DELETE FROM sync_queue WHERE id = ? AND status IN ('PENDING', 'FAILED', 'DEAD_LETTER')")
+            .bind(change.id)
+            .execute(&mut *conn)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        sqlx::query("DELETE FROM sync_change_log WHERE table_name = ? AND record_id = ?")
+            .bind(&change.table_name)
+            .bind(&change.record_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get every pending and failed change for a table, whatever its backoff
+    pub async fn get_unpushed_changes(&self, table_name: &str) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
+        let query = r#"
+            SELECT id, table_name, record_id, operation, data, created_at,
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
+            FROM sync_queue
+            WHERE table_name = ? AND status IN ('PENDING', 'FAILED')
+            ORDER BY created_at ASC
+        "#;
+
+        let rows = sqlx::query_as::<_, SyncQueueItem>(query)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows)
+    }
+
+    /// Whether a change with this mutation ID is or was queued here
+    pub async fn has_mutation_in(conn: &mut SqliteConnection, client_mutation_id: &str) -> Result<bool, sqlx::Error> {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM sync_queue WHERE client_mutation_id = ?)")
+            .bind(client_mutation_id)
+            .fetch_one(&mut *conn)
+            .await?;
+
+        Ok(exists)
+    }
+
+    /// Whether a record has local changes that haven't reached the server
+    pub async fn has_unpushed_change_in(
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let exists: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM sync_queue
+                WHERE table_name = ? AND record_id = ? AND status IN ('PENDING', 'FAILED', 'IN_PROGRESS', 'DEAD_LETTER')
+            ) OR EXISTS (
+                SELECT 1 FROM sync_change_log WHERE table_name = ? AND record_id = ?
+            )
+            "#,
+        )
+        .bind(table_name)
+        .bind(record_id)
+        .bind(table_name)
+        .bind(record_id)
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(exists)
+    }
+
+    /// Queue a change carried over from another device's queue
+    ///
+    /// Keeps the change's mutation ID and queue time, so the server applies
+    /// it once even if the other device pushes it too. Returns `false` when
+    /// the change was already queued here.
+    pub async fn import_change_in(conn: &mut SqliteConnection, change: &SyncQueueItem) -> Result<bool, sqlx::Error> {
+        let query = r#"
+            INSERT INTO sync_queue (table_name, record_id, operation, data, created_at, status, client_mutation_id)
+            VALUES (?, ?, ?, ?, ?, 'PENDING', ?)
+            ON CONFLICT(client_mutation_id) DO NOTHING
+        "#;
+
+        let result = sqlx::query(query)
+            .bind(&change.table_name)
+            .bind(&change.record_id)
+            .bind(format!("{:?}", change.operation))
+            .bind(&change.data)
+            .bind(&change.created_at)
+            .bind(&change.client_mutation_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     /// Clear all failed changes
     pub async fn clear_failed_changes(&self) -> Result<(), sqlx::Error> {
         let query = r#"
@@ -276,7 +778,8 @@ impl SyncQueue {
     pub async fn get_changes_by_operation(&self, operation: SyncOperation) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
             WHERE operation = ? AND status = 'PENDING'
             ORDER BY created_at ASC
@@ -294,7 +797,8 @@ impl SyncQueue {
     pub async fn get_oldest_pending_change(&self) -> Result<Option<SyncQueueItem>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
             WHERE status = 'PENDING'
             ORDER BY created_at ASC
@@ -312,7 +816,8 @@ impl SyncQueue {
     pub async fn get_changes_since(&self, since: &str) -> Result<Vec<SyncQueueItem>, sqlx::Error> {
         let query = r#"
             SELECT id, table_name, record_id, operation, data, created_at, 
-                   synced_at, error_message, retry_count, status
+                   synced_at, error_message, retry_count, next_retry_at, status,
+                   client_mutation_id
             FROM sync_queue 
             WHERE created_at > ?
             ORDER BY created_at ASC
@@ -325,6 +830,26 @@ impl SyncQueue {
 
         Ok(rows)
     }
+
+    /// Drop every change to a record that hasn't been pushed yet
+    ///
+    /// For when the record is settled to the server's version, e.g. a
+    /// conflict resolved in favour of the remote side.
+    pub async fn cancel_unpushed_changes_in(
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "DELETE FROM sync_queue WHERE table_name = ? AND record_id = ? AND status IN ('PENDING', 'FAILED', 'DEAD_LETTER')",
+        )
+        .bind(table_name)
+        .bind(record_id)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
 }
 
 // ====================================================================
@@ -338,6 +863,7 @@ pub struct QueueStats {
     pub in_progress: i32,
     pub completed: i32,
     pub failed: i32,
+    pub dead_letter: i32,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, sqlx::FromRow)]
@@ -348,6 +874,100 @@ pub struct TableQueueStats {
     pub in_progress: i32,
     pub completed: i32,
     pub failed: i32,
+    pub dead_letter: i32,
+}
+
+// ====================================================================
+// QUEUE INSPECTOR MODELS
+// ====================================================================
+
+/// Filters for listing queued changes
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SyncQueueFilter {
+    pub table_name: Option<String>,
+    /// Pending and failed changes when empty
+    pub statuses: Vec<SyncQueueStatus>,
+    /// Only changes queued at least this many minutes ago
+    pub min_age_minutes: Option<u32>,
+    /// Only changes queued at most this many minutes ago
+    pub max_age_minutes: Option<u32>,
+    pub limit: Option<u32>,
+}
+
+/// A queued change with the fields it would push
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SyncQueueEntry {
+    #[serde(flatten)]
+    pub change: SyncQueueItem,
+    /// Current local row, `None` once it is deleted
+    pub local_row: Option<serde_json::Value>,
+    /// What pushing the change would send, against the last synced version
+    pub diff: Vec<PendingFieldChange>,
+    /// Whether discarding the change can restore a synced version
+    pub has_synced_version: bool,
+}
+
+// ====================================================================
+// COALESCING HELPERS
+// ====================================================================
+
+/// Net operation for a queued change followed by a new one, or `None`
+/// when the two cancel out
+fn coalesce_operations(queued: &str, next: &SyncOperation) -> Option<SyncOperation> {
+    match (queued, next) {
+        // The server has never seen the record
+        ("Insert", SyncOperation::Delete) => None,
+        ("Insert", _) => Some(SyncOperation::Insert),
+        // A deleted record that comes back already exists on the server
+        ("Delete", SyncOperation::Insert) => Some(SyncOperation::Update),
+        (_, next) => Some(next.clone()),
+    }
+}
+
+/// Overlay a newer payload on a queued one
+///
+/// Update payloads are partial, so a `null` in the newer payload means
+/// "unchanged" and keeps the queued value.
+fn merge_payloads(queued: Option<&str>, next: Option<String>) -> Option<String> {
+    let next = match next {
+        Some(next) => next,
+        None => return queued.map(str::to_string),
+    };
+
+    let queued_value = queued.and_then(|q| serde_json::from_str::<serde_json::Value>(q).ok());
+    let next_value = serde_json::from_str::<serde_json::Value>(&next).ok();
+
+    match (queued_value, next_value) {
+        (Some(serde_json::Value::Object(mut merged)), Some(serde_json::Value::Object(overlay))) => {
+            for (key, value) in overlay {
+                if !value.is_null() || !merged.contains_key(&key) {
+                    merged.insert(key, value);
+                }
+            }
+            Some(serde_json::Value::Object(merged).to_string())
+        }
+        _ => Some(next),
+    }
+}
+
+/// How a status is stored in `sync_queue.status`
+fn status_value(status: &SyncQueueStatus) -> &'static str {
+    match status {
+        SyncQueueStatus::Pending => "PENDING",
+        SyncQueueStatus::InProgress => "IN_PROGRESS",
+        SyncQueueStatus::Completed => "COMPLETED",
+        SyncQueueStatus::Failed => "FAILED",
+        SyncQueueStatus::DeadLetter => "DEAD_LETTER",
+    }
+}
+
+/// Fresh idempotency key for a queued change
+///
+/// The server remembers applied mutation IDs, so resending a change after
+/// a timeout can't apply it twice.
+pub fn new_client_mutation_id() -> String {
+    uuid::Uuid::new_v4().to_string()
 }
 
 // ====================================================================
@@ -397,3 +1017,152 @@ pub enum QueueHealthStatus {
     Warning,
     Critical,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+
+    #[test]
+    fn retry_delays_grow_exponentially_up_to_the_cap() {
+        let policy = RetryPolicy { max_attempts: 10, base_delay_secs: 30, max_delay_secs: 100 };
+
+        let first = policy.delay_for_attempt(1).as_secs_f64();
+        let second = policy.delay_for_attempt(2).as_secs_f64();
+        let capped = policy.delay_for_attempt(8).as_secs_f64();
+
+        assert!((15.0..=30.0).contains(&first));
+        assert!((30.0..=60.0).contains(&second));
+        assert!((50.0..=100.0).contains(&capped));
+    }
+
+    async fn queued(pool: &SqlitePool, record_id: &str) -> Vec<(String, String)> {
+        sqlx::query_as("SELECT operation, status FROM sync_queue WHERE record_id = ? ORDER BY id")
+            .bind(record_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    /// Send a change and have the push fail, as after a timeout
+    async fn fail_attempt(queue: &SyncQueue, id: i64) {
+        queue.mark_as_in_progress(id).await.unwrap();
+        queue.mark_as_failed(id, "timed out").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_changes_back_off_and_then_move_to_dead_letter() {
+        let policy = RetryPolicy { max_attempts: 2, ..RetryPolicy::default() };
+        let queue = SyncQueue::with_retry_policy(test_cache_pool().await, policy);
+        let id = queue
+            .enqueue_change("companies", "c1", SyncOperation::Update, Some("{}".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+
+        queue.mark_as_failed(id, "timeout").await.unwrap();
+        assert!(queue.get_changes_to_push("companies", true).await.unwrap().is_empty());
+        assert_eq!(queue.get_queue_stats().await.unwrap().failed, 1);
+
+        queue.mark_as_failed(id, "timeout").await.unwrap();
+        let filter = SyncQueueFilter {
+            statuses: vec![SyncQueueStatus::DeadLetter],
+            ..SyncQueueFilter::default()
+        };
+        let dead_letters = queue.list_changes(&filter).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].retry_count, 2);
+
+        queue.requeue_change(id).await.unwrap();
+        let requeued = queue.get_changes_to_push("companies", true).await.unwrap();
+        assert_eq!(requeued[0].retry_count, 0);
+    }
+
+    #[tokio::test]
+    async fn unsent_changes_fold_into_one_net_operation() {
+        let pool = test_cache_pool().await;
+        let queue = SyncQueue::new(pool.clone());
+
+        queue.enqueue_change("companies", "c1", SyncOperation::Insert, Some(r#"{"name":"Acme"}"#.to_string())).await.unwrap();
+        queue.enqueue_change("companies", "c1", SyncOperation::Update, Some(r#"{"phone":"1"}"#.to_string())).await.unwrap();
+
+        let changes = queue.get_changes_to_push("companies", true).await.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].operation, SyncOperation::Insert);
+        let data: serde_json::Value = serde_json::from_str(changes[0].data.as_deref().unwrap()).unwrap();
+        assert_eq!(data, serde_json::json!({ "name": "Acme", "phone": "1" }));
+
+        queue.enqueue_change("companies", "c1", SyncOperation::Delete, None).await.unwrap();
+        assert!(queued(&pool, "c1").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_after_a_sent_insert_is_queued() {
+        let pool = test_cache_pool().await;
+        let queue = SyncQueue::new(pool.clone());
+
+        let id = queue.enqueue_change("companies", "c1", SyncOperation::Insert, None).await.unwrap().unwrap();
+        fail_attempt(&queue, id).await;
+        queue.enqueue_change("companies", "c1", SyncOperation::Delete, None).await.unwrap();
+
+        let operations: Vec<String> = queued(&pool, "c1").await.into_iter().map(|change| change.0).collect();
+        assert_eq!(operations, vec!["Insert", "Delete"]);
+    }
+
+    #[tokio::test]
+    async fn changes_wait_behind_an_earlier_change_that_is_backing_off() {
+        let pool = test_cache_pool().await;
+        let queue = SyncQueue::new(pool.clone());
+
+        let id = queue.enqueue_change("companies", "c1", SyncOperation::Insert, None).await.unwrap().unwrap();
+        fail_attempt(&queue, id).await;
+        queue.enqueue_change("companies", "c1", SyncOperation::Update, None).await.unwrap();
+
+        // The failed insert's backoff hasn't elapsed, so nothing for c1 goes out
+        assert!(queue.get_changes_to_push("companies", true).await.unwrap().is_empty());
+
+        sqlx::query("UPDATE sync_queue SET next_retry_at = NULL WHERE id = ?")
+            .bind(id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let operations: Vec<SyncOperation> = queue
+            .get_changes_to_push("companies", true)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|change| change.operation)
+            .collect();
+        assert_eq!(operations, vec![SyncOperation::Insert, SyncOperation::Update]);
+    }
+
+    async fn mutation_ids(pool: &SqlitePool, record_id: &str) -> Vec<String> {
+        sqlx::query_scalar("SELECT client_mutation_id FROM sync_queue WHERE record_id = ? ORDER BY id")
+            .bind(record_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn a_change_keeps_its_mutation_id_until_its_payload_is_replaced() {
+        let pool = test_cache_pool().await;
+        let queue = SyncQueue::with_retry_policy(pool.clone(), RetryPolicy { max_attempts: 1, ..RetryPolicy::default() });
+
+        let id = queue.enqueue_change("companies", "c1", SyncOperation::Insert, None).await.unwrap().unwrap();
+        let original = mutation_ids(&pool, "c1").await;
+        queue.enqueue_change("companies", "c1", SyncOperation::Update, None).await.unwrap();
+        assert_eq!(mutation_ids(&pool, "c1").await, original);
+
+        // A timed-out send is retried under the same ID
+        fail_attempt(&queue, id).await;
+        queue.requeue_change(id).await.unwrap();
+        assert_eq!(mutation_ids(&pool, "c1").await, original);
+
+        fail_attempt(&queue, id).await;
+        let mut conn = pool.acquire().await.unwrap();
+        SyncQueue::replace_failed_payload_in(&mut conn, id, r#"{"name":"Fixed"}"#).await.unwrap();
+        drop(conn);
+        assert_ne!(mutation_ids(&pool, "c1").await, original);
+    }
+}
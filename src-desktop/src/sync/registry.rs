@@ -0,0 +1,380 @@
+// ====================================================================
+// SYNC TABLE REGISTRY
+// ====================================================================
+//
+// This module declares every table the sync engine is allowed to touch:
+// its primary key, synced columns, foreign-key parents and sync
+// direction. Tables are synced parents-first, and table names that are
+// not registered are rejected before they reach any SQL.
+// ====================================================================
+
+use super::SyncError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SyncDirection {
+    /// Local changes are pushed and remote changes are pulled
+    Bidirectional,
+    /// Local changes are pushed, nothing is pulled (append-only logs)
+    PushOnly,
+    /// Remote changes are pulled, local changes are never pushed
+    PullOnly,
+}
+
+impl SyncDirection {
+    pub fn pushes(&self) -> bool {
+        matches!(self, SyncDirection::Bidirectional | SyncDirection::PushOnly)
+    }
+
+    pub fn pulls(&self) -> bool {
+        matches!(self, SyncDirection::Bidirectional | SyncDirection::PullOnly)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncTable {
+    pub name: &'static str,
+    pub primary_key: &'static str,
+    /// Record columns exchanged with the server; sync bookkeeping
+    /// (`last_synced_at`, `sync_version`, `is_dirty`) is not listed
+    pub columns: &'static [&'static str],
+    /// Tables this one references through foreign keys
+    pub parents: &'static [&'static str],
+    pub direction: SyncDirection,
+}
+
+impl SyncTable {
+    pub fn has_column(&self, column: &str) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+pub struct SyncTableRegistry {
+    tables: &'static [SyncTable],
+}
+
+impl SyncTableRegistry {
+    pub fn new() -> Self {
+        Self { tables: SYNC_TABLES }
+    }
+
+    /// All registered tables, in declaration order
+    pub fn tables(&self) -> &[SyncTable] {
+        self.tables
+    }
+
+    /// Look up a registered table
+    pub fn get(&self, table_name: &str) -> Option<&SyncTable> {
+        self.tables.iter().find(|table| table.name == table_name)
+    }
+
+    /// Look up a registered table, rejecting unknown names
+    pub fn require(&self, table_name: &str) -> Result<&SyncTable, SyncError> {
+        self.get(table_name)
+            .ok_or_else(|| SyncError::Configuration(format!("Unknown sync table: {}", table_name)))
+    }
+
+    /// Registered tables that reference the given table through foreign keys
+    pub fn children(&self, table_name: &str) -> Vec<&SyncTable> {
+        self.tables
+            .iter()
+            .filter(|table| table.parents.contains(&table_name))
+            .collect()
+    }
+
+    /// Registered tables ordered so that every table comes after its parents
+    ///
+    /// Ties keep declaration order, so the order is stable between runs.
+    pub fn sync_order(&self) -> Result<Vec<&SyncTable>, SyncError> {
+        for table in self.tables {
+            if let Some(parent) = table.parents.iter().find(|parent| self.get(parent).is_none()) {
+                return Err(SyncError::Configuration(format!(
+                    "Sync table {} references unregistered parent {}",
+                    table.name, parent
+                )));
+            }
+        }
+
+        let mut ordered: Vec<&SyncTable> = Vec::with_capacity(self.tables.len());
+        let mut placed: HashSet<&str> = HashSet::new();
+
+        while ordered.len() < self.tables.len() {
+            let ready: Vec<&SyncTable> = self
+                .tables
+                .iter()
+                .filter(|table| !placed.contains(table.name))
+                .filter(|table| table.parents.iter().all(|parent| placed.contains(parent)))
+                .collect();
+
+            if ready.is_empty() {
+                let remaining: Vec<&str> = self
+                    .tables
+                    .iter()
+                    .filter(|table| !placed.contains(table.name))
+                    .map(|table| table.name)
+                    .collect();
+                return Err(SyncError::Configuration(format!(
+                    "Foreign-key cycle between sync tables: {}",
+                    remaining.join(", ")
+                )));
+            }
+
+            for table in ready {
+                placed.insert(table.name);
+                ordered.push(table);
+            }
+        }
+
+        Ok(ordered)
+    }
+}
+
+impl Default for SyncTableRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ====================================================================
+// REGISTERED TABLES
+// ====================================================================
+// Columns mirror migrations/003_streamlined_schema_parity.sql
+
+const SYNC_TABLES: &[SyncTable] = &[
+    SyncTable {
+        name: "workspaces",
+        primary_key: "id",
+        columns: &[
+            "id", "name", "slug", "timezone", "description", "created_at", "updated_at",
+            "is_active", "deleted_at", "business_model", "competitive_advantages",
+            "ideal_customer_profile", "industry", "product_portfolio", "sales_methodology",
+            "service_offerings", "target_company_size", "target_industries",
+            "value_propositions", "speedrun_daily_target", "speedrun_weekly_target",
+            "news_enabled", "news_industries", "news_sources",
+        ],
+        parents: &[],
+        // Managed on the server; local writes are never pushed
+        direction: SyncDirection::PullOnly,
+    },
+    SyncTable {
+        name: "users",
+        primary_key: "id",
+        // Password hashes stay on the device that set them
+        columns: &[
+            "id", "email", "name", "first_name", "last_name", "timezone", "created_at",
+            "updated_at", "last_login_at", "is_active", "active_workspace_id", "username",
+            "speedrun_ranking_mode",
+        ],
+        parents: &["workspaces"],
+        // Managed on the server; local writes are never pushed
+        direction: SyncDirection::PullOnly,
+    },
+    SyncTable {
+        name: "companies",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "name", "legal_name", "trading_name", "local_name",
+            "description", "website", "email", "phone", "fax", "address", "city", "state",
+            "country", "postal_code", "industry", "sector", "size", "revenue", "currency",
+            "employee_count", "founded_year", "registration_number", "tax_id", "vat_number",
+            "domain", "logo_url", "status", "priority", "tags", "custom_fields", "notes",
+            "last_action", "last_action_date", "next_action", "next_action_date",
+            "action_status", "global_rank", "created_at", "updated_at", "entity_id",
+            "deleted_at", "main_seller_id", "actual_close_date", "expected_close_date",
+            "opportunity_amount", "opportunity_probability", "opportunity_stage",
+            "acquisition_date", "active_job_postings", "business_challenges",
+            "business_priorities", "company_intelligence", "company_updates",
+            "competitive_advantages", "competitors", "confidence", "decision_timeline",
+            "digital_maturity", "facebook_url", "github_url", "growth_opportunities", "hq_city",
+            "hq_country_iso2", "hq_country_iso3", "hq_full_address", "hq_location", "hq_region",
+            "hq_state", "hq_street", "hq_zipcode", "instagram_url", "is_public",
+            "key_influencers", "last_funding_amount", "last_funding_date", "last_verified",
+            "linkedin_followers", "linkedin_url", "market_position", "market_threats",
+            "naics_codes", "num_technologies_used", "parent_company_domain",
+            "parent_company_name", "sic_codes", "sources", "stock_symbol",
+            "strategic_initiatives", "success_metrics", "tech_stack", "technologies_used",
+            "twitter_followers", "twitter_url", "youtube_url", "next_action_reasoning",
+            "next_action_priority", "next_action_type", "next_action_updated_at",
+            "acquisition_history", "ai_confidence", "ai_intelligence", "ai_last_updated",
+            "data_last_verified", "data_quality_breakdown", "data_quality_score",
+            "data_sources", "employee_count_change", "employee_reviews_score",
+            "executive_arrivals", "executive_departures", "funding_rounds",
+            "job_postings_change", "product_reviews_score", "revenue_range",
+        ],
+        parents: &["workspaces", "users"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "people",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "company_id", "first_name", "last_name", "full_name",
+            "display_name", "salutation", "suffix", "job_title", "title", "department",
+            "seniority", "email", "work_email", "personal_email", "phone", "mobile_phone",
+            "work_phone", "linkedin_url", "address", "city", "state", "country", "postal_code",
+            "date_of_birth", "gender", "bio", "profile_picture_url", "status", "priority",
+            "source", "tags", "custom_fields", "notes", "preferred_language", "timezone",
+            "email_verified", "phone_verified", "last_action", "last_action_date",
+            "next_action", "next_action_date", "action_status", "engagement_score",
+            "global_rank", "company_rank", "created_at", "updated_at", "entity_id",
+            "deleted_at", "main_seller_id", "vertical", "achievements", "budget_responsibility",
+            "buyer_group_optimized", "buyer_group_role", "buyer_group_status",
+            "career_timeline", "certifications", "communication_style", "coresignal_data",
+            "current_company", "current_role", "data_completeness", "decision_making",
+            "decision_power", "degrees", "email_confidence", "engagement_level",
+            "engagement_strategy", "enriched_data", "enrichment_score", "enrichment_sources",
+            "enrichment_version", "fields_of_study", "graduation_years", "hidden_from_sections",
+            "industry_experience", "industry_skills", "influence_level", "influence_score",
+            "institutions", "is_buyer_group_member", "languages", "last_enriched",
+            "leadership_experience", "mobile_verified", "phone_confidence", "preferred_contact",
+            "previous_roles", "publications", "response_time", "role_history", "role_promoted",
+            "soft_skills", "speaking_engagements", "status_reason", "status_update_date",
+            "team_size", "technical_skills", "total_experience", "years_at_company",
+            "years_in_role", "next_action_priority", "next_action_reasoning",
+            "next_action_type", "next_action_updated_at", "linkedin_connection_date",
+            "linkedin_navigator_url", "decision_power_score", "years_experience",
+            "ai_confidence", "ai_intelligence", "ai_last_updated", "data_last_verified",
+            "data_quality_breakdown", "data_quality_score", "data_sources",
+            "email_quality_grade", "linkedin_connections", "linkedin_followers",
+            "phone_quality_score", "salary_projections", "total_experience_months",
+        ],
+        parents: &["workspaces", "companies", "users"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "actions",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "user_id", "company_id", "person_id", "type", "subject",
+            "description", "outcome", "scheduled_at", "completed_at", "status", "priority",
+            "created_at", "updated_at", "deleted_at",
+        ],
+        parents: &["workspaces", "users", "companies", "people"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "research_data",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "entity_type", "entity_id", "research_type", "content",
+            "sources", "extracted_data", "confidence", "model", "tokens_used",
+            "processing_time", "cost", "created_at", "updated_at", "expires_at",
+        ],
+        parents: &["workspaces"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "api_cost_tracking",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "user_id", "api_provider", "endpoint", "operation", "cost",
+            "tokens_used", "entity_type", "entity_id", "request_data", "success",
+            "error_message", "created_at",
+        ],
+        parents: &["workspaces", "users"],
+        direction: SyncDirection::PushOnly,
+    },
+    SyncTable {
+        name: "ai_conversations",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "user_id", "title", "last_activity", "is_active",
+            "welcome_message", "metadata", "created_at", "updated_at", "deleted_at",
+        ],
+        parents: &["workspaces", "users"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "ai_messages",
+        primary_key: "id",
+        columns: &[
+            "id", "conversation_id", "type", "content", "metadata", "created_at",
+        ],
+        parents: &["ai_conversations"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "chronicle_reports",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "title", "report_date", "report_type", "content",
+            "created_at", "updated_at", "created_by", "deleted_at",
+        ],
+        parents: &["workspaces"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "buyer_groups",
+        primary_key: "id",
+        // workspace_id has no FOREIGN KEY in the schema but still scopes the group
+        columns: &[
+            "id", "company_name", "website", "industry", "company_size", "workspace_id",
+            "cohesion_score", "overall_confidence", "total_members", "processing_time",
+            "metadata", "created_at", "updated_at",
+        ],
+        parents: &["workspaces"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "buyer_group_members",
+        primary_key: "id",
+        columns: &[
+            "id", "buyer_group_id", "name", "title", "role", "email", "phone", "linkedin",
+            "confidence", "influence_score", "created_at", "updated_at",
+        ],
+        parents: &["buyer_groups"],
+        direction: SyncDirection::Bidirectional,
+    },
+    SyncTable {
+        name: "audit_logs",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "user_id", "entity_type", "entity_id", "action", "old_values",
+            "new_values", "timestamp", "success",
+        ],
+        parents: &["workspaces", "users"],
+        direction: SyncDirection::PushOnly,
+    },
+    SyncTable {
+        name: "email_messages",
+        primary_key: "id",
+        columns: &[
+            "id", "workspace_id", "provider", "message_id", "thread_id", "subject", "body",
+            "body_html", "from_address", "to_addresses", "cc_addresses", "bcc_addresses",
+            "sent_at", "received_at", "is_read", "is_important", "attachments", "labels",
+            "company_id", "person_id", "created_at", "updated_at",
+        ],
+        parents: &["workspaces", "companies", "people"],
+        direction: SyncDirection::Bidirectional,
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_table_syncs_after_its_parents() {
+        let registry = SyncTableRegistry::new();
+        let order: Vec<&str> = registry.sync_order().unwrap().iter().map(|table| table.name).collect();
+
+        for table in registry.tables() {
+            let position = order.iter().position(|name| *name == table.name).unwrap();
+            for parent in table.parents {
+                assert!(order[..position].contains(parent), "{} syncs before its parent {}", table.name, parent);
+            }
+        }
+    }
+
+    #[test]
+    fn server_managed_tables_are_never_pushed() {
+        let registry = SyncTableRegistry::new();
+
+        for name in ["workspaces", "users"] {
+            let direction = registry.require(name).unwrap().direction;
+            assert!(direction.pulls() && !direction.pushes(), "{} is pushed", name);
+        }
+        assert!(registry.require("not_a_table").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+// ====================================================================
+// BACKGROUND SYNC SCHEDULER
+// ====================================================================
+//
+// This module owns the long-lived background task that runs
+// `sync_workspace` on the shared engine at the configured
+// `sync_interval_minutes` cadence.
+// The schedule is persisted through `SyncSettingsStore`, so it is
+// restored when the app restarts.
+// ====================================================================
+
+use super::*;
+use crate::database_init::get_database_manager;
+use rand::Rng;
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+
+/// Ticks are spread by up to +/- 10% of the interval so that devices
+/// started at the same time don't all hit the server together
+const JITTER_RATIO: f64 = 0.1;
+
+pub struct BackgroundSyncScheduler {
+    engine: SharedSyncEngine,
+    task: Mutex<Option<ScheduledSync>>,
+}
+
+struct ScheduledSync {
+    workspace_id: String,
+    interval_minutes: u32,
+    cancel_tx: watch::Sender<bool>,
+    _handle: tauri::async_runtime::JoinHandle<()>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundSyncStatus {
+    pub enabled: bool,
+    pub workspace_id: Option<String>,
+    pub interval_minutes: Option<u32>,
+    pub sync_in_progress: bool,
+}
+
+impl BackgroundSyncScheduler {
+    pub fn new(engine: SharedSyncEngine) -> Self {
+        Self {
+            engine,
+            task: Mutex::new(None),
+        }
+    }
+
+    /// Start (or restart) the background sync loop for a workspace
+    pub async fn start(&self, workspace_id: String, interval_minutes: u32) -> Result<(), SyncError> {
+        if interval_minutes == 0 {
+            return Err(SyncError::Configuration("Background sync interval must be at least 1 minute".to_string()));
+        }
+
+        let mut task = self.task.lock().await;
+
+        if let Some(previous) = task.take() {
+            let _ = previous.cancel_tx.send(true);
+        }
+
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        let handle = tauri::async_runtime::spawn(run_schedule(
+            self.engine.clone(),
+            workspace_id.clone(),
+            interval_minutes,
+            cancel_rx,
+        ));
+
+        println!(
+            "⏰ [BACKGROUND SYNC] Scheduled sync for workspace {} every {} minutes",
+            workspace_id, interval_minutes
+        );
+
+        *task = Some(ScheduledSync {
+            workspace_id,
+            interval_minutes,
+            cancel_tx,
+            _handle: handle,
+        });
+
+        Ok(())
+    }
+
+    /// Stop the background sync loop
+    ///
+    /// A sync that is already running finishes normally; no further
+    /// ticks are scheduled afterwards.
+    pub async fn stop(&self) -> bool {
+        match self.task.lock().await.take() {
+            Some(previous) => {
+                let _ = previous.cancel_tx.send(true);
+                println!("⏹️ [BACKGROUND SYNC] Stopped sync for workspace {}", previous.workspace_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restore the schedule from persisted settings (called on app startup)
+    pub async fn restore_from_settings(&self) -> Result<(), SyncError> {
+        let db_manager = get_database_manager().map_err(SyncError::Configuration)?;
+        let sqlite_pool = db_manager.get_sqlite_pool().await.map_err(SyncError::Configuration)?;
+        let settings = SyncSettingsStore::new(sqlite_pool);
+
+        let config = settings.load_config().await?;
+        if !config.enable_background_sync {
+            println!("⏸️ [BACKGROUND SYNC] Background sync disabled in settings");
+            return Ok(());
+        }
+
+        match settings.get_background_workspace().await? {
+            Some(workspace_id) => self.start(workspace_id, config.sync_interval_minutes).await,
+            None => {
+                println!("⏸️ [BACKGROUND SYNC] No workspace configured for background sync");
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the current schedule
+    pub async fn status(&self) -> BackgroundSyncStatus {
+        let task = self.task.lock().await;
+
+        BackgroundSyncStatus {
+            enabled: task.is_some(),
+            workspace_id: task.as_ref().map(|t| t.workspace_id.clone()),
+            interval_minutes: task.as_ref().map(|t| t.interval_minutes),
+            sync_in_progress: self.engine.is_sync_in_progress(),
+        }
+    }
+}
+
+// ====================================================================
+// SCHEDULER LOOP
+// ====================================================================
+
+async fn run_schedule(
+    engine: SharedSyncEngine,
+    workspace_id: String,
+    interval_minutes: u32,
+    mut cancel_rx: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(jittered_interval(interval_minutes)) => {}
+            _ = cancel_rx.changed() => break,
+        }
+
+        if *cancel_rx.borrow() {
+            break;
+        }
+
+        match run_background_sync(&engine, &workspace_id).await {
+            Ok(report) => {
+                println!(
+                    "✅ [BACKGROUND SYNC] Synced {} records for workspace {}",
+                    report.records_processed, workspace_id
+                );
+            }
+            Err(SyncError::AlreadyRunning) => {
+                println!("⏭️ [BACKGROUND SYNC] Sync already running, skipping this tick");
+            }
+            Err(e) => {
+                println!("❌ [BACKGROUND SYNC] Sync failed for workspace {}: {}", workspace_id, e);
+            }
+        }
+    }
+
+    println!("⏹️ [BACKGROUND SYNC] Scheduler loop exited for workspace {}", workspace_id);
+}
+
+async fn run_background_sync(engine: &SharedSyncEngine, workspace_id: &str) -> Result<SyncReport, SyncError> {
+    engine
+        .get()
+        .await?
+        .sync_workspace_triggered_by(workspace_id, SyncTrigger::Background)
+        .await
+}
+
+fn jittered_interval(interval_minutes: u32) -> Duration {
+    let base_secs = interval_minutes as f64 * 60.0;
+    let jitter = rand::thread_rng().gen_range(-JITTER_RATIO..=JITTER_RATIO);
+
+    Duration::from_secs_f64((base_secs * (1.0 + jitter)).max(1.0))
+}
@@ -0,0 +1,522 @@
+// ====================================================================
+// SYNC REPLICATION SCOPES
+// ====================================================================
+//
+// This module limits which rows of a workspace are kept on this device.
+// Scopes are stored per workspace in the sync settings and applied in
+// two places:
+// - Pull requests only ask the server for rows inside the scope
+// - After each pull, clean local rows that fell outside the scope are
+//   evicted, so `cache.db` shrinks when a scope is narrowed
+// A row referencing a parent row the scope leaves out is out of scope
+// too, so narrowing a table also narrows the tables below it.
+// Rows with unpushed local edits are never evicted.
+// ====================================================================
+
+use super::models::*;
+use super::registry::{SyncTable, SyncTableRegistry};
+use super::SyncError;
+use serde_json::Value;
+use sqlx::SqliteConnection;
+use std::collections::HashMap;
+
+/// Column age limits apply to when the scope doesn't name one
+pub const DEFAULT_AGE_COLUMN: &str = "updated_at";
+
+/// Column holding a record's main seller
+pub const MAIN_SELLER_COLUMN: &str = "main_seller_id";
+
+/// Tables whose co-sellers count as sellers: (table, co-seller table, foreign key)
+pub const CO_SELLER_TABLES: &[(&str, &str, &str)] = &[("people", "person_co_sellers", "person_id")];
+
+impl ReplicationScope {
+    /// Scope for a workspace that replicates everything
+    pub fn full(workspace_id: &str) -> Self {
+        Self {
+            workspace_id: workspace_id.to_string(),
+            tables: Vec::new(),
+        }
+    }
+
+    /// The scope of one table, if it is restricted
+    pub fn table(&self, table_name: &str) -> Option<&TableScope> {
+        self.tables
+            .iter()
+            .find(|scope| scope.table_name == table_name && scope.is_restricted())
+    }
+
+    /// Whether the scope leaves out rows of a table, directly or through its parents
+    pub fn narrows(&self, registry: &SyncTableRegistry, table_name: &str) -> bool {
+        self.table(table_name).is_some()
+            || registry
+                .get(table_name)
+                .is_some_and(|table| table.parents.iter().any(|parent| self.narrows(registry, parent)))
+    }
+
+    /// Whether a table keeps different rows under this scope than under `previous`
+    pub fn changes_rows_of(&self, previous: &ReplicationScope, registry: &SyncTableRegistry, table_name: &str) -> bool {
+        previous.table(table_name) != self.table(table_name)
+            || registry
+                .get(table_name)
+                .is_some_and(|table| table.parents.iter().any(|parent| self.changes_rows_of(previous, registry, parent)))
+    }
+
+    /// Check every table scope against the registry before it reaches any SQL
+    pub fn validate(&self, registry: &SyncTableRegistry) -> Result<(), SyncError> {
+        for scope in &self.tables {
+            scope.validate(registry.require(&scope.table_name)?)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TableScope {
+    pub fn is_restricted(&self) -> bool {
+        self.skip_pull || self.max_age_days.is_some() || self.seller_user_id.is_some()
+    }
+
+    pub fn age_column(&self) -> &str {
+        self.age_column.as_deref().unwrap_or(DEFAULT_AGE_COLUMN)
+    }
+
+    /// Oldest timestamp inside the age limit, as RFC 3339
+    pub fn age_cutoff(&self) -> Option<String> {
+        self.max_age_days
+            .map(|days| (chrono::Utc::now() - chrono::Duration::days(days as i64)).to_rfc3339())
+    }
+
+    /// Co-seller table and foreign key for the seller filter, if the table has one
+    pub fn co_sellers(&self) -> Option<(&'static str, &'static str)> {
+        CO_SELLER_TABLES
+            .iter()
+            .find(|(table, _, _)| *table == self.table_name)
+            .map(|(_, co_seller_table, foreign_key)| (*co_seller_table, *foreign_key))
+    }
+
+    fn validate(&self, table: &SyncTable) -> Result<(), SyncError> {
+        if self.max_age_days.is_some() && !table.has_column(self.age_column()) {
+            return Err(SyncError::Configuration(format!(
+                "Table {} has no column {} to limit by age",
+                table.name,
+                self.age_column()
+            )));
+        }
+
+        if self.seller_user_id.is_some() && !table.has_column(MAIN_SELLER_COLUMN) {
+            return Err(SyncError::Configuration(format!(
+                "Table {} has no seller to scope by",
+                table.name
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// SQLite condition matching the rows inside the scope, with its bind values
+    ///
+    /// Rows without an age are kept; rows without a seller are not the user's.
+    fn local_condition(&self, table: &SyncTable) -> (String, Vec<String>) {
+        if self.skip_pull {
+            return ("0".to_string(), Vec::new());
+        }
+
+        let mut conditions = Vec::new();
+        let mut binds = Vec::new();
+
+        if let Some(cutoff) = self.age_cutoff() {
+            conditions.push(format!("COALESCE(julianday({}) >= julianday(?), 1)", self.age_column()));
+            binds.push(cutoff);
+        }
+
+        if let Some(seller) = &self.seller_user_id {
+            match self.co_sellers() {
+                Some((co_seller_table, foreign_key)) => {
+                    conditions.push(format!(
+                        "(COALESCE({seller_column} = ?, 0) OR EXISTS (SELECT 1 FROM {co_sellers} cs WHERE cs.{fk} = {table}.{pk} AND cs.user_id = ?))",
+                        seller_column = MAIN_SELLER_COLUMN,
+                        co_sellers = co_seller_table,
+                        fk = foreign_key,
+                        table = table.name,
+                        pk = table.primary_key,
+                    ));
+                    binds.push(seller.clone());
+                    binds.push(seller.clone());
+                }
+                None => {
+                    conditions.push(format!("COALESCE({} = ?, 0)", MAIN_SELLER_COLUMN));
+                    binds.push(seller.clone());
+                }
+            }
+        }
+
+        if conditions.is_empty() {
+            ("1".to_string(), binds)
+        } else {
+            (conditions.join(" AND "), binds)
+        }
+    }
+}
+
+/// A replication scope with the foreign keys into the tables it narrows
+///
+/// Foreign-key columns are read from the cache schema, so a child whose
+/// table has no key to a narrowed parent is scoped by its own rows only.
+pub struct ScopeFilter<'a> {
+    scope: &'a ReplicationScope,
+    registry: &'a SyncTableRegistry,
+    /// (child, parent) -> [(child column, parent column)]
+    references: HashMap<(&'a str, &'a str), Vec<(String, String)>>,
+}
+
+impl<'a> ScopeFilter<'a> {
+    pub async fn load(
+        conn: &mut SqliteConnection,
+        scope: &'a ReplicationScope,
+        registry: &'a SyncTableRegistry,
+    ) -> Result<Self, sqlx::Error> {
+        let mut references = HashMap::new();
+
+        for table in registry.tables() {
+            for parent in table.parents.iter().filter(|parent| scope.narrows(registry, parent)) {
+                let Some(parent_table) = registry.get(parent) else { continue };
+                let columns: Vec<(String, Option<String>)> =
+                    sqlx::query_as(r#"SELECT "from", "to" FROM pragma_foreign_key_list(?) WHERE "table" = ?"#)
+                        .bind(table.name)
+                        .bind(parent_table.name)
+                        .fetch_all(&mut *conn)
+                        .await?;
+
+                let columns: Vec<(String, String)> = columns
+                    .into_iter()
+                    .map(|(column, parent_column)| {
+                        (column, parent_column.unwrap_or_else(|| parent_table.primary_key.to_string()))
+                    })
+                    .collect();
+                if !columns.is_empty() {
+                    references.insert((table.name, parent_table.name), columns);
+                }
+            }
+        }
+
+        Ok(Self { scope, registry, references })
+    }
+
+    /// SQLite condition matching a table's rows inside the scope, with its bind values
+    ///
+    /// A row is inside when it matches its own table's scope and every
+    /// parent row it references in a narrowed table is inside as well.
+    pub fn condition(&self, table: &SyncTable) -> (String, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut binds = Vec::new();
+
+        if let Some(scope) = self.scope.table(table.name) {
+            let (condition, condition_binds) = scope.local_condition(table);
+            conditions.push(condition);
+            binds.extend(condition_binds);
+        }
+
+        for parent in table.parents {
+            let (Some(columns), Some(parent_table)) =
+                (self.references.get(&(table.name, *parent)), self.registry.get(parent))
+            else {
+                continue;
+            };
+            let (parent_condition, parent_binds) = self.condition(parent_table);
+
+            for (column, parent_column) in columns {
+                conditions.push(format!(
+                    "({table}.{column} IS NULL OR EXISTS (SELECT 1 FROM {parent} WHERE {parent}.{parent_column} = {table}.{column} AND {parent_condition}))",
+                    table = table.name,
+                    column = column,
+                    parent = parent_table.name,
+                    parent_column = parent_column,
+                    parent_condition = parent_condition,
+                ));
+                binds.extend(parent_binds.iter().cloned());
+            }
+        }
+
+        if conditions.is_empty() {
+            ("1".to_string(), binds)
+        } else {
+            (conditions.join(" AND "), binds)
+        }
+    }
+
+    /// Whether every parent row a record references in a narrowed table is
+    /// kept on this device
+    pub async fn parents_in_scope(
+        &self,
+        conn: &mut SqliteConnection,
+        table: &SyncTable,
+        data: &Value,
+    ) -> Result<bool, sqlx::Error> {
+        for parent in table.parents {
+            let (Some(columns), Some(parent_table)) =
+                (self.references.get(&(table.name, *parent)), self.registry.get(parent))
+            else {
+                continue;
+            };
+            let (parent_condition, parent_binds) = self.condition(parent_table);
+
+            for (column, parent_column) in columns {
+                let value = match data.get(column) {
+                    None | Some(Value::Null) => continue,
+                    Some(Value::String(value)) => value.clone(),
+                    Some(value) => value.to_string(),
+                };
+
+                let query = format!(
+                    "SELECT EXISTS (SELECT 1 FROM {parent} WHERE {parent}.{parent_column} = ? AND {parent_condition})",
+                    parent = parent_table.name,
+                    parent_column = parent_column,
+                    parent_condition = parent_condition,
+                );
+                let mut statement = sqlx::query_scalar::<_, bool>(&query).bind(value);
+                for bind in &parent_binds {
+                    statement = statement.bind(bind);
+                }
+
+                if !statement.fetch_one(&mut *conn).await? {
+                    return Ok(false);
+                }
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// SQLite conditions excluding rows that rows of the child tables still reference
+///
+/// Foreign-key columns are read from the cache schema, so children whose
+/// table is missing locally add no condition.
+async fn unreferenced_conditions(
+    conn: &mut SqliteConnection,
+    table: &SyncTable,
+    children: &[&SyncTable],
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut conditions = Vec::new();
+
+    for child in children {
+        let foreign_keys: Vec<String> =
+            sqlx::query_scalar(r#"SELECT "from" FROM pragma_foreign_key_list(?) WHERE "table" = ?"#)
+                .bind(child.name)
+                .bind(table.name)
+                .fetch_all(&mut *conn)
+                .await?;
+
+        for foreign_key in foreign_keys {
+            conditions.push(format!(
+                "AND NOT EXISTS (SELECT 1 FROM {child} ref WHERE ref.{fk} = {table}.{pk})",
+                child = child.name,
+                fk = foreign_key,
+                table = table.name,
+                pk = table.primary_key,
+            ));
+        }
+    }
+
+    Ok(conditions)
+}
+
+/// Delete clean local rows that fall outside the table's scope
+///
+/// Rows still referenced by rows of `children` are kept, since deleting
+/// them would break those foreign keys; they are evicted once the
+/// referencing rows are gone. Must run in a sync write so the deletes
+/// aren't captured and pushed. Returns the number of rows evicted.
+pub async fn evict_out_of_scope(
+    conn: &mut SqliteConnection,
+    table: &SyncTable,
+    children: &[&SyncTable],
+    filter: &ScopeFilter<'_>,
+    has_dirty_flag: bool,
+) -> Result<u64, sqlx::Error> {
+    let (in_scope, binds) = filter.condition(table);
+    let dirty_guard = if has_dirty_flag { "AND COALESCE(is_dirty, 0) = 0" } else { "" };
+    let reference_guard = unreferenced_conditions(conn, table, children).await?.join("\n          ");
+
+    let query = format!(
+        r#"
+        DELETE FROM {table}
+        WHERE NOT ({in_scope})
+          {dirty_guard}
+          {reference_guard}
+          AND {pk} NOT IN (
+              SELECT record_id FROM sync_queue
+              WHERE table_name = ? AND status IN ('PENDING', 'FAILED', 'IN_PROGRESS', 'DEAD_LETTER')
+          )
+          AND {pk} NOT IN (SELECT record_id FROM sync_change_log WHERE table_name = ?)
+        "#,
+        table = table.name,
+        pk = table.primary_key,
+        in_scope = in_scope,
+        dirty_guard = dirty_guard,
+        reference_guard = reference_guard,
+    );
+
+    let mut statement = sqlx::query(&query);
+    for bind in binds {
+        statement = statement.bind(bind);
+    }
+    let evicted = statement
+        .bind(table.name)
+        .bind(table.name)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+
+    if evicted > 0 {
+        // Merge bases and clocks of evicted rows are no longer needed
+        for bookkeeping in ["sync_base_versions", "sync_record_clocks"] {
+            let query = format!(
+                "DELETE FROM {bookkeeping} WHERE table_name = ? AND record_id NOT IN (SELECT {pk} FROM {table})",
+                bookkeeping = bookkeeping,
+                pk = table.primary_key,
+                table = table.name,
+            );
+
+            sqlx::query(&query).bind(table.name).execute(&mut *conn).await?;
+        }
+    }
+
+    Ok(evicted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::capture::begin_sync_write;
+    use crate::sync::test_cache_pool;
+
+    fn seller_scope(seller: &str) -> TableScope {
+        TableScope {
+            table_name: "companies".to_string(),
+            skip_pull: false,
+            max_age_days: None,
+            age_column: None,
+            seller_user_id: Some(seller.to_string()),
+        }
+    }
+
+    fn companies_of(seller: &str) -> ReplicationScope {
+        let mut scope = ReplicationScope::full("ws-1");
+        scope.tables.push(seller_scope(seller));
+        scope
+    }
+
+    #[tokio::test]
+    async fn other_sellers_rows_are_evicted_unless_edited_locally() {
+        let pool = test_cache_pool().await;
+        let registry = SyncTableRegistry::new();
+        let companies = registry.require("companies").unwrap();
+
+        let mut tx = begin_sync_write(&pool).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO users (id, email, name) VALUES ('u1', 'u1@example.com', 'One'), ('u2', 'u2@example.com', 'Two');
+             INSERT INTO companies (id, workspace_id, name, main_seller_id, is_dirty) VALUES
+                 ('mine', 'ws-1', 'Mine', 'u1', 0),
+                 ('theirs', 'ws-1', 'Theirs', 'u2', 0),
+                 ('edited', 'ws-1', 'Edited', 'u2', 1);",
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let scope = companies_of("u1");
+        let filter = ScopeFilter::load(&mut tx, &scope, &registry).await.unwrap();
+        let evicted = evict_out_of_scope(&mut tx, companies, &[], &filter, true).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(evicted, 1);
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM companies ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["edited", "mine"]);
+    }
+
+    #[tokio::test]
+    async fn companies_kept_people_still_reference_are_not_evicted() {
+        let pool = test_cache_pool().await;
+        let registry = SyncTableRegistry::new();
+        let companies = registry.require("companies").unwrap();
+
+        let mut tx = begin_sync_write(&pool).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO users (id, email, name) VALUES ('u1', 'u1@example.com', 'One'), ('u2', 'u2@example.com', 'Two');
+             INSERT INTO companies (id, workspace_id, name, main_seller_id, is_dirty) VALUES
+                 ('referenced', 'ws-1', 'Referenced', 'u2', 0),
+                 ('unreferenced', 'ws-1', 'Unreferenced', 'u2', 0);
+             INSERT INTO people (id, workspace_id, company_id, first_name, last_name, full_name, main_seller_id) VALUES
+                 ('p1', 'ws-1', 'referenced', 'Pat', 'Lee', 'Pat Lee', 'u1');",
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let scope = companies_of("u1");
+        let filter = ScopeFilter::load(&mut tx, &scope, &registry).await.unwrap();
+        let evicted = evict_out_of_scope(&mut tx, companies, &registry.children("companies"), &filter, true)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(evicted, 1);
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM companies ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["referenced"]);
+    }
+
+    #[tokio::test]
+    async fn people_of_companies_outside_the_scope_are_evicted() {
+        let pool = test_cache_pool().await;
+        let registry = SyncTableRegistry::new();
+        let people = registry.require("people").unwrap();
+
+        let mut tx = begin_sync_write(&pool).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO users (id, email, name) VALUES ('u1', 'u1@example.com', 'One'), ('u2', 'u2@example.com', 'Two');
+             INSERT INTO companies (id, workspace_id, name, main_seller_id) VALUES
+                 ('mine', 'ws-1', 'Mine', 'u1'),
+                 ('theirs', 'ws-1', 'Theirs', 'u2');
+             INSERT INTO people (id, workspace_id, company_id, first_name, last_name, full_name) VALUES
+                 ('p1', 'ws-1', 'mine', 'Pat', 'Lee', 'Pat Lee'),
+                 ('p2', 'ws-1', 'theirs', 'Sam', 'Lee', 'Sam Lee'),
+                 ('p3', 'ws-1', NULL, 'Kim', 'Lee', 'Kim Lee');",
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+        let scope = companies_of("u1");
+        let filter = ScopeFilter::load(&mut tx, &scope, &registry).await.unwrap();
+        let evicted = evict_out_of_scope(&mut tx, people, &registry.children("people"), &filter, true)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(evicted, 1);
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM people ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["p1", "p3"]);
+    }
+
+    #[test]
+    fn a_seller_scope_needs_a_seller_column() {
+        let registry = SyncTableRegistry::new();
+        let mut scope = ReplicationScope::full("ws-1");
+        scope.tables.push(TableScope {
+            table_name: "workspaces".to_string(),
+            ..seller_scope("u1")
+        });
+
+        assert!(matches!(scope.validate(&registry), Err(SyncError::Configuration(_))));
+    }
+}
@@ -0,0 +1,161 @@
+// ====================================================================
+// SYNC SETTINGS STORE
+// ====================================================================
+//
+// This module persists sync engine settings in the SQLite cache so
+// that the background sync cadence, conflict strategy, target
+// workspace and replication scopes survive app restarts.
+// ====================================================================
+
+use super::models::*;
+use sqlx::{Row, SqlitePool};
+
+const SYNC_CONFIG_KEY: &str = "sync_config";
+const BACKGROUND_WORKSPACE_KEY: &str = "background_sync_workspace_id";
+const REPLICATION_SCOPE_KEY_PREFIX: &str = "replication_scope:";
+
+pub struct SyncSettingsStore {
+    pool: SqlitePool,
+}
+
+impl SyncSettingsStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the persisted sync config, falling back to defaults
+    pub async fn load_config(&self) -> Result<SyncConfig, sqlx::Error> {
+        let value = self.get_value(SYNC_CONFIG_KEY).await?;
+
+        match value {
+            Some(json) => match serde_json::from_str::<SyncConfig>(&json) {
+                Ok(config) => Ok(config),
+                Err(e) => {
+                    println!("⚠️ [SYNC SETTINGS] Stored sync config is invalid, using defaults: {}", e);
+                    Ok(SyncConfig::default())
+                }
+            },
+            None => Ok(SyncConfig::default()),
+        }
+    }
+
+    /// Persist the sync config
+    pub async fn save_config(&self, config: &SyncConfig) -> Result<(), sqlx::Error> {
+        let json = serde_json::to_string(config)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize sync config: {}", e)))?;
+
+        self.set_value(SYNC_CONFIG_KEY, &json).await
+    }
+
+    /// Get the workspace the background sync runs against
+    pub async fn get_background_workspace(&self) -> Result<Option<String>, sqlx::Error> {
+        self.get_value(BACKGROUND_WORKSPACE_KEY).await
+    }
+
+    /// Set (or clear) the workspace the background sync runs against
+    pub async fn set_background_workspace(&self, workspace_id: Option<&str>) -> Result<(), sqlx::Error> {
+        match workspace_id {
+            Some(workspace_id) => self.set_value(BACKGROUND_WORKSPACE_KEY, workspace_id).await,
+            None => self.delete_value(BACKGROUND_WORKSPACE_KEY).await,
+        }
+    }
+
+    /// Load a workspace's replication scope, replicating everything when none is saved
+    pub async fn load_replication_scope(&self, workspace_id: &str) -> Result<ReplicationScope, sqlx::Error> {
+        let key = format!("{}{}", REPLICATION_SCOPE_KEY_PREFIX, workspace_id);
+
+        match self.get_value(&key).await? {
+            Some(json) => match serde_json::from_str::<ReplicationScope>(&json) {
+                Ok(scope) => Ok(scope),
+                Err(e) => {
+                    println!("⚠️ [SYNC SETTINGS] Stored replication scope is invalid, replicating everything: {}", e);
+                    Ok(ReplicationScope::full(workspace_id))
+                }
+            },
+            None => Ok(ReplicationScope::full(workspace_id)),
+        }
+    }
+
+    /// Persist a workspace's replication scope
+    pub async fn save_replication_scope(&self, scope: &ReplicationScope) -> Result<(), sqlx::Error> {
+        let key = format!("{}{}", REPLICATION_SCOPE_KEY_PREFIX, scope.workspace_id);
+        let json = serde_json::to_string(scope)
+            .map_err(|e| sqlx::Error::Protocol(format!("Failed to serialize replication scope: {}", e)))?;
+
+        self.set_value(&key, &json).await
+    }
+
+    // ====================================================================
+    // PRIVATE HELPER METHODS
+    // ====================================================================
+
+    async fn get_value(&self, key: &str) -> Result<Option<String>, sqlx::Error> {
+        let query = r#"
+            SELECT value
+            FROM sync_settings
+            WHERE key = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(key)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| r.get::<String, _>("value")))
+    }
+
+    async fn set_value(&self, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        let query = r#"
+            INSERT INTO sync_settings (key, value, updated_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(key)
+            .bind(value)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn delete_value(&self, key: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_settings WHERE key = ?")
+            .bind(key)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+
+    #[tokio::test]
+    async fn settings_survive_reopening_the_store() {
+        let pool = test_cache_pool().await;
+
+        let store = SyncSettingsStore::new(pool.clone());
+        let config = SyncConfig {
+            sync_interval_minutes: 15,
+            enable_background_sync: false,
+            ..SyncConfig::default()
+        };
+        store.save_config(&config).await.unwrap();
+        store.set_background_workspace(Some("ws-1")).await.unwrap();
+
+        let reopened = SyncSettingsStore::new(pool);
+        let loaded = reopened.load_config().await.unwrap();
+        assert_eq!(loaded.sync_interval_minutes, 15);
+        assert!(!loaded.enable_background_sync);
+        assert_eq!(reopened.get_background_workspace().await.unwrap().as_deref(), Some("ws-1"));
+
+        reopened.set_background_workspace(None).await.unwrap();
+        assert_eq!(reopened.get_background_workspace().await.unwrap(), None);
+    }
+}
@@ -0,0 +1,112 @@
+// ====================================================================
+// SHARED SYNC ENGINE
+// ====================================================================
+//
+// This module holds the single `SyncEngine` the app works with. It is
+// registered in Tauri-managed state and shared by the sync commands
+// and the background scheduler, so every sync goes through the same
+// lock and can be cancelled from the frontend.
+// ====================================================================
+
+use super::*;
+use crate::database::migrations;
+use crate::database_init::get_database_manager;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::RwLock;
+
+#[derive(Clone)]
+pub struct SharedSyncEngine {
+    engine: Arc<RwLock<Option<Arc<SyncEngine>>>>,
+    control: Arc<SyncControl>,
+    connectivity: Arc<ConnectivityState>,
+    app_handle: Arc<OnceLock<tauri::AppHandle<tauri::Wry>>>,
+}
+
+impl SharedSyncEngine {
+    pub fn new() -> Self {
+        Self {
+            engine: Arc::new(RwLock::new(None)),
+            control: Arc::new(SyncControl::new()),
+            connectivity: Arc::new(ConnectivityState::new()),
+            app_handle: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Give engines an app handle so they can emit progress events
+    pub fn attach_app_handle(&self, app_handle: tauri::AppHandle<tauri::Wry>) {
+        let _ = self.app_handle.set(app_handle);
+    }
+
+    /// Get the engine, building it from the persisted settings on first use
+    pub async fn get(&self) -> Result<Arc<SyncEngine>, SyncError> {
+        if let Some(engine) = self.engine.read().await.as_ref() {
+            return Ok(engine.clone());
+        }
+
+        let mut slot = self.engine.write().await;
+        if let Some(engine) = slot.as_ref() {
+            return Ok(engine.clone());
+        }
+
+        let db_manager = get_database_manager().map_err(SyncError::Configuration)?;
+        let sqlite_pool = db_manager.get_sqlite_pool().await.map_err(SyncError::Configuration)?;
+        let postgres_pool = db_manager.get_postgres_pool().await.map_err(SyncError::Configuration)?;
+        require_current_schema(&sqlite_pool).await?;
+        let config = SyncSettingsStore::new(sqlite_pool.clone()).load_config().await?;
+
+        let events = Arc::new(SyncEventEmitter::new(self.app_handle.get().cloned()));
+        let engine = Arc::new(
+            SyncEngine::new(sqlite_pool, postgres_pool, config)
+                .with_control(self.control.clone())
+                .with_connectivity(self.connectivity.clone())
+                .with_events(events),
+        );
+        *slot = Some(engine.clone());
+
+        println!("🔧 [SYNC] Sync engine initialized from saved settings");
+        Ok(engine)
+    }
+
+    /// Drop the cached engine so the next `get` picks up changed settings
+    ///
+    /// A sync already running on the old engine keeps the shared lock, so
+    /// the rebuilt engine still waits for it.
+    pub async fn reload(&self) {
+        *self.engine.write().await = None;
+    }
+
+    /// Connectivity as last seen by the `ConnectivityMonitor`
+    pub fn connectivity(&self) -> Arc<ConnectivityState> {
+        self.connectivity.clone()
+    }
+
+    /// Check whether a sync is currently running
+    pub fn is_sync_in_progress(&self) -> bool {
+        self.control.is_running()
+    }
+
+    /// Ask the running sync to stop at the next batch boundary
+    pub fn cancel(&self) -> bool {
+        self.control.request_cancel()
+    }
+}
+
+/// The engine reads the sync tables added by the migrations, so it refuses
+/// a cache.db that startup didn't bring up to date
+async fn require_current_schema(pool: &SqlitePool) -> Result<(), SyncError> {
+    let version = migrations::applied_version(pool).await?;
+    if version != migrations::latest_version() {
+        return Err(SyncError::Configuration(format!(
+            "cache.db is at schema version {} but sync needs version {}; restart the app to migrate it",
+            version,
+            migrations::latest_version()
+        )));
+    }
+    Ok(())
+}
+
+impl Default for SharedSyncEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
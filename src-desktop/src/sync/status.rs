@@ -7,7 +7,7 @@
 // ====================================================================
 
 use super::models::*;
-use sqlx::SqlitePool;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 use std::collections::HashMap;
 
 pub struct SyncStatusManager {
@@ -40,7 +40,7 @@ impl SyncStatusManager {
     /// Get sync status for all tables
     pub async fn get_all_table_status(&self) -> Result<Vec<TableSyncStatus>, sqlx::Error> {
         let query = r#"
-            SELECT table_name, last_incremental_sync, total_records, synced_records, 
+            SELECT table_name, last_incremental_sync AS last_sync, total_records, synced_records, 
                    pending_records, failed_records,
                    CASE 
                        WHEN failed_records > 0 THEN 'ERROR'
@@ -168,7 +168,161 @@ impl SyncStatusManager {
             .fetch_optional(&self.pool)
             .await?;
 
-        Ok(row.map(|r| r.get::<String, _>("last_incremental_sync")))
+        Ok(row.and_then(|r| r.get::<Option<String>, _>("last_incremental_sync")))
+    }
+
+    /// Get the pull watermark for a table
+    pub async fn get_watermark(&self, table_name: &str) -> Result<Option<SyncWatermark>, sqlx::Error> {
+        let query = r#"
+            SELECT table_name, last_sync_timestamp, last_sync_version, record_count, cursor
+            FROM sync_watermarks
+            WHERE table_name = ?
+        "#;
+
+        let row = sqlx::query(query)
+            .bind(table_name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(|r| SyncWatermark {
+            table_name: r.get::<String, _>("table_name"),
+            last_sync_timestamp: r.get::<String, _>("last_sync_timestamp"),
+            last_sync_version: r.get::<i32, _>("last_sync_version"),
+            record_count: r.get::<i32, _>("record_count"),
+            cursor: r.get::<Option<String>, _>("cursor"),
+        }))
+    }
+
+    /// Save the pull watermark for a table, inside the transaction that applied its page
+    pub async fn save_watermark(&self, conn: &mut SqliteConnection, watermark: &SyncWatermark) -> Result<(), sqlx::Error> {
+        let query = r#"
+            INSERT INTO sync_watermarks (table_name, last_sync_timestamp, last_sync_version, record_count, cursor, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(table_name) DO UPDATE SET
+                last_sync_timestamp = excluded.last_sync_timestamp,
+                last_sync_version = excluded.last_sync_version,
+                record_count = excluded.record_count,
+                cursor = excluded.cursor,
+                updated_at = excluded.updated_at
+        "#;
+
+        sqlx::query(query)
+            .bind(&watermark.table_name)
+            .bind(&watermark.last_sync_timestamp)
+            .bind(watermark.last_sync_version)
+            .bind(watermark.record_count)
+            .bind(&watermark.cursor)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set aside a pulled change that failed to apply, inside its page's transaction
+    pub async fn record_pull_failure(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record: &SyncRecord,
+        error_message: &str,
+    ) -> Result<(), sqlx::Error> {
+        let query = r#"
+            INSERT INTO sync_pull_failures (
+                table_name, record_id, operation, data, sync_version, last_modified,
+                error_message, attempts, first_failed_at, last_failed_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
+            ON CONFLICT(table_name, record_id) DO UPDATE SET
+                operation = excluded.operation,
+                data = excluded.data,
+                sync_version = excluded.sync_version,
+                last_modified = excluded.last_modified,
+                error_message = excluded.error_message,
+                attempts = sync_pull_failures.attempts + 1,
+                last_failed_at = excluded.last_failed_at
+        "#;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(query)
+            .bind(table_name)
+            .bind(&record.id)
+            .bind(&record.operation)
+            .bind(&record.data)
+            .bind(record.sync_version)
+            .bind(&record.last_modified)
+            .bind(error_message)
+            .bind(&now)
+            .bind(&now)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Drop a set-aside change once it, or a newer version of its record, has applied
+    pub async fn clear_pull_failure(
+        &self,
+        conn: &mut SqliteConnection,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_pull_failures WHERE table_name = ? AND record_id = ?")
+            .bind(table_name)
+            .bind(record_id)
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get the set-aside changes of a table, oldest first
+    pub async fn get_pull_failures(&self, table_name: &str) -> Result<Vec<PullFailure>, sqlx::Error> {
+        let query = r#"
+            SELECT table_name, record_id, operation, data, sync_version, last_modified,
+                   error_message, attempts, first_failed_at, last_failed_at
+            FROM sync_pull_failures
+            WHERE table_name = ?
+            ORDER BY first_failed_at ASC, record_id ASC
+        "#;
+
+        let rows = sqlx::query(query)
+            .bind(table_name)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| PullFailure {
+                table_name: r.get("table_name"),
+                record: SyncRecord {
+                    id: r.get("record_id"),
+                    operation: r.get("operation"),
+                    data: r.get("data"),
+                    sync_version: r.get("sync_version"),
+                    last_modified: r.get("last_modified"),
+                },
+                error_message: r.get("error_message"),
+                attempts: r.get("attempts"),
+                first_failed_at: r.get("first_failed_at"),
+                last_failed_at: r.get("last_failed_at"),
+            })
+            .collect())
+    }
+
+    /// Forget where a table's pulls left off, so the next pull starts from the beginning
+    pub async fn reset_pull_position(&self, table_name: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM sync_watermarks WHERE table_name = ?")
+            .bind(table_name)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("UPDATE sync_status SET last_incremental_sync = NULL WHERE table_name = ?")
+            .bind(table_name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
     }
 
     /// Get last global sync timestamp
@@ -343,7 +497,7 @@ impl SyncStatusManager {
             FROM sync_status
         "#;
 
-        let row = sqlx::query(query)
+        let _row = sqlx::query(query)
             .fetch_one(&self.pool)
             .await?;
 
@@ -354,6 +508,10 @@ impl SyncStatusManager {
             compression_ratio: 1.0, // Would be calculated during actual sync
             memory_usage_mb: 0.0, // Would be measured during actual sync
             cpu_usage_percent: 0.0, // Would be measured during actual sync
+            request_bytes: 0, // Filled in from the engine's transfer stats
+            request_bytes_uncompressed: 0,
+            response_bytes: 0,
+            response_bytes_uncompressed: 0,
         })
     }
 }
@@ -422,3 +580,36 @@ impl SyncStatusManager {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::test_cache_pool;
+
+    async fn save(status: &SyncStatusManager, watermark: &SyncWatermark) {
+        let mut conn = status.pool.acquire().await.unwrap();
+        status.save_watermark(&mut conn, watermark).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn watermark_cursor_is_saved_and_cleared() {
+        let status = SyncStatusManager::new(test_cache_pool().await);
+        assert!(status.get_watermark("companies").await.unwrap().is_none());
+
+        let mut watermark = SyncWatermark {
+            table_name: "companies".to_string(),
+            last_sync_timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            last_sync_version: 3,
+            record_count: 10,
+            cursor: Some("page-2".to_string()),
+        };
+        save(&status, &watermark).await;
+        let saved = status.get_watermark("companies").await.unwrap().unwrap();
+        assert_eq!(saved.cursor.as_deref(), Some("page-2"));
+        assert_eq!(saved.record_count, 10);
+
+        watermark.cursor = None;
+        save(&status, &watermark).await;
+        assert_eq!(status.get_watermark("companies").await.unwrap().unwrap().cursor, None);
+    }
+}
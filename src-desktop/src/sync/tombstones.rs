@@ -0,0 +1,415 @@
+// ====================================================================
+// SYNC TOMBSTONES
+// ====================================================================
+//
+// This module keeps a tombstone for every deleted record, so a delete
+// can't be undone by an older remote write that arrives after it. A
+// tombstone holds when the record was deleted, the version of the
+// delete and its hybrid timestamp. Tables with a `deleted_at` column are
+// soft-deleted and keep the row next to its tombstone; other tables
+// lose the row straight away.
+//
+// - Local deletes are recorded by the capture triggers (see `tombstone_trigger_sql`)
+// - Pulled deletes and deletes that win a conflict go through `apply_delete`
+// - Pulled writes a tombstone supersedes are skipped
+// - Once every device has pulled past a tombstone and the retention
+//   period is over, `purge_tombstones` drops it and its soft-deleted row
+// ====================================================================
+
+use super::clock::{self, HybridTimestamp};
+use super::models::SyncRecord;
+use super::registry::SyncTableRegistry;
+use sqlx::{Row, SqliteConnection};
+
+/// Tombstones are kept at least this long, even once every device has them
+pub const TOMBSTONE_RETENTION_DAYS: u32 = 30;
+
+/// Column that marks a row as soft-deleted
+pub const SOFT_DELETE_COLUMN: &str = "deleted_at";
+
+/// Current time as an SQLite expression, in the format the capture log uses
+const SQL_NOW: &str = "strftime('%Y-%m-%dT%H:%M:%fZ', 'now')";
+
+#[derive(Debug, Clone)]
+pub struct Tombstone {
+    pub deleted_at: String,
+    pub sync_version: i32,
+    pub hlc: Option<String>,
+}
+
+impl Tombstone {
+    /// Whether the delete happened after a remote write, so the write must not bring the record back
+    ///
+    /// Clocks are compared when both sides have one. Otherwise the delete
+    /// time is compared with the write's `updated_at` (or, without one, when
+    /// the server last modified it); sync versions are counted per server
+    /// and can't order writes from other devices. A write that can't be
+    /// ordered is let through.
+    pub fn supersedes(&self, change: &SyncRecord, clock: Option<&HybridTimestamp>) -> bool {
+        if let (Some(deleted), Some(written)) = (HybridTimestamp::parse_opt(self.hlc.as_deref()), clock) {
+            return deleted >= *written;
+        }
+
+        let updated_at = serde_json::from_str::<serde_json::Value>(&change.data)
+            .ok()
+            .and_then(|data| data.get("updated_at")?.as_str().and_then(clock::parse_write_time));
+        let written_at = updated_at.or_else(|| clock::parse_write_time(&change.last_modified));
+
+        match (clock::parse_write_time(&self.deleted_at), written_at) {
+            (Some(deleted_at), Some(written_at)) => deleted_at >= written_at,
+            _ => false,
+        }
+    }
+}
+
+/// Get a record's tombstone
+pub async fn find_tombstone(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    record_id: &str,
+) -> Result<Option<Tombstone>, sqlx::Error> {
+    let row = sqlx::query("SELECT deleted_at, sync_version, hlc FROM sync_tombstones WHERE table_name = ? AND record_id = ?")
+        .bind(table_name)
+        .bind(record_id)
+        .fetch_optional(&mut *conn)
+        .await?;
+
+    Ok(row.map(|r| Tombstone {
+        deleted_at: r.get("deleted_at"),
+        sync_version: r.get("sync_version"),
+        hlc: r.get("hlc"),
+    }))
+}
+
+/// Drop a record's tombstone once a newer write has brought it back
+pub async fn clear_tombstone(conn: &mut SqliteConnection, table_name: &str, record_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM sync_tombstones WHERE table_name = ? AND record_id = ?")
+        .bind(table_name)
+        .bind(record_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Delete a record the server already knows is deleted, leaving a tombstone
+///
+/// With `soft_delete` the row keeps its `deleted_at` until it is purged.
+/// `deleted_at` is when the server deleted the record, if known; clock-less
+/// writes are ordered against it.
+#[allow(clippy::too_many_arguments)]
+pub async fn apply_delete(
+    conn: &mut SqliteConnection,
+    table_name: &str,
+    primary_key: &str,
+    record_id: &str,
+    soft_delete: bool,
+    sync_version: i32,
+    clock: Option<&HybridTimestamp>,
+    deleted_at: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let deleted_at = deleted_at
+        .and_then(clock::parse_write_time)
+        .map(|deleted_at| deleted_at.to_rfc3339())
+        .unwrap_or_else(|| now.clone());
+
+    if soft_delete {
+        let query = format!(
+            "UPDATE {table} SET {column} = COALESCE({column}, ?) WHERE {pk} = ?",
+            table = table_name,
+            column = SOFT_DELETE_COLUMN,
+            pk = primary_key
+        );
+
+        sqlx::query(&query).bind(&deleted_at).bind(record_id).execute(&mut *conn).await?;
+    } else {
+        let query = format!("DELETE FROM {} WHERE {} = ?", table_name, primary_key);
+
+        sqlx::query(&query).bind(record_id).execute(&mut *conn).await?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, hlc, acknowledged_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(table_name, record_id) DO UPDATE SET
+            sync_version = MAX(sync_tombstones.sync_version, excluded.sync_version),
+            hlc = COALESCE(excluded.hlc, sync_tombstones.hlc),
+            acknowledged_at = excluded.acknowledged_at
+        "#,
+    )
+    .bind(table_name)
+    .bind(record_id)
+    .bind(&deleted_at)
+    .bind(sync_version)
+    .bind(clock.map(|clock| clock.to_string()))
+    .bind(&now)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Mark a pushed record's delete as received by the server
+///
+/// Left alone while newer local changes to the record are still waiting.
+pub async fn acknowledge_tombstone(conn: &mut SqliteConnection, table_name: &str, record_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE sync_tombstones SET acknowledged_at = ?
+        WHERE table_name = ? AND record_id = ?
+          AND NOT EXISTS (
+              SELECT 1 FROM sync_queue
+              WHERE table_name = ? AND record_id = ? AND status IN ('PENDING', 'FAILED', 'IN_PROGRESS')
+          )
+          AND NOT EXISTS (
+              SELECT 1 FROM sync_change_log WHERE table_name = ? AND record_id = ?
+          )
+        "#,
+    )
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(table_name)
+    .bind(record_id)
+    .bind(table_name)
+    .bind(record_id)
+    .bind(table_name)
+    .bind(record_id)
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Mark a record's delete as not yet received by the server
+///
+/// For deletes recorded with `apply_delete` that still have to be pushed,
+/// so the tombstone can't be purged before the server has it.
+pub async fn unacknowledge_tombstone(conn: &mut SqliteConnection, table_name: &str, record_id: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE sync_tombstones SET acknowledged_at = NULL WHERE table_name = ? AND record_id = ?")
+        .bind(table_name)
+        .bind(record_id)
+        .execute(&mut *conn)
+        .await?;
+
+    Ok(())
+}
+
+/// Trigger statements that keep tombstones in step with local writes
+///
+/// Runs after the capture trigger has stamped the record's clock, so the
+/// tombstone takes the clock of the delete. Deletes and soft deletes
+/// record a tombstone one version past the row; writes that leave the row
+/// live drop it.
+pub fn tombstone_trigger_sql(table_name: &str, primary_key: &str, event: &str, soft_delete: bool, has_version: bool) -> String {
+    let row = if event == "DELETE" { "OLD" } else { "NEW" };
+    let version = if has_version {
+        format!("COALESCE({}.sync_version, 0) + 1", row)
+    } else {
+        "0".to_string()
+    };
+
+    let record = |deleted_at: String, condition: &str| {
+        format!(
+            r#"INSERT INTO sync_tombstones (table_name, record_id, deleted_at, sync_version, hlc)
+                    SELECT '{table}', {row}.{pk}, {deleted_at}, {version},
+                        (SELECT hlc FROM sync_record_clocks WHERE table_name = '{table}' AND record_id = {row}.{pk})
+                    WHERE {condition}
+                    ON CONFLICT(table_name, record_id) DO UPDATE SET
+                        deleted_at = excluded.deleted_at,
+                        sync_version = excluded.sync_version,
+                        hlc = excluded.hlc,
+                        acknowledged_at = NULL;"#,
+            table = table_name,
+            row = row,
+            pk = primary_key,
+            deleted_at = deleted_at,
+            version = version,
+            condition = condition,
+        )
+    };
+    let clear = |condition: &str| {
+        format!(
+            "DELETE FROM sync_tombstones WHERE table_name = '{}' AND record_id = {}.{} AND {};",
+            table_name, row, primary_key, condition
+        )
+    };
+
+    match (event, soft_delete) {
+        ("DELETE", true) => record(format!("COALESCE(OLD.{}, {})", SOFT_DELETE_COLUMN, SQL_NOW), "1"),
+        ("DELETE", false) => record(SQL_NOW.to_string(), "1"),
+        ("UPDATE", true) => format!(
+            "{}\n                    {}",
+            record(
+                format!("NEW.{}", SOFT_DELETE_COLUMN),
+                &format!("NEW.{column} IS NOT NULL AND OLD.{column} IS NULL", column = SOFT_DELETE_COLUMN)
+            ),
+            clear(&format!("NEW.{} IS NULL", SOFT_DELETE_COLUMN))
+        ),
+        (_, true) => clear(&format!("NEW.{} IS NULL", SOFT_DELETE_COLUMN)),
+        (_, false) => clear("1"),
+    }
+}
+
+/// Purge tombstones every device has pulled past, with their soft-deleted rows
+///
+/// `horizon` is the earliest point all of the workspace's devices have
+/// pulled through. Tombstones younger than the retention period are kept
+/// regardless, as are soft-deleted rows edited since the delete. Must run
+/// in a sync write so the purge isn't captured and pushed. Returns the
+/// number of tombstones purged.
+pub async fn purge_tombstones(
+    conn: &mut SqliteConnection,
+    registry: &SyncTableRegistry,
+    horizon: &str,
+) -> Result<u64, sqlx::Error> {
+    let purgeable = format!(
+        r#"
+        SELECT record_id FROM sync_tombstones
+        WHERE table_name = ?
+          AND acknowledged_at IS NOT NULL
+          AND julianday(acknowledged_at) < MIN(julianday(?), julianday('now', '-{} days'))
+        "#,
+        TOMBSTONE_RETENTION_DAYS
+    );
+    let mut purged = 0;
+
+    for table in registry.tables() {
+        let columns: Vec<String> = sqlx::query(&format!("PRAGMA table_info({})", table.name))
+            .fetch_all(&mut *conn)
+            .await?
+            .iter()
+            .map(|column| column.get::<String, _>("name"))
+            .collect();
+
+        if columns.is_empty() {
+            continue;
+        }
+
+        // Soft-deleted rows go with their tombstones; rows that stay keep theirs
+        let keep_remaining = if columns.iter().any(|column| column == SOFT_DELETE_COLUMN) {
+            let dirty_guard = if columns.iter().any(|column| column == "is_dirty") {
+                "AND COALESCE(is_dirty, 0) = 0"
+            } else {
+                ""
+            };
+
+            sqlx::query(&format!(
+                "DELETE FROM {table} WHERE {column} IS NOT NULL {dirty_guard} AND {pk} IN ({purgeable})",
+                table = table.name,
+                column = SOFT_DELETE_COLUMN,
+                dirty_guard = dirty_guard,
+                pk = table.primary_key,
+                purgeable = purgeable,
+            ))
+            .bind(table.name)
+            .bind(horizon)
+            .execute(&mut *conn)
+            .await?;
+
+            format!("AND record_id NOT IN (SELECT {} FROM {})", table.primary_key, table.name)
+        } else {
+            String::new()
+        };
+
+        // Merge bases and clocks of purged records are no longer needed
+        for bookkeeping in ["sync_base_versions", "sync_record_clocks"] {
+            sqlx::query(&format!(
+                "DELETE FROM {bookkeeping} WHERE table_name = ? AND record_id IN ({purgeable}) {keep_remaining}",
+                bookkeeping = bookkeeping,
+                purgeable = purgeable,
+                keep_remaining = keep_remaining,
+            ))
+            .bind(table.name)
+            .bind(table.name)
+            .bind(horizon)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        purged += sqlx::query(&format!(
+            "DELETE FROM sync_tombstones WHERE table_name = ? AND record_id IN ({purgeable}) {keep_remaining}",
+            purgeable = purgeable,
+            keep_remaining = keep_remaining,
+        ))
+        .bind(table.name)
+        .bind(table.name)
+        .bind(horizon)
+        .execute(&mut *conn)
+        .await?
+        .rows_affected();
+    }
+
+    Ok(purged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::capture::begin_sync_write;
+    use crate::sync::models::SyncOperation;
+    use crate::sync::test_cache_pool;
+
+    fn write(sync_version: i32, updated_at: Option<&str>, last_modified: &str) -> SyncRecord {
+        SyncRecord {
+            id: "c1".to_string(),
+            operation: SyncOperation::Update,
+            data: serde_json::json!({ "id": "c1", "updated_at": updated_at }).to_string(),
+            sync_version,
+            last_modified: last_modified.to_string(),
+        }
+    }
+
+    fn tombstone() -> Tombstone {
+        Tombstone {
+            deleted_at: "2026-03-01T00:00:00+00:00".to_string(),
+            sync_version: 4,
+            hlc: None,
+        }
+    }
+
+    #[test]
+    fn a_clockless_delete_supersedes_writes_made_before_it() {
+        assert!(tombstone().supersedes(&write(9, Some("2026-02-01 00:00:00"), "2026-04-01T00:00:00+00:00"), None));
+
+        // Without an updated_at, the time the server last modified the record decides
+        assert!(tombstone().supersedes(&write(0, None, "2026-02-01T00:00:00+00:00"), None));
+        assert!(!tombstone().supersedes(&write(0, None, "2026-04-01T00:00:00+00:00"), None));
+    }
+
+    #[test]
+    fn a_clockless_update_made_after_the_delete_is_let_through() {
+        // A lower version from another server's counter doesn't make the update older
+        assert!(!tombstone().supersedes(&write(2, Some("2026-03-02T00:00:00Z"), "2026-03-02T00:00:00Z"), None));
+    }
+
+    #[tokio::test]
+    async fn old_acknowledged_tombstones_are_purged_with_their_rows() {
+        let pool = test_cache_pool().await;
+        let registry = SyncTableRegistry::new();
+
+        let mut tx = begin_sync_write(&pool).await.unwrap();
+        sqlx::raw_sql(
+            "INSERT INTO workspaces (id, name, slug) VALUES ('ws-1', 'Workspace', 'ws-1');
+             INSERT INTO companies (id, workspace_id, name, deleted_at) VALUES
+                 ('old', 'ws-1', 'Old', '2026-01-01T00:00:00Z'),
+                 ('recent', 'ws-1', 'Recent', datetime('now'));
+             INSERT INTO sync_tombstones (table_name, record_id, deleted_at, acknowledged_at) VALUES
+                 ('companies', 'old', '2026-01-01T00:00:00Z', datetime('now', '-60 days')),
+                 ('companies', 'recent', datetime('now'), datetime('now'));",
+        )
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+
+        let purged = purge_tombstones(&mut tx, &registry, &chrono::Utc::now().to_rfc3339()).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(purged, 1);
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT id FROM companies")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, vec!["recent"]);
+    }
+}
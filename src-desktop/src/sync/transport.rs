@@ -0,0 +1,1164 @@
+// ====================================================================
+// SYNC TRANSPORTS
+// ====================================================================
+//
+// This module defines how the sync engine talks to the remote side.
+// `SyncTransport` covers the calls a sync needs (push a batch, pull a
+// page, acknowledge pulls, health check) and has three implementations:
+// - HttpTransport: The Adrata sync API at `remote_api_base`
+// - PostgresTransport: Direct Postgres access for internal admin builds
+//   (behind the `direct-postgres-sync` feature)
+// - InMemoryTransport: A deterministic fake server for exercising the
+//   push/pull/conflict logic without the network
+// ====================================================================
+
+use super::*;
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// One page of the remote change feed
+pub struct PulledPage {
+    pub records: Vec<SyncRecord>,
+    /// Entries that could not be parsed, as "id (error)"
+    pub malformed: Vec<String>,
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+#[async_trait::async_trait]
+pub trait SyncTransport: Send + Sync {
+    /// Push a batch of queued changes
+    ///
+    /// Returns a verdict per change, matched by client mutation ID. An `Err`
+    /// means the batch as a whole never got a verdict.
+    async fn push_batch(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        changes: &[SyncQueueItem],
+    ) -> Result<Vec<PushRecordResult>, SyncError>;
+
+    /// Pull one page of remote changes after `cursor`
+    ///
+    /// `since` is only used when there is no cursor, for tables synced before
+    /// cursors existed or whose feed was read to the end without one. Rows
+    /// outside `scope` are not returned.
+    async fn pull_page(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        cursor: Option<&str>,
+        since: Option<&str>,
+        scope: Option<&TableScope>,
+        limit: u32,
+    ) -> Result<PulledPage, SyncError>;
+
+    /// Fetch the server's current copy of one record
+    ///
+    /// A record the server deleted comes back as a delete; `None` when the
+    /// server never had it.
+    async fn fetch_record(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        record_id: &str,
+    ) -> Result<Option<SyncRecord>, SyncError>;
+
+    /// Report that this device has pulled every change made before `pulled_through`
+    ///
+    /// Returns the earliest such point across the workspace's devices, so
+    /// deletes acknowledged before it have reached every device. `None`
+    /// when the remote side doesn't track devices.
+    async fn acknowledge_pulls(
+        &self,
+        workspace_id: &str,
+        device_id: &str,
+        pulled_through: &str,
+    ) -> Result<Option<String>, SyncError>;
+
+    /// Check whether the remote side is reachable
+    async fn health_check(&self) -> bool;
+}
+
+// ====================================================================
+// HTTP TRANSPORT
+// ====================================================================
+
+pub struct HttpTransport {
+    client: Client,
+    api_base: String,
+    compression: Option<CompressionCodec>,
+    transfer_stats: Arc<TransferStats>,
+}
+
+impl HttpTransport {
+    pub fn new(config: &SyncConfig, transfer_stats: Arc<TransferStats>) -> Self {
+        let client = Client::builder()
+            .timeout(std::time::Duration::from_secs(config.timeout_seconds as u64))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            api_base: config.remote_api_base.clone(),
+            compression: config.enable_compression.then_some(config.compression_codec),
+            transfer_stats,
+        }
+    }
+
+    /// Convert sync queue items to API format
+    fn convert_changes_to_api_format(&self, changes: &[SyncQueueItem]) -> Result<Value, SyncError> {
+        let mut batch = Vec::new();
+
+        for change in changes {
+            let mut record = serde_json::Map::new();
+            record.insert("id".to_string(), Value::String(change.record_id.clone()));
+            record.insert("client_mutation_id".to_string(), Value::String(change.client_mutation_id.clone()));
+            record.insert("operation".to_string(), Value::String(format!("{:?}", change.operation)));
+
+            if let Some(data) = &change.data {
+                record.insert("data".to_string(), serde_json::from_str(data)?);
+            }
+
+            batch.push(Value::Object(record));
+        }
+
+        Ok(Value::Array(batch))
+    }
+
+    /// Read a JSON response body, decompressing it and recording its size
+    async fn read_json_response(&self, response: reqwest::Response) -> Result<Value, SyncError> {
+        let content_encoding = response
+            .headers()
+            .get(reqwest::header::CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+
+        let wire_body = response
+            .bytes()
+            .await
+            .map_err(|e| SyncError::Network(format!("Failed to read response body: {}", e)))?;
+        let body = decompress_body(content_encoding.as_deref(), &wire_body)?;
+        self.transfer_stats.record_response(wire_body.len(), body.len());
+
+        serde_json::from_slice(&body)
+            .map_err(|e| SyncError::Network(format!("Invalid response body: {}", e)))
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncTransport for HttpTransport {
+    async fn push_batch(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        changes: &[SyncQueueItem],
+    ) -> Result<Vec<PushRecordResult>, SyncError> {
+        // Convert changes to API format
+        let batch_data = self.convert_changes_to_api_format(changes)?;
+
+        let body = serde_json::to_vec(&batch_data)?;
+        let uncompressed_len = body.len();
+
+        // Send to server
+        let url = format!("{}/sync/{}/{}", self.api_base, workspace_id, table_name);
+        let mut request = self.client
+            .post(&url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+
+        let body = match self.compression {
+            Some(codec) => {
+                request = request.header(reqwest::header::CONTENT_ENCODING, codec.content_encoding());
+                codec.compress(&body)?
+            }
+            None => body,
+        };
+        self.transfer_stats.record_request(body.len(), uncompressed_len);
+
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(SyncError::Network(format!("Server error: {}", error_text)));
+        }
+
+        // Parse response
+        let response_data = self.read_json_response(response).await?;
+
+        // Servers without per-record results accept or fail a batch as a whole
+        let results = match response_data.get("results") {
+            Some(results) => serde_json::from_value::<Vec<PushRecordResult>>(results.clone())
+                .map_err(|e| SyncError::Network(format!("Invalid push results: {}", e)))?,
+            None => changes
+                .iter()
+                .map(|change| PushRecordResult {
+                    client_mutation_id: change.client_mutation_id.clone(),
+                    status: PushStatus::Accepted,
+                    reason: None,
+                    remote: None,
+                })
+                .collect(),
+        };
+
+        Ok(results)
+    }
+
+    async fn pull_page(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        cursor: Option<&str>,
+        since: Option<&str>,
+        scope: Option<&TableScope>,
+        limit: u32,
+    ) -> Result<PulledPage, SyncError> {
+        let url = format!("{}/{}/{}", self.api_base, workspace_id, table_name);
+
+        let mut query: Vec<(&str, String)> = vec![("limit", limit.to_string())];
+        match (cursor, since) {
+            (Some(cursor), _) => query.push(("cursor", cursor.to_string())),
+            (None, Some(timestamp)) => query.push(("since", timestamp.to_string())),
+            (None, None) => {}
+        }
+
+        if let Some(scope) = scope {
+            if let Some(cutoff) = scope.age_cutoff() {
+                query.push(("scope_age_column", scope.age_column().to_string()));
+                query.push(("scope_after", cutoff));
+            }
+            if let Some(seller) = &scope.seller_user_id {
+                query.push(("scope_seller_id", seller.clone()));
+            }
+        }
+
+        let mut request = self.client.get(&url).query(&query);
+        if self.compression.is_some() {
+            request = request.header(reqwest::header::ACCEPT_ENCODING, ACCEPT_ENCODING);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(SyncError::Network(format!("Server error: {}", error_text)));
+        }
+
+        let response_data = self.read_json_response(response).await?;
+
+        // Parse response into SyncRecord objects, keeping track of the ones that don't parse
+        let mut page = PulledPage {
+            records: Vec::new(),
+            malformed: Vec::new(),
+            next_cursor: response_data["next_cursor"].as_str().map(|c| c.to_string()),
+            has_more: response_data["has_more"].as_bool().unwrap_or(false),
+        };
+
+        if let Some(data_array) = response_data["data"].as_array() {
+            for item in data_array {
+                match serde_json::from_value::<SyncRecord>(item.clone()) {
+                    Ok(record) => page.records.push(record),
+                    Err(e) => {
+                        let record_id = item["id"].as_str().unwrap_or("<unknown id>");
+                        page.malformed.push(format!("{} ({})", record_id, e));
+                    }
+                }
+            }
+        }
+
+        Ok(page)
+    }
+
+    async fn fetch_record(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        record_id: &str,
+    ) -> Result<Option<SyncRecord>, SyncError> {
+        let url = format!("{}/{}/{}/{}", self.api_base, workspace_id, table_name, record_id);
+
+        let mut request = self.client.get(&url);
+        if self.compression.is_some() {
+            request = request.header(reqwest::header::ACCEPT_ENCODING, ACCEPT_ENCODING);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(SyncError::Network(format!("Server error: {}", error_text)));
+        }
+
+        let response_data = self.read_json_response(response).await?;
+        let record = serde_json::from_value::<SyncRecord>(response_data)?;
+        Ok(Some(record))
+    }
+
+    async fn acknowledge_pulls(
+        &self,
+        workspace_id: &str,
+        device_id: &str,
+        pulled_through: &str,
+    ) -> Result<Option<String>, SyncError> {
+        let url = format!("{}/sync/{}/devices/{}/ack", self.api_base, workspace_id, device_id);
+
+        let response = self.client
+            .post(&url)
+            .json(&serde_json::json!({ "pulled_through": pulled_through }))
+            .send()
+            .await
+            .map_err(|e| SyncError::Network(format!("HTTP request failed: {}", e)))?;
+
+        // Servers that predate device tracking don't have the endpoint
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_else(|_| "Unknown error".to_string());
+            return Err(SyncError::Network(format!("Server error: {}", error_text)));
+        }
+
+        let response_data = self.read_json_response(response).await?;
+        Ok(response_data["horizon"].as_str().map(|horizon| horizon.to_string()))
+    }
+
+    async fn health_check(&self) -> bool {
+        match self.client.get(format!("{}/health", self.api_base)).send().await {
+            Ok(response) => response.status().is_success(),
+            Err(_) => false,
+        }
+    }
+}
+
+// ====================================================================
+// DIRECT POSTGRES TRANSPORT
+// ====================================================================
+//
+// Reads and writes the shared Postgres database directly, for internal
+// admin builds that hold database credentials. Tables share their names
+// with the local cache; columns use the camelCase names of the web app
+// schema. Writes are upserts and soft deletes, so replaying a change is
+// harmless and no conflicts are reported: the latest push wins.
+
+#[cfg(feature = "direct-postgres-sync")]
+pub struct PostgresTransport {
+    pool: sqlx::PgPool,
+    table_registry: SyncTableRegistry,
+}
+
+#[cfg(feature = "direct-postgres-sync")]
+impl PostgresTransport {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self {
+            pool,
+            table_registry: SyncTableRegistry::new(),
+        }
+    }
+
+    /// Write one change inside the batch transaction
+    async fn apply_change(
+        &self,
+        conn: &mut sqlx::PgConnection,
+        table: &SyncTable,
+        change: &SyncQueueItem,
+    ) -> Result<(), SyncError> {
+        if change.operation == SyncOperation::Delete {
+            let query = if table.has_column("deleted_at") {
+                // Bumping updatedAt moves the delete into other devices' pull feeds
+                let touch = if table.has_column("updated_at") { r#", "updatedAt" = now()"# } else { "" };
+                format!(
+                    r#"UPDATE "{}" SET "deletedAt" = now(){} WHERE "{}" = $1"#,
+                    table.name,
+                    touch,
+                    snake_to_camel(table.primary_key)
+                )
+            } else {
+                format!(r#"DELETE FROM "{}" WHERE "{}" = $1"#, table.name, snake_to_camel(table.primary_key))
+            };
+
+            sqlx::query(&query).bind(&change.record_id).execute(&mut *conn).await?;
+            return Ok(());
+        }
+
+        let data: serde_json::Map<String, Value> = match &change.data {
+            Some(data) => serde_json::from_str(data)?,
+            None => return Err(SyncError::Configuration(format!("Change {} has no data to write", change.id))),
+        };
+
+        // Only known columns are written; the primary key always comes from the queue row
+        let mut row = serde_json::Map::new();
+        row.insert(snake_to_camel(table.primary_key), Value::String(change.record_id.clone()));
+        for (key, value) in data {
+            if key != table.primary_key && table.has_column(&key) {
+                row.insert(snake_to_camel(&key), value);
+            }
+        }
+
+        let columns: Vec<String> = row.keys().map(|column| format!(r#""{}""#, column)).collect();
+        let updates: Vec<String> = columns
+            .iter()
+            .filter(|column| **column != format!(r#""{}""#, snake_to_camel(table.primary_key)))
+            .map(|column| format!("{} = EXCLUDED.{}", column, column))
+            .collect();
+
+        let on_conflict = if updates.is_empty() {
+            "DO NOTHING".to_string()
+        } else {
+            format!("DO UPDATE SET {}", updates.join(", "))
+        };
+
+        let query = format!(
+            r#"
+            INSERT INTO "{table}" ({columns})
+            SELECT {columns} FROM json_populate_record(NULL::"{table}", $1::json)
+            ON CONFLICT ("{pk}") {on_conflict}
+            "#,
+            table = table.name,
+            columns = columns.join(", "),
+            pk = snake_to_camel(table.primary_key),
+            on_conflict = on_conflict,
+        );
+
+        sqlx::query(&query)
+            .bind(Value::Object(row).to_string())
+            .execute(&mut *conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "direct-postgres-sync")]
+#[async_trait::async_trait]
+impl SyncTransport for PostgresTransport {
+    async fn push_batch(
+        &self,
+        table_name: &str,
+        _workspace_id: &str,
+        changes: &[SyncQueueItem],
+    ) -> Result<Vec<PushRecordResult>, SyncError> {
+        use sqlx::Connection;
+
+        let table = self.table_registry.require(table_name)?;
+        let mut tx = self.pool.begin().await?;
+        let mut results = Vec::with_capacity(changes.len());
+
+        for change in changes {
+            // Each change gets a savepoint so one bad row only rejects itself
+            let mut savepoint = tx.begin().await?;
+
+            let outcome = match self.apply_change(&mut savepoint, table, change).await {
+                Ok(()) => {
+                    savepoint.commit().await?;
+                    PushRecordResult {
+                        client_mutation_id: change.client_mutation_id.clone(),
+                        status: PushStatus::Accepted,
+                        reason: None,
+                        remote: None,
+                    }
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    PushRecordResult {
+                        client_mutation_id: change.client_mutation_id.clone(),
+                        status: PushStatus::Rejected,
+                        reason: Some(e.to_string()),
+                        remote: None,
+                    }
+                }
+            };
+
+            results.push(outcome);
+        }
+
+        tx.commit().await?;
+        Ok(results)
+    }
+
+    async fn pull_page(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        cursor: Option<&str>,
+        since: Option<&str>,
+        scope: Option<&TableScope>,
+        limit: u32,
+    ) -> Result<PulledPage, SyncError> {
+        use sqlx::Row;
+
+        let table = self.table_registry.require(table_name)?;
+
+        // Cursors are "<updatedAt>|<id>" of the last row served
+        let (after_time, after_id) = match (cursor, since) {
+            (Some(cursor), _) => match cursor.split_once('|') {
+                Some((time, id)) => (Some(time.to_string()), id.to_string()),
+                None => return Err(SyncError::Configuration(format!("Invalid cursor for {}: {}", table_name, cursor))),
+            },
+            (None, Some(since)) => (Some(since.to_string()), String::new()),
+            (None, None) => (None, String::new()),
+        };
+
+        // Replication scope; its columns were checked against the registry when it was saved
+        let age_column = snake_to_camel(scope.map(|scope| scope.age_column()).unwrap_or("updated_at"));
+        let seller_filter = if !table.has_column(MAIN_SELLER_COLUMN) {
+            "$6::text IS NULL".to_string()
+        } else {
+            match scope.and_then(|scope| scope.co_sellers()) {
+                Some((co_seller_table, foreign_key)) => format!(
+                    r#"($6::text IS NULL OR t."mainSellerId" = $6 OR EXISTS (SELECT 1 FROM "{}" cs WHERE cs."{}" = t."{}" AND cs."userId" = $6))"#,
+                    co_seller_table,
+                    snake_to_camel(foreign_key),
+                    snake_to_camel(table.primary_key),
+                ),
+                None => r#"($6::text IS NULL OR t."mainSellerId" = $6)"#.to_string(),
+            }
+        };
+
+        let query = format!(
+            r#"
+            SELECT row_to_json(t)::text AS data,
+                   t."{pk}"::text AS record_id,
+                   t."updatedAt"::text AS updated_at,
+                   t."createdAt" = t."updatedAt" AS is_new,
+                   {is_deleted} AS is_deleted
+            FROM "{table}" t
+            WHERE {workspace_filter}
+              AND ($2::text IS NULL OR (t."updatedAt", t."{pk}"::text) > ($2::timestamp, $3::text))
+              AND ($5::timestamptz IS NULL OR t."{age_column}" >= $5::timestamptz)
+              AND {seller_filter}
+            ORDER BY t."updatedAt", t."{pk}"
+            LIMIT $4
+            "#,
+            table = table.name,
+            pk = snake_to_camel(table.primary_key),
+            workspace_filter = workspace_filter(table),
+            is_deleted = deleted_condition(table),
+            age_column = age_column,
+            seller_filter = seller_filter,
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(workspace_id)
+            .bind(after_time)
+            .bind(after_id)
+            .bind(limit as i64)
+            .bind(scope.and_then(|scope| scope.age_cutoff()))
+            .bind(scope.and_then(|scope| scope.seller_user_id.clone()))
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut page = PulledPage {
+            records: Vec::new(),
+            malformed: Vec::new(),
+            next_cursor: None,
+            has_more: rows.len() as u32 >= limit,
+        };
+
+        for row in &rows {
+            let record_id = row.get::<String, _>("record_id");
+            let updated_at = row.get::<String, _>("updated_at");
+
+            match snake_case_fields(&row.get::<String, _>("data")) {
+                Ok(data) => {
+                    page.records.push(SyncRecord {
+                        id: record_id.clone(),
+                        operation: pulled_operation(row.get("is_deleted"), row.get("is_new")),
+                        data,
+                        sync_version: 0, // Postgres rows carry no sync version
+                        last_modified: updated_at.clone(),
+                    });
+                }
+                Err(e) => page.malformed.push(format!("{} ({})", record_id, e)),
+            }
+
+            page.next_cursor = Some(format!("{}|{}", updated_at, record_id));
+        }
+
+        Ok(page)
+    }
+
+    async fn fetch_record(
+        &self,
+        table_name: &str,
+        workspace_id: &str,
+        record_id: &str,
+    ) -> Result<Option<SyncRecord>, SyncError> {
+        use sqlx::Row;
+
+        let table = self.table_registry.require(table_name)?;
+
+        let query = format!(
+            r#"
+            SELECT row_to_json(t)::text AS data,
+                   t."updatedAt"::text AS updated_at,
+                   {is_deleted} AS is_deleted
+            FROM "{table}" t
+            WHERE {workspace_filter} AND t."{pk}"::text = $2
+            "#,
+            table = table.name,
+            pk = snake_to_camel(table.primary_key),
+            workspace_filter = workspace_filter(table),
+            is_deleted = deleted_condition(table),
+        );
+
+        let row = sqlx::query(&query)
+            .bind(workspace_id)
+            .bind(record_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(SyncRecord {
+            id: record_id.to_string(),
+            operation: pulled_operation(row.get("is_deleted"), false),
+            data: snake_case_fields(&row.get::<String, _>("data"))?,
+            sync_version: 0, // Postgres rows carry no sync version
+            last_modified: row.get("updated_at"),
+        }))
+    }
+
+    async fn acknowledge_pulls(
+        &self,
+        _workspace_id: &str,
+        _device_id: &str,
+        _pulled_through: &str,
+    ) -> Result<Option<String>, SyncError> {
+        // The web app schema has no device registry, so tombstones are never known to be everywhere
+        Ok(None)
+    }
+
+    async fn health_check(&self) -> bool {
+        sqlx::query("SELECT 1").execute(&self.pool).await.is_ok()
+    }
+}
+
+/// Condition limiting a web app table to one workspace, bound as `$1`
+#[cfg(feature = "direct-postgres-sync")]
+fn workspace_filter(table: &SyncTable) -> &'static str {
+    if table.has_column("workspace_id") {
+        r#"t."workspaceId" = $1"#
+    } else if table.name == "workspaces" {
+        r#"t."id" = $1"#
+    } else {
+        "$1::text IS NOT NULL"
+    }
+}
+
+/// Whether a web app row is soft-deleted; tables without `deletedAt` delete rows outright
+#[cfg(feature = "direct-postgres-sync")]
+fn deleted_condition(table: &SyncTable) -> &'static str {
+    if table.has_column("deleted_at") {
+        r#"t."deletedAt" IS NOT NULL"#
+    } else {
+        "false"
+    }
+}
+
+/// The operation a pulled web app row stands for
+#[cfg(feature = "direct-postgres-sync")]
+fn pulled_operation(is_deleted: bool, is_new: bool) -> SyncOperation {
+    if is_deleted {
+        SyncOperation::Delete
+    } else if is_new {
+        SyncOperation::Insert
+    } else {
+        SyncOperation::Update
+    }
+}
+
+/// Rename the fields of a web app row to the desktop's column names
+#[cfg(feature = "direct-postgres-sync")]
+fn snake_case_fields(data: &str) -> Result<String, serde_json::Error> {
+    let object = serde_json::from_str::<serde_json::Map<String, Value>>(data)?;
+    let fields: serde_json::Map<String, Value> = object
+        .into_iter()
+        .map(|(key, value)| (camel_to_snake(&key), value))
+        .collect();
+
+    Ok(Value::Object(fields).to_string())
+}
+
+#[cfg(feature = "direct-postgres-sync")]
+fn snake_to_camel(name: &str) -> String {
+    let mut camel = String::with_capacity(name.len());
+    let mut upper_next = false;
+
+    for c in name.chars() {
+        if c == '_' {
+            upper_next = true;
+        } else if upper_next {
+            camel.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            camel.push(c);
+        }
+    }
+
+    camel
+}
+
+#[cfg(feature = "direct-postgres-sync")]
+fn camel_to_snake(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for c in name.chars() {
+        if c.is_uppercase() {
+            snake.push('_');
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+// ====================================================================
+// IN-MEMORY TRANSPORT
+// ====================================================================
+//
+// A fake server that keeps records in memory. Each write gets the next
+// sequence number, and pull cursors are the last sequence served, so
+// runs are fully deterministic. Records changed through `remote_upsert`
+// or `remote_delete` (another client) and not pulled since conflict
+// when this client pushes them.
+
+#[derive(Debug, Clone)]
+struct StoredRecord {
+    data: String,
+    sync_version: i32,
+    deleted: bool,
+    seq: u64,
+    created_seq: u64,
+    /// Changed by another client since this client last pulled it
+    unseen_remote_change: bool,
+}
+
+#[derive(Default)]
+struct InMemoryState {
+    tables: HashMap<String, HashMap<String, StoredRecord>>,
+    next_seq: u64,
+    applied_mutations: HashSet<String>,
+    rejections: HashMap<(String, String), String>,
+    /// Latest `pulled_through` per (workspace, device)
+    pulled_through: HashMap<(String, String), String>,
+}
+
+impl InMemoryState {
+    fn write(&mut self, table_name: &str, id: &str, data: Option<String>, from_client: bool) {
+        self.next_seq += 1;
+        let seq = self.next_seq;
+
+        let table = self.tables.entry(table_name.to_string()).or_default();
+        let record = table.entry(id.to_string()).or_insert_with(|| StoredRecord {
+            data: "{}".to_string(),
+            sync_version: 0,
+            deleted: false,
+            seq,
+            created_seq: seq,
+            unseen_remote_change: false,
+        });
+
+        match data {
+            Some(data) => {
+                record.data = merge_json(&record.data, &data);
+                record.deleted = false;
+            }
+            None => record.deleted = true,
+        }
+        record.sync_version += 1;
+        record.seq = seq;
+        record.unseen_remote_change = !from_client;
+    }
+}
+
+pub struct InMemoryTransport {
+    state: Mutex<InMemoryState>,
+    online: AtomicBool,
+}
+
+impl InMemoryTransport {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(InMemoryState::default()),
+            online: AtomicBool::new(true),
+        }
+    }
+
+    /// Take the fake server offline; every call then fails with a network error
+    pub fn set_online(&self, online: bool) {
+        self.online.store(online, Ordering::SeqCst);
+    }
+
+    /// Create or update a record as another client would
+    pub fn remote_upsert(&self, table_name: &str, id: &str, data: &str) {
+        self.lock().write(table_name, id, Some(data.to_string()), false);
+    }
+
+    /// Delete a record as another client would
+    pub fn remote_delete(&self, table_name: &str, id: &str) {
+        self.lock().write(table_name, id, None, false);
+    }
+
+    /// Reject every push of this record with the given reason
+    pub fn reject_record(&self, table_name: &str, id: &str, reason: &str) {
+        self.lock()
+            .rejections
+            .insert((table_name.to_string(), id.to_string()), reason.to_string());
+    }
+
+    /// Accept pushes of a record rejected with `reject_record` again
+    pub fn accept_record(&self, table_name: &str, id: &str) {
+        self.lock().rejections.remove(&(table_name.to_string(), id.to_string()));
+    }
+
+    /// Current server copy of a record, `None` if missing or deleted
+    pub fn record(&self, table_name: &str, id: &str) -> Option<String> {
+        self.lock()
+            .tables
+            .get(table_name)
+            .and_then(|table| table.get(id))
+            .filter(|record| !record.deleted)
+            .map(|record| record.data.clone())
+    }
+
+    /// Record that another device has pulled everything made before `pulled_through`
+    pub fn device_pulled_through(&self, workspace_id: &str, device_id: &str, pulled_through: &str) {
+        self.lock()
+            .pulled_through
+            .insert((workspace_id.to_string(), device_id.to_string()), pulled_through.to_string());
+    }
+
+    /// Number of distinct mutations the server has applied
+    pub fn applied_mutation_count(&self) -> usize {
+        self.lock().applied_mutations.len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, InMemoryState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn ensure_online(&self) -> Result<(), SyncError> {
+        if !self.online.load(Ordering::SeqCst) {
+            return Err(SyncError::Network("In-memory server is offline".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl Default for InMemoryTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl SyncTransport for InMemoryTransport {
+    async fn push_batch(
+        &self,
+        table_name: &str,
+        _workspace_id: &str,
+        changes: &[SyncQueueItem],
+    ) -> Result<Vec<PushRecordResult>, SyncError> {
+        self.ensure_online()?;
+        let mut state = self.lock();
+        let mut results = Vec::with_capacity(changes.len());
+
+        for change in changes {
+            let result = |status, reason: Option<String>, remote| PushRecordResult {
+                client_mutation_id: change.client_mutation_id.clone(),
+                status,
+                reason,
+                remote,
+            };
+
+            // A retried mutation is acknowledged without being applied again
+            if state.applied_mutations.contains(&change.client_mutation_id) {
+                results.push(result(PushStatus::Accepted, None, None));
+                continue;
+            }
+
+            let key = (table_name.to_string(), change.record_id.clone());
+            if let Some(reason) = state.rejections.get(&key) {
+                results.push(result(PushStatus::Rejected, Some(reason.clone()), None));
+                continue;
+            }
+
+            let existing = state
+                .tables
+                .get(table_name)
+                .and_then(|table| table.get(&change.record_id))
+                .cloned();
+
+            if let Some(existing) = existing.filter(|record| record.unseen_remote_change) {
+                let remote = SyncRecord {
+                    id: change.record_id.clone(),
+                    operation: if existing.deleted { SyncOperation::Delete } else { SyncOperation::Update },
+                    data: existing.data.clone(),
+                    sync_version: existing.sync_version,
+                    last_modified: SyncUtils::current_timestamp(),
+                };
+                results.push(result(PushStatus::Conflict, Some("Record changed on the server".to_string()), Some(remote)));
+                continue;
+            }
+
+            let data = match change.operation {
+                SyncOperation::Delete => None,
+                SyncOperation::Insert | SyncOperation::Update => Some(change.data.clone().unwrap_or_else(|| "{}".to_string())),
+            };
+            state.write(table_name, &change.record_id, data, true);
+            state.applied_mutations.insert(change.client_mutation_id.clone());
+            results.push(result(PushStatus::Accepted, None, None));
+        }
+
+        Ok(results)
+    }
+
+    async fn pull_page(
+        &self,
+        table_name: &str,
+        _workspace_id: &str,
+        cursor: Option<&str>,
+        _since: Option<&str>,
+        scope: Option<&TableScope>,
+        limit: u32,
+    ) -> Result<PulledPage, SyncError> {
+        self.ensure_online()?;
+        let mut state = self.lock();
+
+        let after = match cursor {
+            Some(cursor) => cursor
+                .parse::<u64>()
+                .map_err(|_| SyncError::Configuration(format!("Invalid cursor for {}: {}", table_name, cursor)))?,
+            None => 0,
+        };
+
+        let mut changed: Vec<(&String, &mut StoredRecord)> = state
+            .tables
+            .get_mut(table_name)
+            .map(|table| {
+                table
+                    .iter_mut()
+                    .filter(|(_, record)| record.seq > after && in_scope(&record.data, scope))
+                    .collect()
+            })
+            .unwrap_or_default();
+        changed.sort_by_key(|(_, record)| record.seq);
+
+        // An empty page still returns its position, so the end of the feed isn't re-read
+        let has_more = changed.len() > limit as usize;
+        let mut page = PulledPage {
+            records: Vec::new(),
+            malformed: Vec::new(),
+            next_cursor: Some(after.to_string()),
+            has_more,
+        };
+
+        for (id, record) in changed.into_iter().take(limit as usize) {
+            // The client has now seen this version, so pushing over it is no conflict
+            record.unseen_remote_change = false;
+
+            let operation = if record.deleted {
+                SyncOperation::Delete
+            } else if record.created_seq == record.seq {
+                SyncOperation::Insert
+            } else {
+                SyncOperation::Update
+            };
+
+            page.records.push(SyncRecord {
+                id: id.clone(),
+                operation,
+                data: record.data.clone(),
+                sync_version: record.sync_version,
+                last_modified: SyncUtils::current_timestamp(),
+            });
+            page.next_cursor = Some(record.seq.to_string());
+        }
+
+        Ok(page)
+    }
+
+    async fn fetch_record(
+        &self,
+        table_name: &str,
+        _workspace_id: &str,
+        record_id: &str,
+    ) -> Result<Option<SyncRecord>, SyncError> {
+        self.ensure_online()?;
+        let mut state = self.lock();
+
+        let Some(record) = state.tables.get_mut(table_name).and_then(|table| table.get_mut(record_id)) else {
+            return Ok(None);
+        };
+        record.unseen_remote_change = false;
+
+        Ok(Some(SyncRecord {
+            id: record_id.to_string(),
+            operation: if record.deleted { SyncOperation::Delete } else { SyncOperation::Update },
+            data: record.data.clone(),
+            sync_version: record.sync_version,
+            last_modified: SyncUtils::current_timestamp(),
+        }))
+    }
+
+    async fn acknowledge_pulls(
+        &self,
+        workspace_id: &str,
+        device_id: &str,
+        pulled_through: &str,
+    ) -> Result<Option<String>, SyncError> {
+        self.ensure_online()?;
+        let mut state = self.lock();
+
+        state
+            .pulled_through
+            .insert((workspace_id.to_string(), device_id.to_string()), pulled_through.to_string());
+
+        let horizon = state
+            .pulled_through
+            .iter()
+            .filter(|((workspace, _), _)| workspace == workspace_id)
+            .filter_map(|(_, pulled_through)| chrono::DateTime::parse_from_rfc3339(pulled_through).ok())
+            .min();
+
+        Ok(horizon.map(|horizon| horizon.to_rfc3339()))
+    }
+
+    async fn health_check(&self) -> bool {
+        self.online.load(Ordering::SeqCst)
+    }
+}
+
+/// Whether a stored record falls inside a replication scope
+///
+/// The fake server has no co-sellers, so only the main seller is checked.
+fn in_scope(data: &str, scope: Option<&TableScope>) -> bool {
+    let scope = match scope {
+        Some(scope) => scope,
+        None => return true,
+    };
+    let data: Value = serde_json::from_str(data).unwrap_or(Value::Null);
+
+    if scope.skip_pull {
+        return false;
+    }
+
+    if let Some(cutoff) = scope.age_cutoff().and_then(|c| chrono::DateTime::parse_from_rfc3339(&c).ok()) {
+        let age = data.get(scope.age_column()).and_then(Value::as_str);
+        if let Some(age) = age.and_then(|age| chrono::DateTime::parse_from_rfc3339(age).ok()) {
+            if age < cutoff {
+                return false;
+            }
+        }
+    }
+
+    match &scope.seller_user_id {
+        Some(seller) => data.get(MAIN_SELLER_COLUMN).and_then(Value::as_str) == Some(seller.as_str()),
+        None => true,
+    }
+}
+
+/// Overlay the fields of `update` onto `current`, the way partial pushes apply
+fn merge_json(current: &str, update: &str) -> String {
+    match (
+        serde_json::from_str::<Value>(current),
+        serde_json::from_str::<Value>(update),
+    ) {
+        (Ok(Value::Object(mut current)), Ok(Value::Object(update))) => {
+            current.extend(update);
+            Value::Object(current).to_string()
+        }
+        _ => update.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queued_insert(record_id: &str, name: &str) -> SyncQueueItem {
+        SyncQueueItem {
+            id: 1,
+            table_name: "companies".to_string(),
+            record_id: record_id.to_string(),
+            operation: SyncOperation::Insert,
+            data: Some(serde_json::json!({ "id": record_id, "name": name }).to_string()),
+            created_at: SyncUtils::current_timestamp(),
+            synced_at: None,
+            error_message: None,
+            retry_count: 0,
+            next_retry_at: None,
+            status: SyncQueueStatus::Pending,
+            client_mutation_id: super::queue::new_client_mutation_id(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_replayed_mutation_is_applied_once() {
+        let server = InMemoryTransport::new();
+        let change = queued_insert("c1", "Acme");
+
+        server.push_batch("companies", "ws-1", std::slice::from_ref(&change)).await.unwrap();
+        // As if the acknowledgement was lost and the push retried
+        let replay = server.push_batch("companies", "ws-1", std::slice::from_ref(&change)).await.unwrap();
+
+        assert_eq!(replay[0].status, PushStatus::Accepted);
+        assert_eq!(server.applied_mutation_count(), 1);
+        assert!(server.record("companies", "c1").unwrap().contains("Acme"));
+    }
+
+    #[tokio::test]
+    async fn pages_continue_from_the_cursor_until_the_feed_is_read() {
+        let server = InMemoryTransport::new();
+        for id in ["c1", "c2", "c3"] {
+            server.remote_upsert("companies", id, &serde_json::json!({ "id": id }).to_string());
+        }
+
+        let first = server.pull_page("companies", "ws-1", None, None, None, 2).await.unwrap();
+        assert_eq!(first.records.len(), 2);
+        assert!(first.has_more);
+
+        let last = server.pull_page("companies", "ws-1", first.next_cursor.as_deref(), None, None, 2).await.unwrap();
+        assert_eq!(last.records[0].id, "c3");
+        assert!(!last.has_more);
+
+        let caught_up = server.pull_page("companies", "ws-1", last.next_cursor.as_deref(), None, None, 2).await.unwrap();
+        assert!(caught_up.records.is_empty());
+        assert_eq!(caught_up.next_cursor, last.next_cursor);
+    }
+
+    #[cfg(feature = "direct-postgres-sync")]
+    #[test]
+    fn soft_deleted_web_app_rows_are_pulled_as_deletes() {
+        let registry = SyncTableRegistry::new();
+
+        assert_eq!(deleted_condition(registry.require("companies").unwrap()), r#"t."deletedAt" IS NOT NULL"#);
+        assert_eq!(deleted_condition(registry.require("email_messages").unwrap()), "false");
+
+        assert_eq!(pulled_operation(true, true), SyncOperation::Delete);
+        assert_eq!(pulled_operation(true, false), SyncOperation::Delete);
+        assert_eq!(pulled_operation(false, true), SyncOperation::Insert);
+        assert_eq!(pulled_operation(false, false), SyncOperation::Update);
+    }
+}